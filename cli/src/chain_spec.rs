@@ -24,7 +24,7 @@ use xp_protocol::{NetworkType, PCX, PCX_DECIMALS, X_BTC};
 use xpallet_gateway_bitcoin::{BtcParams, BtcTxVerifier};
 use xpallet_gateway_common::types::TrusteeInfoConfig;
 
-use crate::genesis::assets::{genesis_assets, init_assets, pcx, AssetParams};
+use crate::genesis::assets::{dev_genesis_assets, genesis_assets, init_assets, pcx, AssetParams};
 use crate::genesis::bitcoin::{btc_genesis_params, BtcGenesisParams, BtcTrusteeParams};
 
 use chainx_runtime as chainx;
@@ -140,7 +140,7 @@ pub fn development_config() -> Result<DevChainSpec, String> {
             vec![authority_keys_from_seed("Alice")],
             get_account_id_from_seed::<sr25519::Public>("Alice"),
             get_account_id_from_seed::<sr25519::Public>("vesting"),
-            genesis_assets(),
+            dev_genesis_assets(),
             endowed_gen![
                 ("Alice", endowed_balance),
                 ("Bob", endowed_balance),
@@ -148,7 +148,11 @@ pub fn development_config() -> Result<DevChainSpec, String> {
                 ("Bob//stash", endowed_balance),
             ],
             btc_genesis_params(include_str!("res/btc_genesis_params_testnet.json")),
-            crate::genesis::bitcoin::local_testnet_trustees(),
+            [
+                crate::genesis::bitcoin::local_testnet_trustees(),
+                crate::genesis::local::local_testnet_trustees(),
+            ]
+            .concat(),
         )
     };
     Ok(DevChainSpec::from_genesis(
@@ -214,7 +218,7 @@ pub fn local_testnet_config() -> Result<DevChainSpec, String> {
             ],
             get_account_id_from_seed::<sr25519::Public>("Alice"),
             get_account_id_from_seed::<sr25519::Public>("vesting"),
-            genesis_assets(),
+            dev_genesis_assets(),
             endowed_gen![
                 ("Alice", endowed_balance),
                 ("Bob", endowed_balance),
@@ -230,7 +234,11 @@ pub fn local_testnet_config() -> Result<DevChainSpec, String> {
                 ("Ferdie//stash", endowed_balance),
             ],
             btc_genesis_params(include_str!("res/btc_genesis_params_testnet.json")),
-            crate::genesis::bitcoin::local_testnet_trustees(),
+            [
+                crate::genesis::bitcoin::local_testnet_trustees(),
+                crate::genesis::local::local_testnet_trustees(),
+            ]
+            .concat(),
         )
     };
     Ok(DevChainSpec::from_genesis(
@@ -348,6 +356,21 @@ fn build_genesis(
             }
         })
         .expect("bitcoin trustees generation can not fail; qed");
+    let local_genesis_trustees = trustees
+        .iter()
+        .find_map(|(chain, _, trustee_params)| {
+            if *chain == Chain::Local {
+                Some(
+                    trustee_params
+                        .iter()
+                        .map(|i| (i.0).clone())
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
 
     dev::GenesisConfig {
         frame_system: Some(dev::SystemConfig {
@@ -412,6 +435,24 @@ fn build_genesis(
             max_withdrawal_count: 100,
             verifier: BtcTxVerifier::Recover,
         }),
+        xpallet_gateway_ethereum: Some(dev::XGatewayEthereumConfig {
+            genesis_header: vec![],
+            genesis_trustees: vec![],
+            confirmation_number: 12,
+            trustee_contract: Default::default(),
+        }),
+        xpallet_gateway_polkadot: Some(dev::XGatewayPolkadotConfig {
+            genesis_header: vec![],
+            genesis_authorities: vec![],
+            genesis_set_id: 0,
+            genesis_trustees: vec![],
+            withdrawal_fee: 100_000_000,
+            transfer_event_index: (5, 2),
+        }),
+        xpallet_gateway_local: Some(dev::XGatewayLocalConfig {
+            genesis_trustees: local_genesis_trustees,
+            withdrawal_fee: crate::genesis::local::LOCAL_WITHDRAWAL_FEE,
+        }),
         xpallet_mining_staking: Some(dev::XStakingConfig {
             validators,
             validator_count: 50,
@@ -1,6 +1,6 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use xp_protocol::{BTC_DECIMALS, PCX, PCX_DECIMALS, X_BTC};
+use xp_protocol::{BTC_DECIMALS, LOCAL_DECIMALS, PCX, PCX_DECIMALS, X_BTC, X_LOCAL};
 
 use chainx_runtime::{AssetId, AssetInfo, AssetRestrictions, Chain, Runtime};
 
@@ -54,6 +54,21 @@ pub(crate) fn xbtc() -> (AssetId, AssetInfo, AssetRestrictions) {
     )
 }
 
+pub(crate) fn xlocal() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_LOCAL,
+        AssetInfo::new::<Runtime>(
+            b"XLOCAL".to_vec(),
+            b"ChainX Local".to_vec(),
+            Chain::Local,
+            LOCAL_DECIMALS,
+            b"ChainX's dummy local chain asset, only for the development network".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
 // asset_id, asset_info, asset_restrictions, is_online, has_mining_rights
 pub(crate) fn genesis_assets() -> Vec<(AssetId, AssetInfo, AssetRestrictions, bool, bool)> {
     let pcx = pcx();
@@ -64,3 +79,11 @@ pub(crate) fn genesis_assets() -> Vec<(AssetId, AssetInfo, AssetRestrictions, bo
     ];
     assets
 }
+
+/// The genesis assets with the dummy local chain asset, for the development networks.
+pub(crate) fn dev_genesis_assets() -> Vec<(AssetId, AssetInfo, AssetRestrictions, bool, bool)> {
+    let local = xlocal();
+    let mut assets = genesis_assets();
+    assets.push((local.0, local.1, local.2, true, false));
+    assets
+}
@@ -0,0 +1,37 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_core::{ed25519, sr25519};
+
+use chainx_runtime::{Chain, TrusteeInfoConfig};
+
+use crate::chain_spec::{get_account_id_from_seed, get_from_seed};
+use crate::genesis::bitcoin::BtcTrusteeParams;
+
+/// The withdrawal fee of the dummy local chain asset.
+pub const LOCAL_WITHDRAWAL_FEE: u128 = 100_000;
+
+/// The trustee uses the ed25519 key derived from its seed as both the hot and cold key.
+fn local_trustee_gen(seed: &str) -> BtcTrusteeParams {
+    let key = get_from_seed::<ed25519::Public>(seed).0.to_vec();
+    (
+        get_account_id_from_seed::<sr25519::Public>(seed), // Account Id
+        seed.as_bytes().to_vec(),                          // Seed Bytes.
+        key.clone(),                                       // Hot Key
+        key,                                               // Cold Key
+    )
+}
+
+pub fn local_testnet_trustees() -> Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)> {
+    let local_config = TrusteeInfoConfig {
+        min_trustee_count: 3,
+        max_trustee_count: 15,
+    };
+
+    let local_trustees = vec![
+        local_trustee_gen("Alice"),
+        local_trustee_gen("Bob"),
+        local_trustee_gen("Charlie"),
+    ];
+
+    vec![(Chain::Local, local_config, local_trustees)]
+}
@@ -2,6 +2,7 @@
 
 pub mod assets;
 pub mod bitcoin;
+pub mod local;
 
 use xp_genesis_builder::AllParams;
 
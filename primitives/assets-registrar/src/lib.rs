@@ -14,11 +14,12 @@ use sp_std::slice::Iter;
 
 use chainx_primitives::AssetId;
 
-const CHAINS: [Chain; 4] = [
+const CHAINS: [Chain; 5] = [
     Chain::ChainX,
     Chain::Bitcoin,
     Chain::Ethereum,
     Chain::Polkadot,
+    Chain::Local,
 ];
 
 /// The blockchain types.
@@ -33,6 +34,8 @@ pub enum Chain {
     Ethereum,
     /// Polkadot
     Polkadot,
+    /// A dummy local chain, only for the tests and the development network.
+    Local,
 }
 
 impl Default for Chain {
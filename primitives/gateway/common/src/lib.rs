@@ -5,6 +5,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use sp_core::crypto::AccountId32;
 
 use xp_logging::error;
@@ -74,3 +80,40 @@ pub fn from_ss58_check(raw_account: &[u8]) -> Option<AccountId32> {
         Some(res.into())
     }
 }
+
+/// Encode the account as the SS58Check address with the given address `version`, which must be
+/// a simple one (less than 64), e.g. 44 for ChainX.
+///
+/// The address is encoded inside the runtime with an explicit version, so that the result
+/// doesn't depend on the default address version of the node.
+/// Same as `Ss58Codec::to_ss58check_with_version` in `substrate/primitives/core/src/crypto.rs`.
+pub fn to_ss58_check_with_version(account: &AccountId32, version: u8) -> Vec<u8> {
+    const PREFIX: &[u8] = b"SS58PRE";
+    let mut data = Vec::with_capacity(35);
+    data.push(version);
+    data.extend_from_slice(AsRef::<[u8]>::as_ref(account));
+    let mut hasher = blake2_rfc::blake2b::Blake2b::new(64);
+    hasher.update(PREFIX);
+    hasher.update(&data);
+    let hash = hasher.finalize();
+    data.extend_from_slice(&hash.as_bytes()[0..2]);
+    bs58::encode(data).into_string().into_bytes()
+}
+
+#[test]
+fn test_to_ss58_check_with_version() {
+    use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+
+    let account = AccountId32::from([7u8; 32]);
+    for version in [
+        Ss58AddressFormat::ChainXAccount,
+        Ss58AddressFormat::SubstrateAccount,
+    ]
+    .iter()
+    {
+        assert_eq!(
+            to_ss58_check_with_version(&account, (*version).into()),
+            account.to_ss58check_with_version(*version).into_bytes()
+        );
+    }
+}
@@ -46,12 +46,21 @@ pub const L_BTC: AssetId = 0x90000000 | X_BTC;
 
 /// ETH asset in ChainX backed by the Mainnet Ethereum.
 pub const X_ETH: AssetId = 60;
+/// Decimals of ETH.
+pub const ETH_DECIMALS: Decimals = 18;
 
 /// DOT asset in ChainX backed by the Mainnet Polkadot.
 pub const X_DOT: AssetId = 354;
+/// Decimals of DOT.
+pub const DOT_DECIMALS: Decimals = 10;
 /// Reserved since this symbol had been used in legacy ChainX 1.0.
 pub const S_DOT: AssetId = 0xa0000000 | X_DOT;
 
+/// Dummy asset backed by the local test chain, only for the tests and the development network.
+pub const X_LOCAL: AssetId = 0x02000000;
+/// Decimals of the dummy local asset.
+pub const LOCAL_DECIMALS: Decimals = 8;
+
 const EXTEND: AssetId = 0x01000000;
 /// USDT asset in ChainX.
 pub const USDT: AssetId = EXTEND | 0x01;
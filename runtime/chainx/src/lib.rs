@@ -17,7 +17,7 @@ use sp_api::impl_runtime_apis;
 use sp_core::{
     crypto::KeyTypeId,
     u32_trait::{_1, _2, _3, _4, _5},
-    OpaqueMetadata,
+    OpaqueMetadata, H256,
 };
 #[cfg(feature = "runtime-benchmarks")]
 use sp_runtime::RuntimeString;
@@ -80,14 +80,14 @@ pub use xpallet_assets::{
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
 pub use xpallet_gateway_bitcoin::{
-    hash_rev, BtcHeader, BtcNetwork, BtcParams, BtcTxVerifier, Compact as BtcCompact,
-    H256 as BtcHash,
+    hash_rev, BtcHeader, BtcNetwork, BtcParams, BtcReserveReport, BtcTransaction, BtcTrusteeUtxo,
+    BtcTxVerifier, BtcUtxo, Compact as BtcCompact, H256 as BtcHash,
 };
 pub use xpallet_gateway_common::{
     trustees,
     types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig},
 };
-pub use xpallet_gateway_records::Withdrawal;
+pub use xpallet_gateway_records::{DepositRecord, Withdrawal, WithdrawalHistory, WithdrawalState};
 pub use xpallet_mining_asset::MiningWeight;
 pub use xpallet_mining_staking::VoteWeight;
 
@@ -104,10 +104,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-net"),
     authoring_version: 1,
-    spec_version: 10,
+    spec_version: 11,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -302,8 +302,10 @@ impl pallet_babe::Trait for Runtime {
         pallet_babe::AuthorityId,
     )>>::IdentificationTuple;
 
-    type HandleEquivocation =
-        pallet_babe::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
+    type HandleEquivocation = pallet_babe::EquivocationHandler<
+        Self::KeyOwnerIdentification,
+        xpallet_mining_staking::EquivocationReporter<Self, Offences>,
+    >;
 
     type WeightInfo = ();
 }
@@ -318,7 +320,10 @@ impl pallet_grandpa::Trait for Runtime {
         GrandpaId,
     )>>::IdentificationTuple;
     type KeyOwnerProofSystem = Historical;
-    type HandleEquivocation = ();
+    type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+        Self::KeyOwnerIdentification,
+        xpallet_mining_staking::EquivocationReporter<Self, Offences>,
+    >;
 
     type WeightInfo = ();
 }
@@ -909,8 +914,19 @@ impl xpallet_assets::Trait for Runtime {
     type WeightInfo = xpallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const WithdrawalHistoryRetention: BlockNumber = 90 * DAYS;
+}
+
 impl xpallet_gateway_records::Trait for Runtime {
     type Event = Event;
+    // The council or the trustees multisig could freeze the delayed withdrawals.
+    type FreezeOrigin = EnsureOneOf<
+        AccountId,
+        EnsureRootOrHalfCouncil,
+        EnsureSignedBy<trustees::bitcoin::BtcTrusteeMultisig<Runtime>, AccountId>,
+    >;
+    type WithdrawalHistoryRetention = WithdrawalHistoryRetention;
     type WeightInfo = xpallet_gateway_records::weights::SubstrateWeight<Runtime>;
 }
 
@@ -927,6 +943,12 @@ impl xpallet_gateway_common::Trait for Runtime {
     type DetermineMultisigAddress = MultisigProvider;
     type Bitcoin = XGatewayBitcoin;
     type BitcoinTrustee = XGatewayBitcoin;
+    type Ethereum = trustees::ethereum::EthereumChainDisabled;
+    type EthereumTrustee = trustees::ethereum::EthereumChainDisabled;
+    type Polkadot = trustees::polkadot::PolkadotChainDisabled;
+    type PolkadotTrustee = trustees::polkadot::PolkadotChainDisabled;
+    type Local = trustees::local::LocalChainDisabled;
+    type LocalTrustee = trustees::local::LocalChainDisabled;
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
@@ -938,6 +960,7 @@ impl xpallet_gateway_bitcoin::Trait for Runtime {
     type TrusteeOrigin = EnsureSignedBy<trustees::bitcoin::BtcTrusteeMultisig<Runtime>, AccountId>;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type OffenderReporter = XStaking;
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
@@ -974,6 +997,7 @@ impl xpallet_mining_staking::Trait for Runtime {
     type AssetMining = XMiningAsset;
     type DetermineRewardPotAccount =
         xpallet_mining_staking::SimpleValidatorRewardPotAccountDeterminer<Runtime>;
+    type OnNewEra = XGatewayCommon;
     type WeightInfo = xpallet_mining_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1021,7 +1045,7 @@ construct_runtime!(
         Offences: pallet_offences::{Module, Call, Storage, Event} = 9,
         Historical: pallet_session_historical::{Module} = 10,
         Session: pallet_session::{Module, Call, Storage, Event, Config<T>} = 11,
-        Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event} = 12,
+        Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned} = 12,
         ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 13,
         AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config} = 14,
 
@@ -1368,6 +1392,49 @@ impl_runtime_apis! {
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<u32, Withdrawal<AccountId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawals_list_by_chain(chain)
         }
+
+        fn withdrawal_delays() -> BTreeMap<u32, BlockNumber> {
+            XGatewayRecords::withdrawal_delays()
+        }
+
+        fn withdrawals_of(
+            who: AccountId,
+            state_filter: Option<WithdrawalState>,
+            page_index: u32,
+            page_size: u32,
+        ) -> Vec<(u32, WithdrawalHistory<AccountId, Balance, BlockNumber>)> {
+            XGatewayRecords::withdrawals_of(who, state_filter, page_index, page_size)
+        }
+
+        fn withdrawal_history(id: u32) -> Option<WithdrawalHistory<AccountId, Balance, BlockNumber>> {
+            XGatewayRecords::withdrawal_history_of(id)
+        }
+
+        fn deposits_of(
+            who: AccountId,
+            page_index: u32,
+            page_size: u32,
+        ) -> Vec<(u64, DepositRecord<AccountId, Balance, BlockNumber>)> {
+            XGatewayRecords::deposits_of(who, page_index, page_size)
+        }
+
+        fn deposits_by_txid(txid: H256) -> Vec<(u64, DepositRecord<AccountId, Balance, BlockNumber>)> {
+            XGatewayRecords::deposits_by_txid(txid)
+        }
+    }
+
+    impl xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, Balance> for Runtime {
+        fn build_withdrawal_tx(withdrawal_id_list: Vec<u32>, fee_rate: u64, utxos: Vec<BtcUtxo>) -> Result<BtcTransaction, DispatchError> {
+            XGatewayBitcoin::build_withdrawal_tx(withdrawal_id_list, fee_rate, utxos)
+        }
+
+        fn trustee_utxos() -> Vec<BtcTrusteeUtxo> {
+            XGatewayBitcoin::trustee_utxo_set()
+        }
+
+        fn reserve_report() -> BtcReserveReport<Balance> {
+            XGatewayBitcoin::reserve_report()
+        }
     }
 
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance> for Runtime {
@@ -406,6 +406,21 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Destroys the usable balance of `who` regardless of the `DESTROY_USABLE` restriction.
+    ///
+    /// Only used by the gateways to revert the deposits which were credited before.
+    pub fn force_destroy_usable(
+        id: &AssetId,
+        who: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_not_native_asset(id)?;
+        xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)?;
+
+        Self::inner_destroy(id, who, AssetType::Usable, value)?;
+        Ok(())
+    }
+
     pub fn move_balance(
         id: &AssetId,
         from: &T::AccountId,
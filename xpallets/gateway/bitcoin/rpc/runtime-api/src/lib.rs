@@ -0,0 +1,30 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Runtime API definition required by ChainX RPC extensions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use sp_std::prelude::*;
+
+use sp_runtime::DispatchError;
+
+pub use xpallet_gateway_bitcoin::{
+    BtcReserveReport, BtcTransaction, BtcTrusteeUtxo, BtcUtxo, H256,
+};
+
+sp_api::decl_runtime_apis! {
+    pub trait XGatewayBitcoinApi<Balance>
+    where
+        Balance: codec::Codec,
+    {
+        /// Build the unsigned withdrawal tx for the pending withdrawal records.
+        fn build_withdrawal_tx(withdrawal_id_list: Vec<u32>, fee_rate: u64, utxos: Vec<BtcUtxo>) -> Result<BtcTransaction, DispatchError>;
+
+        /// Get the tracked unspent outputs of the trustee addresses.
+        fn trustee_utxos() -> Vec<BtcTrusteeUtxo>;
+
+        /// Get the reserve report of X-BTC.
+        fn reserve_report() -> BtcReserveReport<Balance>;
+    }
+}
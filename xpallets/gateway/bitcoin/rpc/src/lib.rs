@@ -0,0 +1,190 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface for the bitcoin gateway module.
+
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use light_bitcoin::serialization::serialize;
+
+use xp_rpc::{
+    hex_decode_error_into_rpc_err, runtime_error_into_rpc_err, Error, Result, RpcBalance,
+};
+
+use xpallet_gateway_bitcoin_rpc_runtime_api::{
+    BtcReserveReport, BtcTrusteeUtxo, BtcUtxo, XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi,
+    H256,
+};
+
+/// XGatewayBitcoin RPC methods.
+#[rpc]
+pub trait XGatewayBitcoinApi<BlockHash, Balance>
+where
+    Balance: Display + FromStr,
+{
+    /// Build the unsigned withdrawal tx (hex) for the pending withdrawal records, the fee rate is
+    /// in sat/vbyte and the utxos are the unspent outputs of the current hot trustee address.
+    /// If the utxos are empty, the tracked utxos of the hot trustee address which are not spent
+    /// by the in-flight withdrawal proposals would be used.
+    #[rpc(name = "xgatewaybitcoin_buildWithdrawalTx")]
+    fn build_withdrawal_tx(
+        &self,
+        withdrawal_id_list: Vec<u32>,
+        fee_rate: u64,
+        utxos: Vec<RpcBtcUtxo>,
+        at: Option<BlockHash>,
+    ) -> Result<String>;
+
+    /// Get the tracked unspent outputs of the trustee (hot and cold) addresses.
+    #[rpc(name = "xgatewaybitcoin_trusteeUtxos")]
+    fn trustee_utxos(&self, at: Option<BlockHash>) -> Result<Vec<RpcBtcTrusteeUtxo>>;
+
+    /// Get the reserve report of X-BTC, X-BTC is fully backed if
+    /// `trusteeBalance >= totalIssuance + pendingDeposits`.
+    #[rpc(name = "xgatewaybitcoin_reserveReport")]
+    fn reserve_report(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<BtcReserveReport<RpcBalance<Balance>>>;
+}
+
+/// A struct that implements the [`XGatewayBitcoinApi`].
+pub struct XGatewayBitcoin<C, B, Balance> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(B, Balance)>,
+}
+
+impl<C, B, Balance> XGatewayBitcoin<C, B, Balance> {
+    /// Create new `XGatewayBitcoin` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Balance> XGatewayBitcoinApi<<Block as BlockT>::Hash, Balance>
+    for XGatewayBitcoin<C, Block, Balance>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, Balance>,
+    Balance: Codec + Display + FromStr + Send + Sync + 'static,
+{
+    fn build_withdrawal_tx(
+        &self,
+        withdrawal_id_list: Vec<u32>,
+        fee_rate: u64,
+        utxos: Vec<RpcBtcUtxo>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<String> {
+        let utxos = utxos
+            .into_iter()
+            .map(BtcUtxo::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let tx = api
+            .build_withdrawal_tx(&at, withdrawal_id_list, fee_rate, utxos)
+            .map_err(runtime_error_into_rpc_err)?
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(hex::encode(serialize(&tx)))
+    }
+
+    fn trustee_utxos(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<RpcBtcTrusteeUtxo>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let utxos = api.trustee_utxos(&at).map_err(runtime_error_into_rpc_err)?;
+        Ok(utxos.into_iter().map(Into::into).collect())
+    }
+
+    fn reserve_report(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BtcReserveReport<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let report = api
+            .reserve_report(&at)
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(BtcReserveReport {
+            trustee_balance: report.trustee_balance,
+            utxo_set_seeded: report.utxo_set_seeded,
+            total_issuance: report.total_issuance.into(),
+            pending_withdrawals: report.pending_withdrawals.into(),
+            delayed_withdrawals: report.delayed_withdrawals.into(),
+            pending_deposits: report.pending_deposits,
+        })
+    }
+}
+
+/// The unspent output of the trustee address, the `txid` is the hex of the tx hash in the
+/// reversed byte order (same as the bitcoin explorers).
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub value: u64,
+}
+
+impl TryFrom<RpcBtcUtxo> for BtcUtxo {
+    type Error = Error;
+
+    fn try_from(utxo: RpcBtcUtxo) -> Result<Self> {
+        let txid = utxo.txid.trim_start_matches("0x");
+        let mut bytes = hex::decode(txid).map_err(hex_decode_error_into_rpc_err)?;
+        if bytes.len() != 32 {
+            return Err(hex_decode_error_into_rpc_err(format!(
+                "Invalid txid length:{}",
+                bytes.len()
+            )));
+        }
+        bytes.reverse();
+        Ok(BtcUtxo {
+            txid: H256::from_slice(&bytes),
+            vout: utxo.vout,
+            value: utxo.value,
+        })
+    }
+}
+
+/// The tracked unspent output of the trustee address, the `txid` is the hex of the tx hash in the
+/// reversed byte order (same as the bitcoin explorers).
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcTrusteeUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub value: u64,
+    pub addr: String,
+}
+
+impl From<BtcTrusteeUtxo> for RpcBtcTrusteeUtxo {
+    fn from(utxo: BtcTrusteeUtxo) -> Self {
+        let mut txid = utxo.txid.as_bytes().to_vec();
+        txid.reverse();
+        Self {
+            txid: hex::encode(txid),
+            vout: utxo.vout,
+            value: utxo.value,
+            addr: String::from_utf8_lossy(&utxo.addr).into_owned(),
+        }
+    }
+}
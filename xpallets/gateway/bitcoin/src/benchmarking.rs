@@ -4,12 +4,13 @@ use codec::{Decode, Encode};
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 use frame_support::storage::{StorageMap, StorageValue};
 use frame_system::RawOrigin;
-use sp_runtime::{AccountId32, SaturatedConversion};
+use sp_runtime::{AccountId32, Perbill, SaturatedConversion};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use chainx_primitives::AssetId;
 use xp_gateway_bitcoin::BtcTxType;
-use xpallet_assets::{BalanceOf, Module as XAssets};
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
+use xpallet_gateway_common::traits::AddressBinding;
 use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
 
 use light_bitcoin::{
@@ -20,7 +21,11 @@ use light_bitcoin::{
 };
 
 use crate::{
-    types::*, Call, Module, PendingDeposits, Trait, TxState, Verifier, WithdrawalProposal,
+    trustee::get_hot_trustee_address,
+    tx::{addr2vecu8, unsigned_txid},
+    types::*,
+    Call, Module, PendingDeposits, Trait, TxState, Verifier, WithdrawalFeeRates,
+    WithdrawalProposals,
 };
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
@@ -70,7 +75,7 @@ fn withdraw_tx() -> (Transaction, BtcRelayedTxInfo, Transaction) {
     (tx, info, prev_tx)
 }
 
-fn prepare_withdrawal<T: Trait>() -> Transaction {
+fn prepare_withdrawal<T: Trait>() -> (H256, Transaction) {
     // https://blockchain.info/rawtx/62c389f1974b8a44737d76f92da0f5cd7f6f48d065e7af6ba368298361141270?format=hex
     const RAW_TX: &str = "0100000001052ceda6cf9c93012a994f4ffa2a29c9e31ecf96f472b175eb8e602bfa2b2c5100000000fdfd000047304402200e4d732c456f4722d376252be16554edb27fc93c55db97859e16682bc62b014502202b9c4b01ad55daa1f76e6a564b7762cd0a81240c947806ab3f3b056f2e77c1da01483045022100c7cd680992de60da8c33fc3ef7f5ead85b204660822d9fbda2d85f9fadba732a022021fdc49b20a6007ea971a385732a4065d1d7c792ac9dc391034fb78aa9f5034b014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff03e0349500000000001976a91413256ff2dee6e80c275ddb877abc1ffe453a731488ace00f9700000000001976a914ea6e8dd56703ace584eb9dff0224629f8486672988acc88a02000000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000";
     let old_withdraw = RAW_TX.parse::<Transaction>().unwrap();
@@ -110,13 +115,15 @@ fn prepare_withdrawal<T: Trait>() -> Transaction {
         withdrawal_id_list: vec![0, 1],
         tx: old_withdraw.clone(),
         trustee_list: vec![(alice, true), (bob, true)],
+        withdrawal_fee: Module::<T>::btc_withdrawal_fee(),
     };
-    WithdrawalProposal::<T>::put(proposal);
+    let txid = unsigned_txid(&old_withdraw);
+    WithdrawalProposals::<T>::insert(txid, proposal);
 
     // replace tx
     let mut new_withdraw = old_withdraw;
     new_withdraw.inputs = tmp.inputs; // replace inputs
-    new_withdraw
+    (txid, new_withdraw)
 }
 
 // block height: 577696
@@ -179,12 +186,14 @@ benchmarks! {
             withdrawal_id_list: vec![0, 1],
             tx: tx.clone(),
             trustee_list: vec![],
+            withdrawal_fee: Module::<T>::btc_withdrawal_fee(),
         };
-        WithdrawalProposal::<T>::put(proposal);
+        let txid = unsigned_txid(&tx);
+        WithdrawalProposals::<T>::insert(txid, proposal);
 
     }: _(RawOrigin::Signed(caller), tx_raw, info, Some(prev_tx_raw))
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_none());
+        assert!(WithdrawalProposals::<T>::get(txid).is_none());
         assert_eq!(
             TxState::get(tx_hash),
             Some(BtcTxState {
@@ -214,15 +223,17 @@ benchmarks! {
         XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, second_withdraw, b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(), b"".to_vec().into()).unwrap();
 
         let tx = create_tx();
+        let txid = unsigned_txid(&tx);
         let tx_raw: Vec<u8> = serialization::serialize(&tx).into();
     }: _(RawOrigin::Signed(caller), vec![0, 1], tx_raw)
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_some());
+        assert!(WithdrawalProposals::<T>::get(txid).is_some());
     }
 
     sign_withdraw_tx {
         let l = 1024 * 1024 * 500; // 500KB length
         let tx = create_tx();
+        let txid = unsigned_txid(&tx);
         let alice = alice::<T>();
         let bob = bob::<T>();
 
@@ -231,14 +242,15 @@ benchmarks! {
             withdrawal_id_list: vec![0, 1],
             tx: tx,
             trustee_list: vec![ (alice, true) ],
+            withdrawal_fee: Module::<T>::btc_withdrawal_fee(),
         };
-        WithdrawalProposal::<T>::put(proposal);
+        WithdrawalProposals::<T>::insert(txid, proposal);
 
         let (signed_tx, _, _) = withdraw_tx();
         let tx_raw: Vec<u8> = serialization::serialize(&signed_tx).into();
-    }: _(RawOrigin::Signed(bob), Some(tx_raw))
+    }: _(RawOrigin::Signed(bob), txid, Some(tx_raw))
     verify {
-        assert_eq!(WithdrawalProposal::<T>::get().unwrap().sig_state, VoteResult::Finish);
+        assert_eq!(WithdrawalProposals::<T>::get(txid).unwrap().sig_state, VoteResult::Finish);
     }
 
     set_best_index {
@@ -285,29 +297,70 @@ benchmarks! {
         assert_eq!(XAssets::<T>::usable_balance(&receiver, &ASSET_ID), (100000000 + 200000000 + 300000000).into());
     }
 
+    claim_pending_deposits {
+        // P2PKH address of the private key 0x01
+        let addr = b"1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_vec();
+        let v = vec![
+            BtcDepositCache {
+                txid: H256::repeat_byte(1),
+                balance: 100000000,
+            },
+            BtcDepositCache {
+                txid: H256::repeat_byte(2),
+                balance: 200000000,
+            },
+            BtcDepositCache {
+                txid: H256::repeat_byte(3),
+                balance: 300000000,
+            },
+        ];
+        PendingDeposits::insert(&addr, v);
+        let caller = alice::<T>();
+        // "Bitcoin Signed Message" signature of the SS58 text of Alice
+        let signature = hex::decode("200d5ae652b36dff8365b5991f3888ec2949179446c1c3f7637afc3346fd63e41673a3468a2a8dc47727c3565c48a5aeabe06f0a39b78f50d2591728e4989d69c0").unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), addr.clone(), signature)
+    verify {
+        assert!(Module::<T>::pending_deposits(&addr).is_empty());
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), (100000000 + 200000000 + 300000000).into());
+    }
+
+    bind_btc_address {
+        // P2PKH address of the private key 0x01
+        let addr = b"1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_vec();
+        let caller = alice::<T>();
+        // "Bitcoin Signed Message" signature of the SS58 text of Alice
+        let signature = hex::decode("200d5ae652b36dff8365b5991f3888ec2949179446c1c3f7637afc3346fd63e41673a3468a2a8dc47727c3565c48a5aeabe06f0a39b78f50d2591728e4989d69c0").unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), addr.clone(), signature)
+    verify {
+        assert_eq!(T::AddressBinding::address(Chain::Bitcoin, addr), Some(caller));
+    }
+
     remove_proposal {
         let (tx, _, _) = withdraw_tx();
+        let txid = unsigned_txid(&tx);
         let proposal = BtcWithdrawalProposal::<T::AccountId> {
             sig_state: VoteResult::Unfinish,
             withdrawal_id_list: vec![0, 1],
             tx: tx,
             trustee_list: vec![],
+            withdrawal_fee: Module::<T>::btc_withdrawal_fee(),
         };
-        WithdrawalProposal::<T>::put(proposal);
-    }: _(RawOrigin::Root)
+        WithdrawalProposals::<T>::insert(txid, proposal);
+    }: _(RawOrigin::Root, txid)
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_none());
+        assert!(WithdrawalProposals::<T>::get(txid).is_none());
     }
 
     force_replace_proposal_tx {
         let l = 1024 * 1024 * 500; // 500KB length
 
         Verifier::put(BtcTxVerifier::Test);
-        let tx = prepare_withdrawal::<T>();
+        let (txid, tx) = prepare_withdrawal::<T>();
         let raw = serialization::serialize(&tx);
-    }: _(RawOrigin::Root, raw.into())
+    }: _(RawOrigin::Root, txid, raw.into())
     verify {
-        assert_eq!(WithdrawalProposal::<T>::get().unwrap().tx, tx);
+        assert!(WithdrawalProposals::<T>::get(txid).is_none());
+        assert_eq!(WithdrawalProposals::<T>::get(unsigned_txid(&tx)).unwrap().tx, tx);
     }
 
     set_btc_withdrawal_fee {
@@ -321,6 +374,74 @@ benchmarks! {
     }: _(RawOrigin::Root,  2000000)
     verify {
     }
+
+    set_header_retention_window {
+        let window = 2016;
+    }: _(RawOrigin::Root, window)
+    verify {
+        assert_eq!(Module::<T>::header_retention_window(), window);
+    }
+
+    set_max_reorg_depth {
+        let depth = 100;
+    }: _(RawOrigin::Root, depth)
+    verify {
+        assert_eq!(Module::<T>::max_reorg_depth(), depth);
+    }
+
+    set_max_proposal_count {
+        let count = 8;
+    }: _(RawOrigin::Root, count)
+    verify {
+        assert_eq!(Module::<T>::max_proposal_count(), count);
+    }
+
+    set_trustee_reward_ratio {
+        let ratio = Perbill::from_percent(20);
+    }: _(RawOrigin::Root, ratio)
+    verify {
+        assert_eq!(Module::<T>::trustee_reward_ratio(), ratio);
+    }
+
+    set_max_missed_proposals {
+        let count = 10;
+    }: _(RawOrigin::Root, count)
+    verify {
+        assert_eq!(Module::<T>::max_missed_proposals(), count);
+    }
+
+    set_btc_withdrawal_fee_bounds {
+        WithdrawalFeeRates::put(vec![10, 20, 30]);
+        let bounds = (1_000, 1_000_000);
+    }: _(RawOrigin::Root, Some(bounds))
+    verify {
+        assert_eq!(Module::<T>::btc_withdrawal_fee_bounds(), Some(bounds));
+    }
+
+    set_withdrawal_fee_destination {
+        let destination: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Root, Some(destination.clone()))
+    verify {
+        assert_eq!(Module::<T>::withdrawal_fee_destination(), Some(destination));
+    }
+
+    seed_trustee_utxos {
+        let u in 1 .. 100;
+
+        let addr = addr2vecu8(&get_hot_trustee_address::<T>().unwrap());
+        let utxos = (0..u)
+            .map(|vout| BtcTrusteeUtxo {
+                txid: H256::repeat_byte(1),
+                vout,
+                value: 100000000,
+                addr: addr.clone(),
+            })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Root, utxos, true)
+    verify {
+        assert!(Module::<T>::trustee_utxos((H256::repeat_byte(1), u - 1)).is_some());
+        assert!(Module::<T>::trustee_utxo_seeded());
+    }
 }
 
 #[cfg(test)]
@@ -339,9 +460,19 @@ mod tests {
             assert_ok!(test_benchmark_set_best_index::<Test>());
             assert_ok!(test_benchmark_set_confirmed_index::<Test>());
             assert_ok!(test_benchmark_remove_pending::<Test>());
+            assert_ok!(test_benchmark_claim_pending_deposits::<Test>());
+            assert_ok!(test_benchmark_bind_btc_address::<Test>());
             assert_ok!(test_benchmark_force_replace_proposal_tx::<Test>());
             assert_ok!(test_benchmark_set_btc_withdrawal_fee::<Test>());
             assert_ok!(test_benchmark_set_btc_deposit_limit::<Test>());
+            assert_ok!(test_benchmark_set_header_retention_window::<Test>());
+            assert_ok!(test_benchmark_set_max_reorg_depth::<Test>());
+            assert_ok!(test_benchmark_set_max_proposal_count::<Test>());
+            assert_ok!(test_benchmark_set_trustee_reward_ratio::<Test>());
+            assert_ok!(test_benchmark_set_max_missed_proposals::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_fee_destination::<Test>());
+            assert_ok!(test_benchmark_set_btc_withdrawal_fee_bounds::<Test>());
+            assert_ok!(test_benchmark_seed_trustee_utxos::<Test>());
         });
     }
 }
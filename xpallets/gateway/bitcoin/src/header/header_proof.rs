@@ -109,7 +109,7 @@ pub fn work_required<T: Trait>(parent_hash: H256, height: u32, params: &BtcParam
     RequiredWork::Value(parent_header.bits)
 }
 
-fn is_retarget_height(height: u32, params: &BtcParams) -> bool {
+pub(crate) fn is_retarget_height(height: u32, params: &BtcParams) -> bool {
     height % params.retargeting_interval() == 0
 }
 
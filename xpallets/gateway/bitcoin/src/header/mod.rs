@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 mod header_proof;
+mod pruning;
 
 use frame_support::{StorageMap, StorageValue};
 use sp_runtime::DispatchResult;
@@ -10,10 +11,14 @@ use light_bitcoin::primitives::{hash_rev, H256};
 
 use xp_logging::{error, info};
 
+use crate::tx;
 use crate::types::{BtcHeaderIndex, BtcHeaderInfo};
 use crate::{ConfirmedIndex, Error, MainChain, Module, Trait};
 
 pub use self::header_proof::HeaderVerifier;
+pub use self::pruning::{
+    compact_legacy_tx_states, prune_ancient_headers, start_legacy_tx_state_compaction,
+};
 
 /// Look back the headers to pick the confirmed index,
 /// return the header indexes on the look back path.
@@ -65,11 +70,50 @@ fn look_back_confirmed_header<T: Trait>(
     }
 }
 
+/// Ensure the count of main chain blocks which would leave the main chain when this header
+/// becomes the best is not more than `MaxReorgDepth`, at most `MaxReorgDepth + 1` headers are
+/// looked up.
+pub fn check_reorg_depth<T: Trait>(header_info: &BtcHeaderInfo) -> DispatchResult {
+    let max_depth = Module::<T>::max_reorg_depth();
+    let mut depth = 0;
+    let mut hash = header_info.header.previous_header_hash;
+    while !Module::<T>::main_chain(&hash) {
+        depth += 1;
+        if depth > max_depth {
+            error!(
+                "[check_reorg_depth] The reorg is deeper than {}, header:{:?}",
+                max_depth,
+                hash_rev(header_info.header.hash())
+            );
+            return Err(Error::<T>::ReorgTooDeep.into());
+        }
+        match Module::<T>::headers(&hash) {
+            Some(info) => hash = info.header.previous_header_hash,
+            None => break,
+        }
+    }
+    Ok(())
+}
+
 pub fn update_confirmed_header<T: Trait>(header_info: &BtcHeaderInfo) -> Option<BtcHeaderIndex> {
     let (confirmed, chain) = look_back_confirmed_header::<T>(header_info);
+    let mut prev_hash = chain.last().and_then(|index| {
+        Module::<T>::headers(&index.hash).map(|info| info.header.previous_header_hash)
+    });
     for index in chain {
         set_main_chain::<T>(index.height, index.hash);
     }
+    // the fork may be deeper than the confirmations, keep switching the main chain until
+    // reaching the common ancestor.
+    while let Some(hash) = prev_hash {
+        if Module::<T>::main_chain(&hash) {
+            break;
+        }
+        prev_hash = Module::<T>::headers(&hash).map(|info| {
+            set_main_chain::<T>(info.height, hash);
+            info.header.previous_header_hash
+        });
+    }
     confirmed.map(|index| {
         ConfirmedIndex::put(index);
         index
@@ -85,8 +129,14 @@ fn set_main_chain<T: Trait>(height: u32, main_hash: H256) {
     for hash in hashes {
         if hash == main_hash {
             MainChain::insert(&hash, true);
-        } else {
-            MainChain::remove(&hash);
+        } else if MainChain::take(&hash) {
+            // the block leaves the main chain, the deposits credited in it must be reverted.
+            info!(
+                "[set_main_chain] Block ({:?}) at height {} leaves the main chain",
+                hash_rev(hash),
+                height
+            );
+            tx::revert_credited_deposits::<T>(hash);
         }
     }
 }
@@ -0,0 +1,181 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    storage::StoragePrefixedMap,
+    traits::Get,
+    weights::{RuntimeDbWeight, Weight},
+    StorageMap, StorageValue,
+};
+use sp_std::{cmp, prelude::*};
+
+use light_bitcoin::primitives::H256;
+
+use xp_logging::{debug, info};
+
+use super::header_proof::is_retarget_height;
+use crate::types::BtcTxResult;
+use crate::{
+    BlockChanges, BlockHashFor, CreditedDeposits, Headers, LegacyTxStateCursor,
+    LegacyTxStatesCompacted, MainChain, Module, NextPruneHeight, PendingDepositBlock, ProcessedTxs,
+    Trait, TxState, TxsOfHeight,
+};
+
+/// Prune the headers (include forked headers) and the relayed tx states which are lower than
+/// `ConfirmedIndex - HeaderRetentionWindow`.
+///
+/// The main chain headers on the retarget heights are retained, due to `work_required_retarget`
+/// would look up the header of `height - retargeting_interval`. The tx state of a pruned height
+/// would be compacted into `ProcessedTxs`, which just remember the successful processed txs for
+/// replay protection.
+///
+/// At most `limit` items (a tx state or a height of headers) would be handled in one call, the
+/// rest would be handled in the following blocks. Returns the consumed weight.
+pub fn prune_ancient_headers<T: Trait>(limit: u32) -> Weight {
+    let db_weight: RuntimeDbWeight = T::DbWeight::get();
+    let confirmed = match Module::<T>::confirmed_index() {
+        Some(confirmed) => confirmed,
+        None => return db_weight.reads(1),
+    };
+    let prune_to = confirmed
+        .height
+        .saturating_sub(Module::<T>::header_retention_window());
+    let (_, genesis_height) = Module::<T>::genesis_info();
+    let params = Module::<T>::params_info();
+    // ConfirmedIndex, HeaderRetentionWindow, GenesisInfo, ParamsInfo, NextPruneHeight
+    let mut reads: Weight = 5;
+    let mut writes: Weight = 0;
+
+    // the genesis header is always retained.
+    let mut height = cmp::max(Module::<T>::next_prune_height(), genesis_height + 1);
+    if height >= prune_to {
+        return db_weight.reads(reads);
+    }
+
+    let mut count = 0;
+    while height < prune_to && count < limit {
+        let mut txs = TxsOfHeight::get(height);
+        reads += 1;
+        while count < limit {
+            match txs.pop() {
+                Some(txid) => {
+                    compact_tx_state(txid);
+                    count += 1;
+                    reads += 1;
+                    writes += 2;
+                }
+                None => break,
+            }
+        }
+        if !txs.is_empty() {
+            // run out of the limit, the rest txs of this height would be compacted in next block.
+            TxsOfHeight::insert(height, txs);
+            writes += 1;
+            break;
+        }
+        TxsOfHeight::remove(height);
+        writes += 1;
+
+        let hashes = BlockHashFor::get(height);
+        reads += 1;
+        let is_retarget = is_retarget_height(height, &params);
+        let mut retained = Vec::new();
+        for hash in hashes {
+            reads += 1;
+            if is_retarget && MainChain::get(&hash) {
+                retained.push(hash);
+                continue;
+            }
+            Headers::remove(&hash);
+            MainChain::remove(&hash);
+            CreditedDeposits::<T>::remove(&hash);
+            let changes = BlockChanges::take(&hash);
+            for (_, txid) in changes.pending_deposits.iter() {
+                PendingDepositBlock::remove(txid);
+            }
+            reads += 1;
+            writes += 4 + changes.pending_deposits.len() as Weight;
+        }
+        if retained.is_empty() {
+            BlockHashFor::remove(height);
+        } else {
+            debug!(
+                "[prune_ancient_headers] Retain the retarget header, height:{}, hash:{:?}",
+                height, retained
+            );
+            BlockHashFor::insert(height, retained);
+        }
+        writes += 1;
+
+        count += 1;
+        height += 1;
+    }
+
+    info!(
+        "[prune_ancient_headers] Pruned headers and tx states before height:{}, confirmed:{:?}",
+        height, confirmed
+    );
+    NextPruneHeight::put(height);
+    writes += 1;
+
+    db_weight.reads_writes(reads, writes)
+}
+
+/// Start compacting the tx states relayed before `TxsOfHeight` existed, which would never be
+/// reached by `prune_ancient_headers`. Does nothing if the compaction has been started or done.
+pub fn start_legacy_tx_state_compaction<T: Trait>() -> Weight {
+    let db_weight: RuntimeDbWeight = T::DbWeight::get();
+    if LegacyTxStatesCompacted::get() || LegacyTxStateCursor::exists() {
+        return db_weight.reads(2);
+    }
+    info!("[start_legacy_tx_state_compaction] Start compacting the legacy tx states");
+    LegacyTxStateCursor::put(TxState::final_prefix().to_vec());
+    db_weight.reads_writes(2, 1)
+}
+
+/// Walk `TxState` from `LegacyTxStateCursor` and compact at most `limit` tx states, the rest
+/// would be compacted in the following blocks. Returns the consumed weight.
+///
+/// The legacy tx states have no height, so they are compacted regardless of the retention
+/// window. A tx relayed after the upgrade may be walked as well, compacting it early is harmless
+/// since `ProcessedTxs` still protects it from replay and is dropped as well on reorg.
+pub fn compact_legacy_tx_states<T: Trait>(limit: u32) -> Weight {
+    let db_weight: RuntimeDbWeight = T::DbWeight::get();
+    let mut cursor = match LegacyTxStateCursor::get() {
+        Some(cursor) => cursor,
+        None => return db_weight.reads(1),
+    };
+    let prefix = TxState::final_prefix();
+    let mut reads: Weight = 1;
+    let mut writes: Weight = 1;
+
+    let mut count = 0;
+    while count < limit {
+        let key = match sp_io::storage::next_key(&cursor) {
+            Some(key) if key.starts_with(&prefix) && key.len() == prefix.len() + 32 => key,
+            _ => {
+                info!("[compact_legacy_tx_states] Compacted all the legacy tx states");
+                LegacyTxStateCursor::kill();
+                LegacyTxStatesCompacted::put(true);
+                return db_weight.reads_writes(reads, writes + 1);
+            }
+        };
+        // `TxState` uses the identity hasher, the txid is the suffix of the key
+        compact_tx_state(H256::from_slice(&key[prefix.len()..]));
+        reads += 1;
+        writes += 2;
+        cursor = key;
+        count += 1;
+    }
+    LegacyTxStateCursor::put(cursor);
+
+    db_weight.reads_writes(reads, writes)
+}
+
+/// Drop the tx state, just record the successful processed tx for replay protection.
+fn compact_tx_state(txid: H256) {
+    if let Some(state) = TxState::take(&txid) {
+        if state.result == BtcTxResult::Success {
+            ProcessedTxs::insert(&txid, true);
+        }
+    }
+}
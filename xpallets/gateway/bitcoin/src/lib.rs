@@ -17,53 +17,70 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    ModuleId, Perbill, SaturatedConversion,
+};
 use sp_std::prelude::*;
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     ensure,
-    traits::{EnsureOrigin, UnixTime},
-    weights::Pays,
+    storage::migration::take_storage_value,
+    traits::{EnsureOrigin, Get, UnixTime},
+    weights::{Pays, Weight},
+    IterableStorageMap,
 };
 use frame_system::{ensure_root, ensure_signed};
 use orml_utilities::with_transaction_result;
 
 #[cfg(feature = "std")]
 pub use light_bitcoin::primitives::h256_rev;
-pub use light_bitcoin::{
-    chain::BlockHeader as BtcHeader,
-    keys::Network as BtcNetwork,
-    primitives::{hash_rev, Compact, H256, H264},
-};
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, DisplayLayout},
     serialization::{deserialize, Reader},
 };
+pub use light_bitcoin::{
+    chain::{BlockHeader as BtcHeader, Transaction as BtcTransaction},
+    keys::Network as BtcNetwork,
+    primitives::{hash_rev, Compact, H256, H264},
+};
 
 use chainx_primitives::{AssetId, ReferralId};
 use xp_gateway_common::AccountExtractor;
 use xp_logging::{debug, error, info};
 use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
 use xpallet_gateway_common::{
-    traits::{AddressBinding, ReferralBinding, TrusteeSession},
+    traits::{AddressBinding, AddressOwnership, ReferralBinding, TrusteeSession},
     trustees::bitcoin::BtcTrusteeAddrInfo,
 };
-use xpallet_support::try_addr;
+use xpallet_gateway_records::WithdrawalState;
+use xpallet_support::{traits::ReportOffenders, try_addr};
 
-pub use self::types::{BtcAddress, BtcParams, BtcTxVerifier, BtcWithdrawalProposal};
+pub use self::types::{
+    BtcAddress, BtcParams, BtcReserveReport, BtcTrusteeUtxo, BtcTxVerifier, BtcUtxo,
+    BtcWithdrawalProposal,
+};
 pub use self::weights::WeightInfo;
 use self::{
-    trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
-    tx::remove_pending_deposit,
+    trustee::{
+        get_current_trustee_address_pair, get_hot_trustee_address, get_last_trustee_address_pair,
+    },
+    tx::{addr2vecu8, remove_pending_deposit, verify_address_ownership},
     types::{
-        BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcRelayedTx, BtcRelayedTxInfo,
-        BtcTxResult, BtcTxState,
+        BtcBlockChanges, BtcCreditedDeposit, BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo,
+        BtcRelayedTx, BtcRelayedTxInfo, BtcTxResult, BtcTxState, LegacyBtcWithdrawalProposal,
     },
 };
 
+/// The max number of items (a tx state or a height of headers) pruned in one block.
+pub const MAX_PRUNED_ITEMS_PER_BLOCK: u32 = 50;
+
+/// The id of the pot which holds the X-BTC rewards for the trustees.
+pub const TRUSTEE_REWARD_POT_ID: ModuleId = ModuleId(*b"pcx/btct");
+
 // syntactic sugar for native log.
 #[macro_export]
 macro_rules! native {
@@ -83,6 +100,8 @@ pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
     type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
     type ReferralBinding: ReferralBinding<Self::AccountId>;
     type AddressBinding: AddressBinding<Self::AccountId, BtcAddress>;
+    /// Reports the trustees missing too many withdrawal proposals.
+    type OffenderReporter: ReportOffenders<Self::AccountId>;
     type WeightInfo: WeightInfo;
 }
 
@@ -163,6 +182,38 @@ decl_error! {
         NoWithdrawalRecord,
         /// already vote for this withdrawal proposal
         DuplicateVote,
+        /// the fee rate for building withdrawal tx should not be zero
+        InvalidFeeRate,
+        /// the provided utxos can't cover the withdrawals and the miner fee
+        InsufficientUtxo,
+        /// header retention window should not be less than confirmation number
+        InvalidRetentionWindow,
+        /// the min withdrawal fee should not be greater than the max one
+        InvalidFeeBounds,
+        /// a replacement for current withdrawal proposal is pending
+        ExistingReplacement,
+        /// no replacement for current withdrawal proposal
+        NoReplacement,
+        /// the withdrawal tx does not signal the replaceability (BIP125)
+        IrreplaceableTx,
+        /// the replacement tx must pay a higher fee than the replaced one
+        LowReplacementFee,
+        /// the count of in-flight withdrawal proposals reaches the max proposal count
+        TooManyProposals,
+        /// the proposal for this tx already exists
+        DuplicatedProposal,
+        /// the withdrawals or the inputs of tx are already in other proposals
+        ConflictingProposal,
+        /// no pending deposit for the address
+        NoPendingDeposit,
+        /// the account of the caller isn't a 32 bytes account, can't be encoded as the SS58 text
+        InvalidAccount,
+        /// the utxo is not controlled by the current or the last trustee addresses
+        NotTrusteeUtxo,
+        /// the reorg is deeper than the max reorg depth, should be handled by root
+        ReorgTooDeep,
+        /// max reorg depth should not be less than confirmation number
+        InvalidReorgDepth,
     }
 }
 
@@ -178,22 +229,45 @@ decl_event!(
         TxProcessed(H256, H256, BtcTxState),
         /// An account deposited some token. [tx_hash, who, amount]
         Deposited(H256, AccountId, Balance),
-        /// A list of withdrawal applications were processed successfully. [tx_hash, withdrawal_ids, total_withdrawn]
-        Withdrawn(H256, Vec<u32>, Balance),
+        /// A list of withdrawal applications were processed successfully. [tx_hash, withdrawal_ids, total_withdrawn, withdrawal_fee, miner_fee]
+        Withdrawn(H256, Vec<u32>, Balance, Balance, Balance),
         /// A new record of unclaimed deposit. [tx_hash, btc_address]
         UnclaimedDeposit(H256, BtcAddress),
         /// A unclaimed deposit record was removed. [depositor, deposit_amount, tx_hash, btc_address]
         PendingDepositRemoved(AccountId, Balance, H256, BtcAddress),
-        /// A new withdrawal proposal was created. [proposer, withdrawal_ids]
-        WithdrawalProposalCreated(AccountId, Vec<u32>),
-        /// A trustee voted/vetoed a withdrawal proposal. [trustee, vote_status]
-        WithdrawalProposalVoted(AccountId, bool),
+        /// A new withdrawal proposal was created. [proposer, proposal_txid, withdrawal_ids]
+        WithdrawalProposalCreated(AccountId, H256, Vec<u32>),
+        /// A trustee voted/vetoed a withdrawal proposal. [trustee, proposal_txid, vote_status]
+        WithdrawalProposalVoted(AccountId, H256, bool),
         /// A withdrawal proposal was dropped. [reject_count, total_count, withdrawal_ids]
         WithdrawalProposalDropped(u32, u32, Vec<u32>),
         /// The proposal has been processed successfully and is waiting for broadcasting. [tx_hash]
         WithdrawalProposalCompleted(H256),
         /// A fatal error happened during the withdrwal process. [tx_hash, proposal_hash]
         WithdrawalFatalErr(H256, H256),
+        /// A credited deposit was reverted due to its block left the main chain. [tx_hash, block_hash, who, amount]
+        DepositReverted(H256, H256, AccountId, Balance),
+        /// A fatal error happened during reverting the deposit, the depositor does not have
+        /// enough usable balance. [tx_hash, block_hash, who, unreverted_amount]
+        DepositRevertFatalErr(H256, H256, AccountId, Balance),
+        /// A unclaimed deposit record was removed due to its block left the main chain. [tx_hash, block_hash, btc_address]
+        PendingDepositReverted(H256, H256, BtcAddress),
+        /// The trustee utxo set was seeded by root. [seeded_count, complete]
+        TrusteeUtxosSeeded(u32, bool),
+        /// A replacement tx with higher fee was proposed for the stuck withdrawal proposal. [proposer, tx_hash]
+        WithdrawalReplacementProposed(AccountId, H256),
+        /// A replacement tx was dropped. [reject_count, total_count, tx_hash]
+        WithdrawalReplacementDropped(u32, u32, H256),
+        /// The tx of withdrawal proposal was replaced. [replaced_tx_hash, tx_hash]
+        WithdrawalReplacementCompleted(H256, H256),
+        /// A trustee was rewarded for signing the withdrawal tx. [trustee, reward]
+        TrusteeRewarded(AccountId, Balance),
+        /// The withdrawal fee except the trustee reward was credited. [destination, fee]
+        WithdrawalFeeCredited(AccountId, Balance),
+        /// The withdrawal fee was updated by the fee rates of the withdrawal txs. [withdrawal_fee]
+        WithdrawalFeeUpdated(u64),
+        /// Some trustees missed too many withdrawal proposals and were reported. [trustees]
+        TrusteesReported(Vec<AccountId>),
     }
 );
 
@@ -212,11 +286,55 @@ decl_storage! {
 
         /// mark tx has been handled, in case re-handle this tx, and log handle result
         pub TxState get(fn tx_state): map hasher(identity) H256 => Option<BtcTxState>;
+        /// relayed tx list for a height, used for compacting `TxState` when pruning the height
+        pub TxsOfHeight get(fn txs_of_height): map hasher(twox_64_concat) u32 => Vec<H256>;
+        /// mark this tx is processed successfully and its `TxState` has been pruned
+        pub ProcessedTxs get(fn processed_tx): map hasher(identity) H256 => bool;
+
+        /// headers lower than `ConfirmedIndex - HeaderRetentionWindow` would be pruned,
+        /// except the main chain headers on the retarget heights
+        pub HeaderRetentionWindow get(fn header_retention_window): u32 = 2 * 2016;
+        /// a new best header switching more main chain blocks than this depth would be rejected
+        pub MaxReorgDepth get(fn max_reorg_depth): u32 = 24;
+        /// the next height to prune headers and tx states
+        pub NextPruneHeight get(fn next_prune_height): u32;
+        /// the storage key of `TxState` to continue compacting the tx states relayed before
+        /// `TxsOfHeight` existed from, None if the compaction is not in progress
+        pub LegacyTxStateCursor get(fn legacy_tx_state_cursor): Option<Vec<u8>>;
+        /// whether the tx states relayed before `TxsOfHeight` existed have been compacted
+        pub LegacyTxStatesCompacted get(fn legacy_tx_states_compacted): bool;
+        /// credited deposits of a block, used for reverting them when the block leaves the main chain
+        pub CreditedDeposits get(fn credited_deposits): map hasher(identity) H256 => Vec<BtcCreditedDeposit<T::AccountId>>;
+        /// trustee utxo and pending deposit changes of a block, used for undoing them when the block leaves the main chain
+        pub BlockChanges get(fn block_changes): map hasher(identity) H256 => BtcBlockChanges;
+        /// the block which a pending deposit tx is packed in, txid => block hash
+        pub PendingDepositBlock get(fn pending_deposit_block): map hasher(identity) H256 => Option<H256>;
+        /// unspent outputs of the trustee (hot and cold) addresses, (txid, vout) => utxo
+        pub TrusteeUtxos get(fn trustee_utxos): map hasher(twox_64_concat) (H256, u32) => Option<BtcTrusteeUtxo>;
+        /// total value of `TrusteeUtxos`
+        pub TrusteeUtxoBalance get(fn trustee_utxo_balance): u64;
+        /// whether `TrusteeUtxos` has been seeded with the trustee utxos created before the
+        /// utxo tracking, i.e. whether `TrusteeUtxoBalance` is all the BTC held by the trustees
+        pub TrusteeUtxoSeeded get(fn trustee_utxo_seeded): bool;
         /// unclaimed deposit info, addr => tx_hash, btc value,
         pub PendingDeposits get(fn pending_deposits): map hasher(blake2_128_concat) BtcAddress => Vec<BtcDepositCache>;
 
-        /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
-        pub WithdrawalProposal get(fn withdrawal_proposal): Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// in-flight withdrawal proposals, unsigned txid => proposal
+        pub WithdrawalProposals get(fn withdrawal_proposals): map hasher(identity) H256 => Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// the replacement tx with higher fee for a withdrawal proposal, waiting for signatures,
+        /// unsigned txid of the proposal => replacement
+        pub WithdrawalReplacements get(fn withdrawal_replacements): map hasher(identity) H256 => Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// max count of the in-flight withdrawal proposals
+        pub MaxProposalCount get(fn max_proposal_count): u32 = 4;
+        /// the ratio of the withdrawal fee income rewarded to the trustees
+        pub TrusteeRewardRatio get(fn trustee_reward_ratio): Perbill;
+        /// the account credited with the withdrawal fee income except the trustee reward, the
+        /// treasury if it's None
+        pub WithdrawalFeeDestination get(fn withdrawal_fee_destination): Option<T::AccountId>;
+        /// trustee => count of the consecutive finished withdrawal proposals missed by the trustee
+        pub MissedProposalsOf get(fn missed_proposals_of): map hasher(blake2_128_concat) T::AccountId => u32;
+        /// trustees missing this count of consecutive proposals would be reported, 0 means no limit
+        pub MaxMissedProposals get(fn max_missed_proposals): u32;
 
         /// get GenesisInfo (header, height)
         pub GenesisInfo get(fn genesis_info) config(): (BtcHeader, u32);
@@ -228,6 +346,11 @@ decl_storage! {
         pub ConfirmationNumber get(fn confirmation_number) config(): u32;
         /// get BtcWithdrawalFee from genesis_config
         pub BtcWithdrawalFee get(fn btc_withdrawal_fee) config(): u64;
+        /// the (min, max) bounds of the withdrawal fee derived from the fee rates of the
+        /// withdrawal txs, the withdrawal fee is set manually if it's None
+        pub BtcWithdrawalFeeBounds get(fn btc_withdrawal_fee_bounds): Option<(u64, u64)>;
+        /// the fee rates (sat/vbyte) of the recent relayed withdrawal txs, the oldest first
+        pub WithdrawalFeeRates get(fn withdrawal_fee_rates): Vec<u64>;
         /// min deposit value limit, default is 10w sotashi(0.001 BTC)
         pub BtcMinDeposit get(fn btc_min_deposit): u64 = 1 * 100000;
         /// max withdraw account count in bitcoin withdrawal transaction
@@ -254,6 +377,8 @@ decl_storage! {
             BlockHashFor::insert(&genesis_index.height, vec![genesis_hash]);
             MainChain::insert(&genesis_hash, true);
             BestIndex::put(genesis_index);
+            // all the tx states of a new chain are tracked by `TxsOfHeight`
+            LegacyTxStatesCompacted::put(true);
 
             // init trustee (not this action should ha)
             if !config.genesis_trustees.is_empty() {
@@ -268,6 +393,15 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        fn on_initialize() -> Weight {
+            header::prune_ancient_headers::<T>(MAX_PRUNED_ITEMS_PER_BLOCK)
+                + header::compact_legacy_tx_states::<T>(MAX_PRUNED_ITEMS_PER_BLOCK)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_withdrawal_proposals() + header::start_legacy_tx_state_compaction::<T>()
+        }
+
         /// if use `BtcHeader` struct would export in metadata, cause complex in front-end
         #[weight = <T as Trait>::WeightInfo::push_header()]
         pub fn push_header(origin, header: Vec<u8>) -> DispatchResultWithPostInfo {
@@ -320,11 +454,11 @@ decl_module! {
             Ok(())
         }
 
-        /// Trustees sign a withdrawal proposal. If `tx` is None, means this trustee vote to reject
-        /// this proposal. If `tx` is Some(), the inner part must be a valid transaction with this
-        /// trustee signature.
+        /// Trustees sign the withdrawal proposal `txid` (the unsigned txid of proposal tx). If `tx`
+        /// is None, means this trustee vote to reject this proposal. If `tx` is Some(), the inner
+        /// part must be a valid transaction with this trustee signature.
         #[weight = <T as Trait>::WeightInfo::sign_withdraw_tx()]
-        pub fn sign_withdraw_tx(origin, tx: Option<Vec<u8>>) -> DispatchResult {
+        pub fn sign_withdraw_tx(origin, txid: H256, tx: Option<Vec<u8>>) -> DispatchResult {
             let from = ensure_signed(origin)?;
             Self::ensure_trustee(&from)?;
 
@@ -335,7 +469,45 @@ decl_module! {
             };
             native!(debug, "[sign_withdraw_tx] from:{:?}, vote_tx:{:?}", from, tx);
 
-            Self::apply_sig_withdraw(from, tx)?;
+            Self::apply_sig_withdraw(from, txid, tx)?;
+            Ok(())
+        }
+
+        /// Trustee propose a replacement tx for the finished withdrawal proposal `txid` whose tx is
+        /// stuck due to the low fee. The replacement must spend the same inputs, keep the withdrawal
+        /// outputs and pay a higher fee. The rule of signing is same to `create_withdraw_tx`.
+        ///
+        /// Fee bumping is done by replacement (BIP125) only, not CPFP. The withdrawal txs always
+        /// signal the replaceability and all the inputs are controlled by the trustees, while a
+        /// child tx would have to spend the unconfirmed change output, which may not exist (dust
+        /// change) and is unknown to the trustee utxo set until the parent tx is relayed.
+        #[weight = <T as Trait>::WeightInfo::create_withdraw_tx()]
+        pub fn propose_replacement_tx(origin, txid: H256, tx: Vec<u8>) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            Self::ensure_trustee(&from)?;
+
+            let tx = Self::deserialize_tx(tx.as_slice())?;
+            native!(debug, "[propose_replacement_tx] from:{:?}, tx:{:?}", from, tx);
+
+            Self::apply_propose_replacement(from, txid, tx)?;
+            Ok(())
+        }
+
+        /// Trustees sign the replacement tx. The rule of voting is same to `sign_withdraw_tx`.
+        /// When the replacement is finished, it would replace the tx of the withdrawal proposal.
+        #[weight = <T as Trait>::WeightInfo::sign_withdraw_tx()]
+        pub fn sign_replacement_tx(origin, txid: H256, tx: Option<Vec<u8>>) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            Self::ensure_trustee(&from)?;
+
+            let tx = if let Some(raw_tx) = tx {
+                Some(Self::deserialize_tx(raw_tx.as_slice())?)
+            } else {
+                None
+            };
+            native!(debug, "[sign_replacement_tx] from:{:?}, vote_tx:{:?}", from, tx);
+
+            Self::apply_sig_replacement(from, txid, tx)?;
             Ok(())
         }
 
@@ -366,35 +538,70 @@ decl_module! {
                 remove_pending_deposit::<T>(&addr, &w);
             } else {
                 info!("[remove_pending] Release pending deposit directly, not deposit to someone, addr:{:?}", try_addr(&addr));
-                PendingDeposits::remove(&addr);
+                for cache in PendingDeposits::take(&addr) {
+                    PendingDepositBlock::remove(&cache.txid);
+                }
             }
             Ok(())
         }
 
-        /// Dangerous! remove current withdrawal proposal directly. Please check business logic before
-        /// do this operation.
+        /// Claim the pending deposits of the Bitcoin `address` (deposited without OP_RETURN), and
+        /// bind `address` to the caller.
+        ///
+        /// `signature` is the compact signature (65 bytes) of the "Bitcoin Signed Message" from
+        /// the key of `address`, and the signed message is the SS58 text of the caller's account
+        /// in the ChainX address format (44).
+        #[weight = <T as Trait>::WeightInfo::claim_pending_deposits()]
+        pub fn claim_pending_deposits(origin, address: BtcAddress, signature: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::pending_deposits(&address).is_empty(), Error::<T>::NoPendingDeposit);
+            verify_address_ownership::<T>(&who, &address, &signature)?;
+
+            Self::apply_bind_address(who, address);
+            Ok(())
+        }
+
+        /// Bind the Bitcoin `address` to the caller, then the deposits from `address` without
+        /// OP_RETURN would be credited to the caller. The pending deposits of `address` would be
+        /// claimed as well.
+        ///
+        /// `signature` is the same as `claim_pending_deposits`.
+        #[weight = <T as Trait>::WeightInfo::bind_btc_address()]
+        pub fn bind_btc_address(origin, address: BtcAddress, signature: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            verify_address_ownership::<T>(&who, &address, &signature)?;
+
+            Self::apply_bind_address(who, address);
+            Ok(())
+        }
+
+        /// Dangerous! remove the withdrawal proposal `txid` directly. Please check business logic
+        /// before do this operation.
         #[weight = <T as Trait>::WeightInfo::remove_proposal()]
-        pub fn remove_proposal(origin) -> DispatchResult {
+        pub fn remove_proposal(origin, txid: H256) -> DispatchResult {
             ensure_root(origin)?;
-            WithdrawalProposal::<T>::kill();
+            WithdrawalProposals::<T>::remove(txid);
+            WithdrawalReplacements::<T>::remove(txid);
             Ok(())
         }
 
-        /// Dangerous! force replace current withdrawal proposal transaction. Please check business
-        /// logic before do this operation. Must make sure current proposal transaction is invalid
-        /// (e.g. when created a proposal, the inputs are not in double spend state, but after other
-        /// trustees finish signing, the inputs are in double spend due other case. Thus could create
-        /// a new valid transaction which outputs same to current proposal to replace current proposal
-        /// transaction.)
+        /// Dangerous! force replace the transaction of withdrawal proposal `txid`. Please check
+        /// business logic before do this operation. Must make sure current proposal transaction
+        /// is invalid (e.g. when created a proposal, the inputs are not in double spend state, but
+        /// after other trustees finish signing, the inputs are in double spend due other case.
+        /// Thus could create a new valid transaction which outputs same to current proposal to
+        /// replace current proposal transaction.)
         #[weight = <T as Trait>::WeightInfo::force_replace_proposal_tx()]
-        pub fn force_replace_proposal_tx(origin, tx: Vec<u8>) -> DispatchResult {
+        pub fn force_replace_proposal_tx(origin, txid: H256, tx: Vec<u8>) -> DispatchResult {
             T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
             let tx = Self::deserialize_tx(tx.as_slice())?;
             native!(debug, "[force_replace_proposal_tx] new_tx:{:?}", tx);
-            Self::force_replace_withdraw_tx(tx)
+            Self::force_replace_withdraw_tx(txid, tx)
         }
 
         /// Set bitcoin withdrawal fee
+        ///
+        /// It would be overridden by the next relayed withdrawal tx if the fee bounds are set.
         #[weight = <T as Trait>::WeightInfo::set_btc_withdrawal_fee()]
         pub fn set_btc_withdrawal_fee(origin, #[compact] fee: u64) -> DispatchResult {
             T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
@@ -409,6 +616,99 @@ decl_module! {
             BtcMinDeposit::put(value);
             Ok(())
         }
+
+        /// Set the retention window of bitcoin headers, headers lower than
+        /// `ConfirmedIndex - window` would be pruned.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_header_retention_window()]
+        pub fn set_header_retention_window(origin, #[compact] window: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(window >= Self::confirmation_number(), Error::<T>::InvalidRetentionWindow);
+            HeaderRetentionWindow::put(window);
+            Ok(())
+        }
+
+        /// Set the max count of main chain blocks a new best header could switch, a deeper reorg
+        /// could be applied by raising it temporarily.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_max_reorg_depth()]
+        pub fn set_max_reorg_depth(origin, #[compact] depth: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(depth >= Self::confirmation_number(), Error::<T>::InvalidReorgDepth);
+            MaxReorgDepth::put(depth);
+            Ok(())
+        }
+
+        /// Set the max count of the in-flight withdrawal proposals.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_max_proposal_count()]
+        pub fn set_max_proposal_count(origin, #[compact] count: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            MaxProposalCount::put(count);
+            Ok(())
+        }
+
+        /// Set the ratio of the withdrawal fee income rewarded to the trustees who signed the
+        /// withdrawal tx.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_trustee_reward_ratio()]
+        pub fn set_trustee_reward_ratio(origin, ratio: Perbill) -> DispatchResult {
+            ensure_root(origin)?;
+            TrusteeRewardRatio::put(ratio);
+            Ok(())
+        }
+
+        /// Set the count of the consecutive withdrawal proposals a trustee could miss before
+        /// being reported as an offender, 0 means no limit.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_max_missed_proposals()]
+        pub fn set_max_missed_proposals(origin, #[compact] count: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            MaxMissedProposals::put(count);
+            Ok(())
+        }
+
+        /// Set the (min, max) bounds of the withdrawal fee, which is derived from the fee rates of
+        /// the relayed withdrawal txs within the bounds, None means setting it manually.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_btc_withdrawal_fee_bounds()]
+        pub fn set_btc_withdrawal_fee_bounds(origin, bounds: Option<(u64, u64)>) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some((min, max)) = bounds {
+                ensure!(min <= max, Error::<T>::InvalidFeeBounds);
+            }
+            BtcWithdrawalFeeBounds::set(bounds);
+            tx::fee::update_withdrawal_fee::<T>();
+            Ok(())
+        }
+
+        /// Seed the trustee utxo set with the unspent outputs of the trustee addresses which were
+        /// created before the utxo tracking, the existing ones would be skipped. `complete` marks
+        /// the utxo set has been fully seeded.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::seed_trustee_utxos(utxos.len() as u32)]
+        pub fn seed_trustee_utxos(origin, utxos: Vec<BtcTrusteeUtxo>, complete: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::apply_seed_trustee_utxos(utxos, complete)
+        }
+
+        /// Set the account credited with the withdrawal fee income except the trustee reward,
+        /// e.g. the trustee reward pot, None means the treasury.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee_destination()]
+        pub fn set_withdrawal_fee_destination(origin, destination: Option<T::AccountId>) -> DispatchResult {
+            ensure_root(origin)?;
+            WithdrawalFeeDestination::<T>::set(destination);
+            Ok(())
+        }
     }
 }
 
@@ -460,6 +760,16 @@ impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
     }
 }
 
+impl<T: Trait> AddressOwnership<T::AccountId> for Module<T> {
+    fn verify_address_ownership(
+        who: &T::AccountId,
+        address: &[u8],
+        signature: &[u8],
+    ) -> DispatchResult {
+        verify_address_ownership::<T>(who, address, signature)
+    }
+}
+
 impl<T: Trait> Module<T> {
     pub fn verify_btc_address(data: &[u8]) -> Result<Address, DispatchError> {
         let r = bs58::decode(data)
@@ -469,12 +779,169 @@ impl<T: Trait> Module<T> {
         Ok(addr)
     }
 
+    /// Build the unsigned withdrawal tx for the pending withdrawal records, which is used by
+    /// the trustees for creating the withdrawal proposal.
+    ///
+    /// If `utxos` is empty, the tracked utxos of the hot trustee address would be used, except
+    /// the ones spent by the in-flight withdrawal proposals.
+    pub fn build_withdrawal_tx(
+        withdrawal_id_list: Vec<u32>,
+        fee_rate: u64,
+        utxos: Vec<BtcUtxo>,
+    ) -> Result<Transaction, DispatchError> {
+        let mut withdrawal_id_list = withdrawal_id_list;
+        withdrawal_id_list.sort();
+        withdrawal_id_list.dedup();
+        let utxos = if utxos.is_empty() {
+            let hot_addr = addr2vecu8(&get_hot_trustee_address::<T>()?);
+            // the replacements spend the same inputs as the proposals
+            let spent = WithdrawalProposals::<T>::iter()
+                .flat_map(|(_, proposal)| proposal.tx.inputs)
+                .map(|input| (input.previous_output.txid, input.previous_output.index))
+                .collect::<Vec<_>>();
+            Self::trustee_utxo_set()
+                .into_iter()
+                .filter(|utxo| utxo.addr == hot_addr && !spent.contains(&(utxo.txid, utxo.vout)))
+                .map(|utxo| BtcUtxo {
+                    txid: utxo.txid,
+                    vout: utxo.vout,
+                    value: utxo.value,
+                })
+                .collect()
+        } else {
+            utxos
+        };
+        tx::builder::build_withdrawal_tx::<T>(&withdrawal_id_list, fee_rate, utxos)
+    }
+
+    fn apply_seed_trustee_utxos(utxos: Vec<BtcTrusteeUtxo>, complete: bool) -> DispatchResult {
+        let (hot, cold) = get_current_trustee_address_pair::<T>()?;
+        let mut trustee_addrs = vec![hot, cold];
+        if let Ok((hot, cold)) = get_last_trustee_address_pair::<T>() {
+            trustee_addrs.push(hot);
+            trustee_addrs.push(cold);
+        }
+        for utxo in utxos.iter() {
+            let addr = Self::verify_btc_address(&utxo.addr)?;
+            ensure!(
+                trustee_addrs
+                    .iter()
+                    .any(|trustee| trustee.hash == addr.hash),
+                Error::<T>::NotTrusteeUtxo
+            );
+        }
+
+        let mut seeded = 0u32;
+        for utxo in utxos {
+            let outpoint = (utxo.txid, utxo.vout);
+            if TrusteeUtxos::contains_key(&outpoint) {
+                continue;
+            }
+            TrusteeUtxoBalance::mutate(|balance| *balance = balance.saturating_add(utxo.value));
+            TrusteeUtxos::insert(outpoint, utxo);
+            seeded += 1;
+        }
+        if complete {
+            TrusteeUtxoSeeded::put(true);
+        }
+        info!(
+            "[apply_seed_trustee_utxos] Seeded {} trustee utxos, complete:{}",
+            seeded, complete
+        );
+        Self::deposit_event(Event::<T>::TrusteeUtxosSeeded(seeded, complete));
+        Ok(())
+    }
+
+    /// Move the single in-flight withdrawal proposal (and its replacement) in the storage values
+    /// `WithdrawalProposal` and `WithdrawalReplacement` into the maps keyed by the unsigned txid.
+    ///
+    /// The legacy proposal does not keep the withdrawal fee, which was only changed by root
+    /// before, thus the current withdrawal fee is the one charged by the proposal.
+    fn migrate_withdrawal_proposals() -> Weight {
+        let db_weight = T::DbWeight::get();
+        let withdrawal_fee = Self::btc_withdrawal_fee();
+        let proposal = take_storage_value::<LegacyBtcWithdrawalProposal<T::AccountId>>(
+            b"XGatewayBitcoin",
+            b"WithdrawalProposal",
+            &[],
+        );
+        let replacement = take_storage_value::<LegacyBtcWithdrawalProposal<T::AccountId>>(
+            b"XGatewayBitcoin",
+            b"WithdrawalReplacement",
+            &[],
+        );
+        let proposal = match proposal {
+            Some(proposal) => proposal.upgrade(withdrawal_fee),
+            None => return db_weight.reads_writes(3, 2),
+        };
+        let txid = tx::unsigned_txid(&proposal.tx);
+        info!(
+            "[migrate_withdrawal_proposals] Migrate withdrawal proposal, unsigned txid:{:?}",
+            txid
+        );
+        WithdrawalProposals::<T>::insert(txid, proposal);
+        if let Some(replacement) = replacement {
+            WithdrawalReplacements::<T>::insert(txid, replacement.upgrade(withdrawal_fee));
+        }
+        db_weight.reads_writes(3, 4)
+    }
+
+    /// Returns the account of the trustee reward pot.
+    pub fn trustee_reward_pot() -> T::AccountId {
+        TRUSTEE_REWARD_POT_ID.into_account()
+    }
+
+    /// Returns all the tracked unspent outputs of the trustee addresses.
+    pub fn trustee_utxo_set() -> Vec<BtcTrusteeUtxo> {
+        TrusteeUtxos::iter().map(|(_, utxo)| utxo).collect()
+    }
+
+    /// Returns the reserve report of X-BTC.
+    pub fn reserve_report() -> BtcReserveReport<BalanceOf<T>> {
+        let mut pending_withdrawals = BalanceOf::<T>::zero();
+        let mut delayed_withdrawals = BalanceOf::<T>::zero();
+        for withdrawal in
+            xpallet_gateway_records::Module::<T>::withdrawals_list_by_chain(Chain::Bitcoin).values()
+        {
+            match withdrawal.state {
+                WithdrawalState::Delayed | WithdrawalState::Frozen => {
+                    delayed_withdrawals = delayed_withdrawals.saturating_add(withdrawal.balance)
+                }
+                _ => pending_withdrawals = pending_withdrawals.saturating_add(withdrawal.balance),
+            }
+        }
+        let pending_deposits = PendingDeposits::iter()
+            .flat_map(|(_, caches)| caches)
+            .fold(0u64, |acc, cache| acc.saturating_add(cache.balance));
+        BtcReserveReport {
+            trustee_balance: Self::trustee_utxo_balance(),
+            utxo_set_seeded: Self::trustee_utxo_seeded(),
+            total_issuance: xpallet_assets::Module::<T>::total_issuance(
+                &<Self as ChainT<_>>::ASSET_ID,
+            ),
+            pending_withdrawals,
+            delayed_withdrawals,
+            pending_deposits,
+        }
+    }
+
     /// Helper function for deserializing the slice of raw tx.
     #[inline]
     fn deserialize_tx(input: &[u8]) -> Result<Transaction, Error<T>> {
         deserialize(Reader::new(input)).map_err(|_| Error::<T>::DeserializeErr)
     }
 
+    fn apply_bind_address(who: T::AccountId, address: BtcAddress) {
+        info!(
+            "[apply_bind_address] Bind address:{:?} to who:{:?}",
+            try_addr(&address),
+            who
+        );
+        // same as the deposit with OP_RETURN, claim the pending deposits before binding
+        remove_pending_deposit::<T>(&address, &who);
+        T::AddressBinding::update_binding(Self::chain(), address, who);
+    }
+
     fn apply_push_header(header: BtcHeader) -> DispatchResult {
         // current should not exist
         if Self::headers(&header.hash()).is_some() {
@@ -525,6 +992,8 @@ impl<T: Trait> Module<T> {
             let best_index = Self::best_index();
 
             if header_info.height > best_index.height {
+                // the reverted blocks are walked in this call, bound them before switching.
+                header::check_reorg_depth::<T>(&header_info)?;
                 // note update_confirmed_header would mutate other storage depend on BlockHashFor
                 let confirmed_index = header::update_confirmed_header::<T>(&header_info);
                 info!(
@@ -580,6 +1049,7 @@ impl<T: Trait> Module<T> {
             return Err(Error::<T>::UnconfirmedTx.into());
         }
         // check whether replayed tx has been processed, just process failed and not processed tx;
+        ensure!(!Self::processed_tx(&tx_hash), Error::<T>::ReplayedTx);
         match Self::tx_state(&tx_hash) {
             None => { /* do nothing */ }
             Some(state) => {
@@ -600,12 +1070,18 @@ impl<T: Trait> Module<T> {
         let state = tx::process_tx::<T>(
             tx.raw,
             prev_tx,
+            block_hash,
             network,
             min_deposit,
             current_trustee_pair,
             last_trustee_pair,
         );
         TxState::insert(&tx_hash, state);
+        TxsOfHeight::mutate(height, |txs| {
+            if !txs.contains(&tx_hash) {
+                txs.push(tx_hash);
+            }
+        });
         Self::deposit_event(Event::<T>::TxProcessed(tx_hash, block_hash, state));
         match state.result {
             BtcTxResult::Success => Ok(()),
@@ -11,15 +11,21 @@ use sp_keyring::sr25519;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
-    AccountId32, Perbill,
+    AccountId32, DispatchError, DispatchResult, Perbill,
 };
 
 use chainx_primitives::AssetId;
 use xp_assets_registrar::Chain;
-pub use xp_protocol::{X_BTC, X_ETH};
-use xpallet_assets::AssetRestrictions;
+pub use xp_protocol::{X_BTC, X_DOT, X_ETH};
+use xpallet_assets::{AssetRestrictions, ChainT};
 use xpallet_assets_registrar::AssetInfo;
-use xpallet_gateway_common::types::TrusteeInfoConfig;
+use xpallet_gateway_common::{
+    traits::{AddressOwnership, TrusteeForChain},
+    trustees::ethereum::{EthTrusteeAddrInfo, EthTrusteeType},
+    trustees::local::LocalChainDisabled,
+    trustees::polkadot::{DotTrusteeAddrInfo, DotTrusteeType},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+};
 
 use light_bitcoin::{
     chain::BlockHeader as BtcHeader,
@@ -118,6 +124,8 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+    type WithdrawalHistoryRetention = ();
     type WeightInfo = ();
 }
 
@@ -127,11 +135,72 @@ impl xpallet_gateway_common::Trait for Test {
     type DetermineMultisigAddress = ();
     type Bitcoin = XGatewayBitcoin;
     type BitcoinTrustee = XGatewayBitcoin;
+    type Ethereum = MockEthereum;
+    type EthereumTrustee = MockEthereum;
+    type Polkadot = MockPolkadot;
+    type PolkadotTrustee = MockPolkadot;
+    type Local = LocalChainDisabled;
+    type LocalTrustee = LocalChainDisabled;
     type WeightInfo = ();
 }
 
+pub struct MockEthereum;
+impl ChainT<Balance> for MockEthereum {
+    const ASSET_ID: AssetId = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+impl TrusteeForChain<AccountId, EthTrusteeType, EthTrusteeAddrInfo> for MockEthereum {
+    fn check_trustee_entity(_: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+pub struct MockPolkadot;
+impl ChainT<Balance> for MockPolkadot {
+    const ASSET_ID: AssetId = X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+impl AddressOwnership<AccountId> for MockPolkadot {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+}
+impl TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo> for MockPolkadot {
+    fn check_trustee_entity(_: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
 thread_local! {
     pub static NOW: RefCell<Option<Duration>> = RefCell::new(None);
+    pub static REPORTED_OFFENDERS: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+}
+
+pub struct OffenderReporter;
+impl xpallet_support::traits::ReportOffenders<AccountId> for OffenderReporter {
+    fn report_offenders(offenders: &[AccountId]) {
+        REPORTED_OFFENDERS.with(|r| r.borrow_mut().extend_from_slice(offenders));
+    }
 }
 
 pub struct Timestamp;
@@ -162,6 +231,7 @@ impl Trait for Test {
     >;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type OffenderReporter = OffenderReporter;
     type WeightInfo = ();
 }
 
@@ -1,14 +1,32 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::{OnInitialize, OnRuntimeUpgrade},
+};
+use frame_system::RawOrigin;
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
+
+use light_bitcoin::{
+    chain::BlockHeader,
+    keys::Network,
+    primitives::{h256_rev, hash_rev, H256},
+    serialization,
+};
 
-use light_bitcoin::{chain::BlockHeader, keys::Network, primitives::h256_rev, serialization};
+use xp_gateway_bitcoin::BtcTxType;
 
+use super::tx::{
+    account3, account4, deposit3_0, deposit3_1, deposit3_1_prev, deposit3_addr, deposit4_0,
+    deposit4_0_prev, deposit4_1, deposit4_1_prev, deposit4_addr, process_tx_in_block,
+};
 use crate::mock::{
-    generate_blocks_478557_478563, generate_blocks_576576_578692, ExtBuilder, XGatewayBitcoin,
-    XGatewayBitcoinErr,
+    charlie, generate_blocks_478557_478563, generate_blocks_576576_578692, ExtBuilder, Test,
+    XAssets, XGatewayBitcoin, XGatewayBitcoinErr, XGatewayRecords, X_BTC,
 };
-use crate::types::BtcHeaderIndex;
+use crate::types::{BtcCreditedDeposit, BtcHeaderIndex, BtcTxResult, BtcTxState};
+use crate::{ConfirmedIndex, LegacyTxStatesCompacted, MaxReorgDepth, TxState, TxsOfHeight};
 
 #[test]
 fn test_genesis() {
@@ -255,3 +273,291 @@ fn test_call() {
         assert_ok!(XGatewayBitcoin::push_header(origin, v));
     })
 }
+
+#[test]
+fn test_prune_ancient_headers() {
+    ExtBuilder::default().build_and_execute(|| {
+        let headers = generate_blocks_576576_578692();
+        // 578592 (2016 * 287) is a retarget height
+        let retarget_height = 576576 + 2016;
+        for i in 576577..=retarget_height + 20 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i].clone()));
+        }
+
+        assert_noop!(
+            XGatewayBitcoin::set_header_retention_window(RawOrigin::Root.into(), 1),
+            XGatewayBitcoinErr::InvalidRetentionWindow,
+        );
+        assert_ok!(XGatewayBitcoin::set_header_retention_window(
+            RawOrigin::Root.into(),
+            10
+        ));
+
+        // mock relayed txs
+        let success_tx = H256::repeat_byte(1);
+        let failure_tx = H256::repeat_byte(2);
+        let tx_height = 576580;
+        TxState::insert(
+            &success_tx,
+            BtcTxState {
+                tx_type: BtcTxType::Deposit,
+                result: BtcTxResult::Success,
+            },
+        );
+        TxState::insert(
+            &failure_tx,
+            BtcTxState {
+                tx_type: BtcTxType::Deposit,
+                result: BtcTxResult::Failure,
+            },
+        );
+        TxsOfHeight::insert(tx_height, vec![success_tx, failure_tx]);
+
+        let confirmed = XGatewayBitcoin::confirmed_index().unwrap();
+        let prune_to = confirmed.height - 10;
+        while XGatewayBitcoin::next_prune_height() < prune_to {
+            XGatewayBitcoin::on_initialize(1);
+        }
+        assert_eq!(XGatewayBitcoin::next_prune_height(), prune_to);
+
+        // genesis header is retained
+        assert!(XGatewayBitcoin::headers(&headers[&576576].hash()).is_some());
+        for i in 576577..prune_to {
+            let hash = headers[&i].hash();
+            if i == retarget_height {
+                assert!(XGatewayBitcoin::headers(&hash).is_some());
+                assert!(XGatewayBitcoin::main_chain(&hash));
+                assert_eq!(XGatewayBitcoin::block_hash_for(i), vec![hash]);
+            } else {
+                assert!(XGatewayBitcoin::headers(&hash).is_none());
+                assert!(!XGatewayBitcoin::main_chain(&hash));
+                assert!(XGatewayBitcoin::block_hash_for(i).is_empty());
+            }
+        }
+        for i in prune_to..=retarget_height + 20 {
+            assert!(XGatewayBitcoin::headers(&headers[&i].hash()).is_some());
+        }
+
+        // tx states are compacted into the replay-protection set
+        assert!(XGatewayBitcoin::txs_of_height(tx_height).is_empty());
+        assert_eq!(XGatewayBitcoin::tx_state(&success_tx), None);
+        assert_eq!(XGatewayBitcoin::tx_state(&failure_tx), None);
+        assert!(XGatewayBitcoin::processed_tx(&success_tx));
+        assert!(!XGatewayBitcoin::processed_tx(&failure_tx));
+
+        // nothing to prune until confirmed index moves
+        let confirmed = ConfirmedIndex::get();
+        XGatewayBitcoin::on_initialize(2);
+        assert_eq!(XGatewayBitcoin::next_prune_height(), prune_to);
+        assert_eq!(ConfirmedIndex::get(), confirmed);
+
+        // still could push new headers after pruning
+        assert_ok!(XGatewayBitcoin::apply_push_header(
+            headers[&(retarget_height + 21)].clone()
+        ));
+        XGatewayBitcoin::on_initialize(3);
+        assert_eq!(XGatewayBitcoin::next_prune_height(), prune_to + 1);
+    })
+}
+
+#[test]
+fn test_compact_legacy_tx_states() {
+    ExtBuilder::default().build_and_execute(|| {
+        // mock the tx states relayed before `TxsOfHeight` existed
+        LegacyTxStatesCompacted::put(false);
+        let success_txs = vec![H256::repeat_byte(1), H256::repeat_byte(3)];
+        let failure_tx = H256::repeat_byte(2);
+        for txid in success_txs.iter() {
+            TxState::insert(
+                txid,
+                BtcTxState {
+                    tx_type: BtcTxType::Deposit,
+                    result: BtcTxResult::Success,
+                },
+            );
+        }
+        TxState::insert(
+            &failure_tx,
+            BtcTxState {
+                tx_type: BtcTxType::Withdrawal,
+                result: BtcTxResult::Failure,
+            },
+        );
+
+        // nothing is compacted before the upgrade
+        XGatewayBitcoin::on_initialize(1);
+        assert!(XGatewayBitcoin::tx_state(&failure_tx).is_some());
+
+        XGatewayBitcoin::on_runtime_upgrade();
+        assert!(XGatewayBitcoin::legacy_tx_state_cursor().is_some());
+        // compact one tx state per block
+        for _ in 0..3 {
+            crate::header::compact_legacy_tx_states::<Test>(1);
+            assert!(XGatewayBitcoin::legacy_tx_state_cursor().is_some());
+        }
+        assert_eq!(TxState::iter().count(), 0);
+        crate::header::compact_legacy_tx_states::<Test>(1);
+        assert_eq!(XGatewayBitcoin::legacy_tx_state_cursor(), None);
+        assert!(XGatewayBitcoin::legacy_tx_states_compacted());
+
+        for txid in success_txs.iter() {
+            assert!(XGatewayBitcoin::processed_tx(txid));
+        }
+        assert!(!XGatewayBitcoin::processed_tx(&failure_tx));
+
+        // the compaction is not started again by the following upgrades
+        XGatewayBitcoin::on_runtime_upgrade();
+        assert_eq!(XGatewayBitcoin::legacy_tx_state_cursor(), None);
+    })
+}
+
+#[test]
+fn test_revert_deposits_on_deep_reorg() {
+    // e.g.
+    // b0
+    // b --- b --- b(deposits) --- b(claim) --- b --- b
+    //       |---- b ------------- b ---------- b --- b --- b
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    let (base_height, c1, forked) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((c1.get(0).unwrap().clone(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            for header in &c1[1..6] {
+                assert_ok!(XGatewayBitcoin::apply_push_header(header.clone()));
+            }
+            let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+            assert_eq!(confirmed_index.hash, c1.get(2).unwrap().hash());
+
+            let (block_hash, claim_block_hash) =
+                (c1.get(2).unwrap().hash(), c1.get(3).unwrap().hash());
+            // a deposit with opreturn
+            let r = process_tx_in_block::<Test>(deposit3_0.clone(), None, block_hash);
+            assert_eq!(r.result, BtcTxResult::Success);
+            // deposits without opreturn, become pending deposits
+            let r = process_tx_in_block::<Test>(
+                deposit3_1.clone(),
+                Some(deposit3_1_prev.clone()),
+                block_hash,
+            );
+            assert_eq!(r.result, BtcTxResult::Success);
+            let r = process_tx_in_block::<Test>(
+                deposit4_0.clone(),
+                Some(deposit4_0_prev.clone()),
+                block_hash,
+            );
+            assert_eq!(r.result, BtcTxResult::Success);
+            // claim the pending deposit of deposit4_0 in the next block
+            let r = process_tx_in_block::<Test>(
+                deposit4_1.clone(),
+                Some(deposit4_1_prev.clone()),
+                claim_block_hash,
+            );
+            assert_eq!(r.result, BtcTxResult::Success);
+
+            assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 100000000);
+            assert_eq!(
+                XAssets::usable_balance(&account4, &X_BTC),
+                100000000 + 387439
+            );
+            assert_eq!(XGatewayBitcoin::pending_deposits(&*deposit3_addr).len(), 1);
+            assert!(XGatewayBitcoin::pending_deposits(&*deposit4_addr).is_empty());
+            // the claimed deposit would be reverted along with the block containing it
+            assert_eq!(
+                XGatewayBitcoin::credited_deposits(&block_hash),
+                vec![
+                    BtcCreditedDeposit {
+                        txid: deposit3_0.hash(),
+                        who: account3.clone(),
+                        balance: 100000000,
+                    },
+                    BtcCreditedDeposit {
+                        txid: deposit4_0.hash(),
+                        who: account4.clone(),
+                        balance: 100000000,
+                    },
+                ]
+            );
+            assert_eq!(XGatewayBitcoin::trustee_utxo_set().len(), 4);
+            assert_eq!(
+                XGatewayRecords::deposits_by_txid(hash_rev(deposit3_0.hash())).len(),
+                1
+            );
+            // account4 has moved a part of the deposit away
+            assert_ok!(XAssets::move_usable_balance(
+                &X_BTC,
+                &account4,
+                &charlie(),
+                60
+            ));
+
+            for header in &forked[2..5] {
+                assert_ok!(XGatewayBitcoin::apply_push_header(header.clone()));
+            }
+            // the fork is deeper than the confirmations, it would be rejected unless the root
+            // acknowledges it.
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(forked.get(5).unwrap().clone()),
+                XGatewayBitcoinErr::AncientFork,
+            );
+            assert_ok!(XGatewayBitcoin::set_confirmed_index(
+                RawOrigin::Root.into(),
+                BtcHeaderIndex {
+                    hash: forked.get(2).unwrap().hash(),
+                    height: base_height + 2,
+                }
+            ));
+            assert_ok!(XGatewayBitcoin::apply_push_header(
+                forked.get(5).unwrap().clone()
+            ));
+            should_in_mainchain(&c1[2..6], true);
+            assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 100000000);
+
+            // mock a max reorg depth lower than the depth of this reorg (4)
+            MaxReorgDepth::put(3);
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(forked.get(6).unwrap().clone()),
+                XGatewayBitcoinErr::ReorgTooDeep,
+            );
+            assert_noop!(
+                XGatewayBitcoin::set_max_reorg_depth(RawOrigin::Root.into(), 3),
+                XGatewayBitcoinErr::InvalidReorgDepth,
+            );
+            assert_ok!(XGatewayBitcoin::set_max_reorg_depth(
+                RawOrigin::Root.into(),
+                4
+            ));
+
+            // the forked chain overtakes the normal chain
+            assert_ok!(XGatewayBitcoin::apply_push_header(
+                forked.get(6).unwrap().clone()
+            ));
+            should_in_mainchain(&c1[2..6], false);
+            should_in_mainchain(&forked[0..7], true);
+
+            assert_eq!(XAssets::usable_balance(&account3, &X_BTC), 0);
+            assert_eq!(XAssets::usable_balance(&account4, &X_BTC), 0);
+            assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 60);
+            assert_eq!(XAssets::total_issuance(&X_BTC), 60);
+            assert!(XGatewayBitcoin::credited_deposits(&block_hash).is_empty());
+            assert!(XGatewayBitcoin::credited_deposits(&claim_block_hash).is_empty());
+            // the pending deposits and the trustee utxos are undone
+            assert!(XGatewayBitcoin::pending_deposits(&*deposit3_addr).is_empty());
+            assert_eq!(
+                XGatewayBitcoin::pending_deposit_block(deposit3_1.hash()),
+                None
+            );
+            assert_eq!(
+                XGatewayBitcoin::block_changes(&block_hash),
+                Default::default()
+            );
+            assert!(XGatewayBitcoin::trustee_utxo_set().is_empty());
+            assert_eq!(XGatewayBitcoin::trustee_utxo_balance(), 0);
+            // all the txs could be relayed again
+            for tx in &[&*deposit3_0, &*deposit3_1, &*deposit4_0, &*deposit4_1] {
+                assert_eq!(XGatewayBitcoin::tx_state(&tx.hash()), None);
+            }
+            // the deposit records are removed too
+            assert!(XGatewayRecords::deposits_by_txid(hash_rev(deposit3_0.hash())).is_empty());
+            assert!(XGatewayRecords::deposits_of(account4.clone(), 0, 10).is_empty());
+        })
+}
@@ -1,28 +1,47 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::{assert_noop, assert_ok, storage::StorageValue};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{
+        migration::{get_storage_value, put_storage_value},
+        StorageMap, StorageValue,
+    },
+    traits::OnRuntimeUpgrade,
+    IterableStorageMap,
+};
 use frame_system::RawOrigin;
 use hex_literal::hex;
+use sp_runtime::Perbill;
 
 use light_bitcoin::{
     chain::Transaction,
     crypto::dhash160,
     keys::{Address, Network, Public, Type},
+    primitives::H256,
     script::{Builder, Opcode},
     serialization::{self, Reader},
 };
 
+use xp_gateway_bitcoin::extract_output_addr;
+use xpallet_assets::Chain;
 use xpallet_gateway_common::traits::TrusteeForChain;
 
+use super::tx::{process_tx_in_block, withdraw, withdraw_prev};
 use crate::mock::{
-    alice, bob, AccountId, ExtBuilder, Test, XGatewayBitcoin, XGatewayBitcoinErr, XGatewayCommon,
-    XGatewayRecords, X_BTC,
+    alice, bob, charlie, AccountId, ExtBuilder, Test, XAssets, XGatewayBitcoin, XGatewayBitcoinErr,
+    XGatewayCommon, XGatewayRecords, REPORTED_OFFENDERS, X_BTC,
 };
 use crate::{
-    trustee::create_multi_address,
-    tx::validator::parse_and_check_signed_tx_impl,
-    types::{BtcTxVerifier, BtcWithdrawalProposal, VoteResult},
-    Verifier, WithdrawalProposal,
+    trustee::{create_multi_address, get_hot_trustee_address},
+    tx::{
+        addr2vecu8, builder::MAX_BIP125_RBF_SEQUENCE, unsigned_txid,
+        validator::parse_and_check_signed_tx_impl,
+    },
+    types::{
+        BtcTrusteeUtxo, BtcTxResult, BtcTxVerifier, BtcUtxo, BtcWithdrawalProposal,
+        LegacyBtcWithdrawalProposal, VoteResult,
+    },
+    BtcWithdrawalFee, Verifier, WithdrawalProposals,
 };
 
 #[test]
@@ -205,15 +224,658 @@ fn force_replace_withdraw() {
             withdrawal_id_list: vec![0, 1],
             tx: old_withdraw.clone(),
             trustee_list: vec![(alice, true), (bob, true)],
+            withdrawal_fee,
         };
-        WithdrawalProposal::<Test>::put(proposal);
+        let txid = unsigned_txid(&old_withdraw);
+        WithdrawalProposals::<Test>::insert(txid, proposal);
 
         // replace tx
         let mut new_withdraw = old_withdraw;
         new_withdraw.inputs = tmp.inputs; // replace inputs
 
         let raw = serialization::serialize(&new_withdraw);
-        assert_ok!(XGatewayBitcoin::force_replace_proposal_tx(RawOrigin::Root.into(), raw.into()));
-        assert_eq!(XGatewayBitcoin::withdrawal_proposal().unwrap().tx, new_withdraw);
+        assert_ok!(XGatewayBitcoin::force_replace_proposal_tx(
+            RawOrigin::Root.into(),
+            txid,
+            raw.into()
+        ));
+        // the proposal is re-keyed by the new tx
+        assert!(XGatewayBitcoin::withdrawal_proposals(txid).is_none());
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_proposals(unsigned_txid(&new_withdraw))
+                .unwrap()
+                .tx,
+            new_withdraw
+        );
+    });
+}
+
+#[test]
+fn test_build_withdrawal_tx() {
+    ExtBuilder::default().build_and_execute(|| {
+        let alice = alice();
+        let bob = bob();
+        let withdrawal_fee = XGatewayBitcoin::btc_withdrawal_fee();
+
+        let balance = (100_000_000 + withdrawal_fee).into();
+        XGatewayRecords::deposit(&alice, X_BTC, balance).unwrap();
+        XGatewayRecords::deposit(&bob, X_BTC, balance).unwrap();
+        assert_ok!(XGatewayCommon::withdraw(
+            RawOrigin::Signed(alice).into(),
+            X_BTC,
+            balance,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+            b"memo".to_vec().into(),
+        ));
+        assert_ok!(XGatewayCommon::withdraw(
+            RawOrigin::Signed(bob).into(),
+            X_BTC,
+            balance,
+            b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
+            b"memo".to_vec().into(),
+        ));
+
+        let utxo = |byte: u8, value: u64| BtcUtxo {
+            txid: H256::repeat_byte(byte),
+            vout: 0,
+            value,
+        };
+        assert_eq!(
+            XGatewayBitcoin::build_withdrawal_tx(vec![0, 1], 0, vec![utxo(1, 300_000_000)]),
+            Err(XGatewayBitcoinErr::InvalidFeeRate.into())
+        );
+        assert_eq!(
+            XGatewayBitcoin::build_withdrawal_tx(vec![0, 1], 10, vec![utxo(1, 150_000_000)]),
+            Err(XGatewayBitcoinErr::InsufficientUtxo.into())
+        );
+
+        // 2-of-3 multisig input: 43 + 1 + 2 * 74 + 2 + 105 = 299
+        // 2 p2pkh withdrawal outputs: 2 * 34 = 68, 1 p2sh change output: 32
+        let tx = XGatewayBitcoin::build_withdrawal_tx(
+            vec![1, 0],
+            10,
+            vec![
+                utxo(1, 50_000_000),
+                utxo(2, 150_000_000),
+                utxo(3, 60_000_000),
+            ],
+        )
+        .unwrap();
+        let spent = tx
+            .inputs
+            .iter()
+            .map(|input| input.previous_output.txid)
+            .collect::<Vec<_>>();
+        assert_eq!(spent, vec![H256::repeat_byte(2), H256::repeat_byte(3)]);
+        assert_eq!(tx.outputs.len(), 3);
+        let size = 10 + 2 * 299 + 68 + 32;
+        let change = tx.outputs.last().unwrap();
+        assert_eq!(change.value, 210_000_000 - 200_000_000 - size * 10);
+        let hot_addr = get_hot_trustee_address::<Test>().unwrap();
+        assert_eq!(
+            extract_output_addr(change, Network::Mainnet).unwrap().hash,
+            hot_addr.hash
+        );
+        // the built tx is acceptable for creating the withdrawal proposal
+        assert_ok!(XGatewayBitcoin::create_withdraw_tx(
+            RawOrigin::Signed(alice()).into(),
+            vec![0, 1],
+            serialization::serialize(&tx).into()
+        ));
+    });
+}
+
+#[test]
+fn test_replace_withdrawal_tx() {
+    ExtBuilder::default().build_and_execute(|| {
+        let balance = (100_000_000 + XGatewayBitcoin::btc_withdrawal_fee()).into();
+        XGatewayRecords::deposit(&alice(), X_BTC, balance).unwrap();
+        assert_ok!(XGatewayCommon::withdraw(
+            RawOrigin::Signed(alice()).into(),
+            X_BTC,
+            balance,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+            b"memo".to_vec().into(),
+        ));
+        let utxo = BtcUtxo {
+            txid: H256::repeat_byte(1),
+            vout: 0,
+            value: 150_000_000,
+        };
+        let tx = XGatewayBitcoin::build_withdrawal_tx(vec![0], 1, vec![utxo]).unwrap();
+        assert_ok!(XGatewayBitcoin::create_withdraw_tx(
+            RawOrigin::Signed(alice()).into(),
+            vec![0],
+            serialization::serialize(&tx).into()
+        ));
+        let txid = unsigned_txid(&tx);
+
+        let mut bumped = tx.clone();
+        bumped.outputs.last_mut().unwrap().value -= 1_000;
+        let raw_bumped: Vec<u8> = serialization::serialize(&bumped).into();
+        // only the finished proposal could be replaced
+        assert_noop!(
+            XGatewayBitcoin::propose_replacement_tx(
+                RawOrigin::Signed(alice()).into(),
+                txid,
+                raw_bumped.clone()
+            ),
+            XGatewayBitcoinErr::NotFinishProposal
+        );
+        WithdrawalProposals::<Test>::mutate(txid, |proposal| {
+            proposal.as_mut().unwrap().sig_state = VoteResult::Finish;
+        });
+
+        // the replacement must pay a higher fee
+        assert_noop!(
+            XGatewayBitcoin::propose_replacement_tx(
+                RawOrigin::Signed(alice()).into(),
+                txid,
+                serialization::serialize(&tx).into()
+            ),
+            XGatewayBitcoinErr::LowReplacementFee
+        );
+        // the replacement must spend the same inputs
+        let mut other_inputs = bumped.clone();
+        other_inputs.inputs[0].previous_output.txid = H256::repeat_byte(2);
+        assert_noop!(
+            XGatewayBitcoin::propose_replacement_tx(
+                RawOrigin::Signed(alice()).into(),
+                txid,
+                serialization::serialize(&other_inputs).into()
+            ),
+            XGatewayBitcoinErr::MismatchedTx
+        );
+
+        assert_ok!(XGatewayBitcoin::propose_replacement_tx(
+            RawOrigin::Signed(alice()).into(),
+            txid,
+            raw_bumped.clone()
+        ));
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_replacements(txid).unwrap().tx,
+            bumped
+        );
+        assert_noop!(
+            XGatewayBitcoin::propose_replacement_tx(
+                RawOrigin::Signed(bob()).into(),
+                txid,
+                raw_bumped
+            ),
+            XGatewayBitcoinErr::ExistingReplacement
+        );
+
+        // rejected by the trustees, the proposal is kept
+        assert_ok!(XGatewayBitcoin::sign_replacement_tx(
+            RawOrigin::Signed(alice()).into(),
+            txid,
+            None
+        ));
+        assert_ok!(XGatewayBitcoin::sign_replacement_tx(
+            RawOrigin::Signed(bob()).into(),
+            txid,
+            None
+        ));
+        assert!(XGatewayBitcoin::withdrawal_replacements(txid).is_none());
+        assert_eq!(XGatewayBitcoin::withdrawal_proposals(txid).unwrap().tx, tx);
+        assert_noop!(
+            XGatewayBitcoin::sign_replacement_tx(RawOrigin::Signed(alice()).into(), txid, None),
+            XGatewayBitcoinErr::NoReplacement
+        );
+    });
+}
+
+/// Prepare a finished withdrawal proposal whose tx is stuck, the replacement is the real withdrawal
+/// tx `withdraw` (signed by alice and bob) which pays 1000 satoshi more fee.
+fn prepare_stuck_withdrawal() -> (H256, Transaction) {
+    let fee = XGatewayBitcoin::btc_withdrawal_fee();
+    for (who, value, addr) in vec![
+        (
+            alice(),
+            9778400,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+        ),
+        (
+            bob(),
+            9900000,
+            b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
+        ),
+    ] {
+        let balance = (value + fee).into();
+        XGatewayRecords::deposit(&who, X_BTC, balance).unwrap();
+        XGatewayRecords::withdraw(&who, X_BTC, balance, addr, b"memo".to_vec().into()).unwrap();
+    }
+    XGatewayRecords::process_withdrawals(&[0, 1], Chain::Bitcoin).unwrap();
+
+    let mut stuck = withdraw.clone();
+    for input in stuck.inputs.iter_mut() {
+        input.script_sig = Default::default();
+        input.sequence = MAX_BIP125_RBF_SEQUENCE;
+    }
+    stuck.outputs.last_mut().unwrap().value += 1_000;
+    let txid = unsigned_txid(&stuck);
+    WithdrawalProposals::<Test>::insert(
+        txid,
+        BtcWithdrawalProposal {
+            sig_state: VoteResult::Finish,
+            withdrawal_id_list: vec![0, 1],
+            tx: stuck.clone(),
+            trustee_list: vec![(alice(), true), (bob(), true)],
+            withdrawal_fee: fee,
+        },
+    );
+    (txid, stuck)
+}
+
+fn complete_replacement(txid: H256) {
+    // `withdraw` with the signature of alice only
+    let half_signed: Transaction = "0100000001052ceda6cf9c93012a994f4ffa2a29c9e31ecf96f472b175eb8e602bfa2b2c5100000000b40047304402200e4d732c456f4722d376252be16554edb27fc93c55db97859e16682bc62b014502202b9c4b01ad55daa1f76e6a564b7762cd0a81240c947806ab3f3b056f2e77c1da014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff03e0349500000000001976a91413256ff2dee6e80c275ddb877abc1ffe453a731488ace00f9700000000001976a914ea6e8dd56703ace584eb9dff0224629f8486672988acc88a02000000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000".parse().unwrap();
+    assert_ok!(XGatewayBitcoin::propose_replacement_tx(
+        RawOrigin::Signed(alice()).into(),
+        txid,
+        serialization::serialize(&half_signed).into()
+    ));
+    let replacement = XGatewayBitcoin::withdrawal_replacements(txid).unwrap();
+    assert_eq!(replacement.trustee_list, vec![(alice(), true)]);
+    assert_eq!(replacement.sig_state, VoteResult::Unfinish);
+
+    assert_ok!(XGatewayBitcoin::sign_replacement_tx(
+        RawOrigin::Signed(bob()).into(),
+        txid,
+        Some(serialization::serialize(&*withdraw).into())
+    ));
+}
+
+#[test]
+fn test_complete_withdrawal_replacement() {
+    ExtBuilder::default().build_and_execute(|| {
+        let (txid, stuck) = prepare_stuck_withdrawal();
+        complete_replacement(txid);
+
+        // the proposal is re-keyed by the unsigned txid of the replacement
+        let new_txid = unsigned_txid(&withdraw);
+        assert_ne!(new_txid, txid);
+        assert!(XGatewayBitcoin::withdrawal_proposals(txid).is_none());
+        assert!(XGatewayBitcoin::withdrawal_replacements(txid).is_none());
+        let proposal = XGatewayBitcoin::withdrawal_proposals(new_txid).unwrap();
+        assert_eq!(proposal.tx, *withdraw);
+        assert_eq!(proposal.sig_state, VoteResult::Finish);
+        assert_eq!(proposal.withdrawal_id_list, vec![0, 1]);
+        // the signers of the replaced tx are kept
+        assert_eq!(proposal.trustee_list, vec![(alice(), true), (bob(), true)]);
+
+        // the replaced tx can't be replaced again
+        assert_noop!(
+            XGatewayBitcoin::propose_replacement_tx(
+                RawOrigin::Signed(alice()).into(),
+                txid,
+                serialization::serialize(&stuck).into()
+            ),
+            XGatewayBitcoinErr::NoProposal
+        );
+    });
+}
+
+#[test]
+fn test_withdraw_by_replaced_or_original_tx() {
+    // either the replacement or the replaced tx could be packed by miners, both of them settle
+    // the withdrawal proposal
+    for packed_replacement in &[true, false] {
+        ExtBuilder::default().build_and_execute(|| {
+            let (txid, stuck) = prepare_stuck_withdrawal();
+            // the withdrawal fee is updated while the proposal is in flight, the replacement and
+            // the settlement are still checked by the fee charged by the proposal
+            BtcWithdrawalFee::put(XGatewayBitcoin::btc_withdrawal_fee() * 2);
+            complete_replacement(txid);
+
+            let packed = if *packed_replacement {
+                withdraw.clone()
+            } else {
+                stuck.clone()
+            };
+            let state = process_tx_in_block::<Test>(
+                packed,
+                Some(withdraw_prev.clone()),
+                H256::repeat_byte(1),
+            );
+            assert_eq!(state.result, BtcTxResult::Success);
+            assert!(XGatewayBitcoin::withdrawal_proposals(unsigned_txid(&withdraw)).is_none());
+            assert!(XGatewayRecords::pending_withdrawals(0).is_none());
+            assert!(XGatewayRecords::pending_withdrawals(1).is_none());
+        });
+    }
+}
+
+#[test]
+fn test_concurrent_withdrawal_proposals() {
+    ExtBuilder::default().build_and_execute(|| {
+        let balance = (100_000_000 + XGatewayBitcoin::btc_withdrawal_fee()).into();
+        for (who, addr) in vec![
+            (alice(), b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec()),
+            (bob(), b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec()),
+            (charlie(), b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec()),
+        ] {
+            XGatewayRecords::deposit(&who, X_BTC, balance).unwrap();
+            assert_ok!(XGatewayCommon::withdraw(
+                RawOrigin::Signed(who).into(),
+                X_BTC,
+                balance,
+                addr,
+                b"memo".to_vec().into(),
+            ));
+        }
+        let utxo = |byte: u8| BtcUtxo {
+            txid: H256::repeat_byte(byte),
+            vout: 0,
+            value: 150_000_000,
+        };
+        let create = |id: u32, tx: &Transaction| {
+            XGatewayBitcoin::create_withdraw_tx(
+                RawOrigin::Signed(alice()).into(),
+                vec![id],
+                serialization::serialize(tx).into(),
+            )
+        };
+
+        let tx0 = XGatewayBitcoin::build_withdrawal_tx(vec![0], 1, vec![utxo(1)]).unwrap();
+        let tx1 = XGatewayBitcoin::build_withdrawal_tx(vec![1], 1, vec![utxo(2)]).unwrap();
+        assert_ok!(create(0, &tx0));
+        // the in-flight proposal does not block other withdrawals
+        assert_ok!(create(1, &tx1));
+        assert!(XGatewayBitcoin::withdrawal_proposals(unsigned_txid(&tx0)).is_some());
+        assert!(XGatewayBitcoin::withdrawal_proposals(unsigned_txid(&tx1)).is_some());
+
+        // can't spend the utxo spent by other proposals
+        let tx2 = XGatewayBitcoin::build_withdrawal_tx(vec![2], 1, vec![utxo(1)]).unwrap();
+        assert_noop!(create(2, &tx2), XGatewayBitcoinErr::ConflictingProposal);
+        // can't include the withdrawal in other proposals
+        let mut tx0_again = tx0.clone();
+        tx0_again.inputs[0].previous_output.txid = H256::repeat_byte(3);
+        assert_noop!(
+            create(0, &tx0_again),
+            XGatewayBitcoinErr::ConflictingProposal
+        );
+
+        let tx2 = XGatewayBitcoin::build_withdrawal_tx(vec![2], 1, vec![utxo(3)]).unwrap();
+        assert_ok!(XGatewayBitcoin::set_max_proposal_count(
+            RawOrigin::Root.into(),
+            2
+        ));
+        assert_noop!(create(2, &tx2), XGatewayBitcoinErr::TooManyProposals);
+
+        // the vote state of proposals are independent
+        let txid0 = unsigned_txid(&tx0);
+        assert_ok!(XGatewayBitcoin::sign_withdraw_tx(
+            RawOrigin::Signed(alice()).into(),
+            txid0,
+            None
+        ));
+        assert_ok!(XGatewayBitcoin::sign_withdraw_tx(
+            RawOrigin::Signed(bob()).into(),
+            txid0,
+            None
+        ));
+        assert!(XGatewayBitcoin::withdrawal_proposals(txid0).is_none());
+        let proposal1 = XGatewayBitcoin::withdrawal_proposals(unsigned_txid(&tx1)).unwrap();
+        assert!(proposal1.trustee_list.is_empty());
+
+        // the dropped proposal releases its slot and withdrawals
+        assert_ok!(create(2, &tx2));
+        let tx0 = XGatewayBitcoin::build_withdrawal_tx(vec![0], 1, vec![utxo(4)]).unwrap();
+        assert_noop!(create(0, &tx0), XGatewayBitcoinErr::TooManyProposals);
+    });
+}
+
+#[test]
+fn test_build_withdrawal_tx_with_proposal_in_flight() {
+    ExtBuilder::default().build_and_execute(|| {
+        let balance = (100_000_000 + XGatewayBitcoin::btc_withdrawal_fee()).into();
+        for (who, addr) in vec![
+            (alice(), b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec()),
+            (bob(), b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec()),
+        ] {
+            XGatewayRecords::deposit(&who, X_BTC, balance).unwrap();
+            assert_ok!(XGatewayCommon::withdraw(
+                RawOrigin::Signed(who).into(),
+                X_BTC,
+                balance,
+                addr,
+                b"memo".to_vec().into(),
+            ));
+        }
+        let hot_addr = addr2vecu8(&get_hot_trustee_address::<Test>().unwrap());
+        let utxo = |byte: u8, value: u64| BtcTrusteeUtxo {
+            txid: H256::repeat_byte(byte),
+            vout: 0,
+            value,
+            addr: hot_addr.clone(),
+        };
+        assert_ok!(XGatewayBitcoin::seed_trustee_utxos(
+            RawOrigin::Root.into(),
+            vec![utxo(1, 150_000_000), utxo(2, 120_000_000)],
+            true
+        ));
+        let create = |id: u32, tx: &Transaction| {
+            XGatewayBitcoin::create_withdraw_tx(
+                RawOrigin::Signed(alice()).into(),
+                vec![id],
+                serialization::serialize(tx).into(),
+            )
+        };
+
+        // the largest utxo is picked first
+        let tx0 = XGatewayBitcoin::build_withdrawal_tx(vec![0], 1, vec![]).unwrap();
+        assert_eq!(tx0.inputs.len(), 1);
+        assert_eq!(tx0.inputs[0].previous_output.txid, H256::repeat_byte(1));
+        assert_ok!(create(0, &tx0));
+
+        // the utxo spent by the in-flight proposal is skipped
+        let tx1 = XGatewayBitcoin::build_withdrawal_tx(vec![1], 1, vec![]).unwrap();
+        assert_eq!(tx1.inputs.len(), 1);
+        assert_eq!(tx1.inputs[0].previous_output.txid, H256::repeat_byte(2));
+        assert_ok!(create(1, &tx1));
+    });
+}
+
+#[test]
+fn test_trustee_reward() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pot = XGatewayBitcoin::trustee_reward_pot();
+        let trustee_list = vec![(alice(), true), (bob(), true), (charlie(), false)];
+
+        // no reward by default
+        XGatewayBitcoin::reward_trustees(&trustee_list, 1000);
+        assert_eq!(XAssets::usable_balance(&pot, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 0);
+
+        assert_ok!(XGatewayBitcoin::set_trustee_reward_ratio(
+            RawOrigin::Root.into(),
+            Perbill::from_percent(50)
+        ));
+        // only the signers are rewarded, the remainder is kept in the pot
+        XGatewayBitcoin::reward_trustees(&trustee_list, 1002);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 250);
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 250);
+        assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&pot, &X_BTC), 1);
+
+        XGatewayBitcoin::reward_trustees(&trustee_list, 1000);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 250 + 250);
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 250 + 250);
+        assert_eq!(XAssets::usable_balance(&pot, &X_BTC), 1);
+    });
+}
+
+#[test]
+fn test_credit_withdrawal_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pot = XGatewayBitcoin::trustee_reward_pot();
+        let treasury = AccountId::default();
+        let trustee_list = vec![(alice(), true), (bob(), true)];
+
+        // the whole fee goes to the treasury by default
+        XGatewayBitcoin::credit_withdrawal_fee(&trustee_list, 1000);
+        assert_eq!(XAssets::usable_balance(&treasury, &X_BTC), 1000);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 0);
+
+        assert_ok!(XGatewayBitcoin::set_trustee_reward_ratio(
+            RawOrigin::Root.into(),
+            Perbill::from_percent(50)
+        ));
+        assert_ok!(XGatewayBitcoin::set_withdrawal_fee_destination(
+            RawOrigin::Root.into(),
+            Some(charlie())
+        ));
+        // the trustees are rewarded first, and the rest goes to the destination
+        XGatewayBitcoin::credit_withdrawal_fee(&trustee_list, 1002);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 250);
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 250);
+        assert_eq!(XAssets::usable_balance(&pot, &X_BTC), 1);
+        assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 501);
+        assert_eq!(XAssets::usable_balance(&treasury, &X_BTC), 1000);
+        assert_eq!(XAssets::total_issuance(&X_BTC), 1000 + 1002);
+
+        // no trustee reward without signers
+        XGatewayBitcoin::credit_withdrawal_fee(&[(alice(), false)], 1000);
+        assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 501 + 1000);
+        assert_eq!(XAssets::usable_balance(&alice(), &X_BTC), 250);
+    });
+}
+
+#[test]
+fn test_report_missed_trustees() {
+    ExtBuilder::default().build_and_execute(|| {
+        let reported = || REPORTED_OFFENDERS.with(|r| r.borrow().clone());
+
+        // no limit by default
+        XGatewayBitcoin::note_missed_trustees(&[(alice(), true), (bob(), true)]);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(charlie()), 0);
+
+        assert_ok!(XGatewayBitcoin::set_max_missed_proposals(
+            RawOrigin::Root.into(),
+            2
+        ));
+        XGatewayBitcoin::note_missed_trustees(&[(alice(), true), (bob(), true)]);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(charlie()), 1);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(bob()), 0);
+        assert!(reported().is_empty());
+
+        // the veto is also a participation
+        XGatewayBitcoin::note_missed_trustees(&[(alice(), true), (charlie(), false)]);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(charlie()), 0);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(bob()), 1);
+
+        XGatewayBitcoin::note_missed_trustees(&[(alice(), true), (charlie(), true)]);
+        assert_eq!(reported(), vec![bob()]);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(bob()), 0);
+
+        // the vote after the proposal is finished is noted as the participation
+        let (txid, _) = prepare_stuck_withdrawal();
+        XGatewayBitcoin::note_missed_trustees(&[(alice(), true), (bob(), true)]);
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(charlie()), 1);
+        assert_noop!(
+            XGatewayBitcoin::sign_withdraw_tx(RawOrigin::Signed(alice()).into(), txid, None),
+            XGatewayBitcoinErr::RejectSig
+        );
+        let proposal = XGatewayBitcoin::withdrawal_proposals(txid).unwrap();
+        assert_ok!(XGatewayBitcoin::sign_withdraw_tx(
+            RawOrigin::Signed(charlie()).into(),
+            txid,
+            None
+        ));
+        assert_eq!(XGatewayBitcoin::missed_proposals_of(charlie()), 0);
+        assert_eq!(XGatewayBitcoin::withdrawal_proposals(txid), Some(proposal));
+    });
+}
+
+#[test]
+fn test_migrate_withdrawal_proposals() {
+    ExtBuilder::default().build_and_execute(|| {
+        let legacy = |sig_state, trustee_list| LegacyBtcWithdrawalProposal {
+            sig_state,
+            withdrawal_id_list: vec![0, 1],
+            tx: withdraw.clone(),
+            trustee_list,
+        };
+        let proposal = legacy(VoteResult::Finish, vec![(alice(), true), (bob(), true)]);
+        let replacement = legacy(VoteResult::Unfinish, vec![(alice(), true)]);
+        put_storage_value(b"XGatewayBitcoin", b"WithdrawalProposal", &[], &proposal);
+        put_storage_value(
+            b"XGatewayBitcoin",
+            b"WithdrawalReplacement",
+            &[],
+            &replacement,
+        );
+
+        // the current withdrawal fee is kept in the migrated proposals
+        let fee = XGatewayBitcoin::btc_withdrawal_fee();
+        XGatewayBitcoin::on_runtime_upgrade();
+        let txid = unsigned_txid(&withdraw);
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_proposals(txid),
+            Some(proposal.upgrade(fee))
+        );
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_replacements(txid),
+            Some(replacement.upgrade(fee))
+        );
+        assert!(get_storage_value::<LegacyBtcWithdrawalProposal<AccountId>>(
+            b"XGatewayBitcoin",
+            b"WithdrawalProposal",
+            &[]
+        )
+        .is_none());
+
+        // nothing to migrate for the second time
+        XGatewayBitcoin::on_runtime_upgrade();
+        assert_eq!(WithdrawalProposals::<Test>::iter().count(), 1);
+    });
+}
+
+#[test]
+fn test_seed_trustee_utxos() {
+    ExtBuilder::default().build_and_execute(|| {
+        let hot_addr = get_hot_trustee_address::<Test>().unwrap();
+        let utxo = |byte: u8, value: u64| BtcTrusteeUtxo {
+            txid: H256::repeat_byte(byte),
+            vout: 0,
+            value,
+            addr: addr2vecu8(&hot_addr),
+        };
+        assert!(!XGatewayBitcoin::trustee_utxo_seeded());
+
+        // only the utxos of the trustee addresses could be seeded
+        let mut other = utxo(1, 100);
+        other.addr = b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec();
+        assert_noop!(
+            XGatewayBitcoin::seed_trustee_utxos(
+                RawOrigin::Root.into(),
+                vec![utxo(1, 100), other],
+                true
+            ),
+            XGatewayBitcoinErr::NotTrusteeUtxo
+        );
+
+        // seed in batches
+        assert_ok!(XGatewayBitcoin::seed_trustee_utxos(
+            RawOrigin::Root.into(),
+            vec![utxo(1, 100)],
+            false
+        ));
+        assert!(!XGatewayBitcoin::trustee_utxo_seeded());
+        // the existing utxo is skipped
+        assert_ok!(XGatewayBitcoin::seed_trustee_utxos(
+            RawOrigin::Root.into(),
+            vec![utxo(1, 100), utxo(2, 200)],
+            true
+        ));
+        assert!(XGatewayBitcoin::trustee_utxo_seeded());
+        assert_eq!(XGatewayBitcoin::trustee_utxo_set().len(), 2);
+        assert_eq!(XGatewayBitcoin::trustee_utxo_balance(), 300);
+        // the seeded utxos could be used for building the withdrawal tx
+        assert_eq!(
+            XGatewayBitcoin::trustee_utxos((H256::repeat_byte(2), 0)),
+            Some(utxo(2, 200))
+        );
     });
 }
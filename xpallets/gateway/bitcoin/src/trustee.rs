@@ -3,33 +3,40 @@
 use frame_support::{
     debug::native,
     dispatch::{DispatchError, DispatchResult},
-    ensure, StorageValue,
+    ensure, IterableStorageMap, StorageMap,
+};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
 };
-use sp_runtime::SaturatedConversion;
 use sp_std::{convert::TryFrom, prelude::*};
 
 use light_bitcoin::{
     chain::Transaction,
     crypto::dhash160,
     keys::{Address, Public, Type},
-    primitives::Bytes,
+    primitives::{Bytes, H256},
     script::{Builder, Opcode, Script},
 };
 
 use xp_gateway_bitcoin::extract_output_addr;
-use xp_logging::{debug, error, info};
-use xpallet_assets::Chain;
+use xp_logging::{debug, error, info, warn};
+use xpallet_assets::{BalanceOf, Chain, ChainT};
 use xpallet_gateway_common::{
     traits::{TrusteeForChain, TrusteeSession},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
     types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
     utils::two_thirds_unsafe,
 };
+use xpallet_support::traits::{ReportOffenders, TreasuryAccount};
 
 use crate::{
-    tx::{addr2vecu8, ensure_identical, validator::parse_and_check_signed_tx},
+    tx::{
+        addr2vecu8, builder::MAX_BIP125_RBF_SEQUENCE, ensure_identical, unsigned_txid,
+        validator::parse_and_check_signed_tx,
+    },
     types::{BtcWithdrawalProposal, VoteResult},
-    Error, Event, Module, Trait, WithdrawalProposal,
+    Error, Event, MissedProposalsOf, Module, Trait, WithdrawalProposals, WithdrawalReplacements,
 };
 
 pub fn current_trustee_session<T: Trait>(
@@ -252,7 +259,9 @@ impl<T: Trait> Module<T> {
         withdrawal_id_list.sort();
         withdrawal_id_list.dedup();
 
-        check_withdraw_tx::<T>(&tx, &withdrawal_id_list)?;
+        let withdrawal_fee = Self::btc_withdrawal_fee();
+        check_withdraw_tx::<T>(&tx, &withdrawal_id_list, withdrawal_fee)?;
+        let txid = unsigned_txid(&tx);
         info!(
             "[apply_create_withdraw] Create new withdraw, id_list:{:?}",
             withdrawal_id_list
@@ -282,12 +291,14 @@ impl<T: Trait> Module<T> {
             withdrawal_id_list.clone(),
             tx,
             Vec::new(),
+            withdrawal_fee,
         );
 
         info!("[apply_create_withdraw] Pass the legality check of withdrawal");
 
         Self::deposit_event(Event::<T>::WithdrawalProposalCreated(
             who.clone(),
+            txid,
             withdrawal_id_list,
         ));
 
@@ -295,109 +306,66 @@ impl<T: Trait> Module<T> {
             info!("[apply_create_withdraw] Apply sign after creating proposal");
             // due to `SignWithdrawalProposal` event should after `WithdrawalProposalCreated`, thus this function should after proposal
             // but this function would have an error return, this error return should not meet.
-            if insert_trustee_vote_state::<T>(true, &who, &mut proposal.trustee_list).is_err() {
+            if insert_trustee_vote_state::<T>(true, &who, txid, &mut proposal.trustee_list).is_err()
+            {
                 // should not be error in this function, if hit this branch, panic to clear all modification
                 // TODO change to revoke in future
                 panic!("insert_trustee_vote_state should not be error")
             }
         }
 
-        WithdrawalProposal::<T>::put(proposal);
+        WithdrawalProposals::<T>::insert(txid, proposal);
 
         Ok(())
     }
 
-    pub fn apply_sig_withdraw(who: T::AccountId, tx: Option<Transaction>) -> DispatchResult {
+    pub fn apply_sig_withdraw(
+        who: T::AccountId,
+        txid: H256,
+        tx: Option<Transaction>,
+    ) -> DispatchResult {
         let mut proposal: BtcWithdrawalProposal<T::AccountId> =
-            Self::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+            Self::withdrawal_proposals(txid).ok_or(Error::<T>::NoProposal)?;
 
-        if proposal.sig_state == VoteResult::Finish {
-            error!("[apply_sig_withdraw] Proposal is on FINISH state, can't sign for this proposal:{:?}", proposal);
-            return Err(Error::<T>::RejectSig.into());
+        // the signing is closed once enough trustees signed, the later vote of the other trustees
+        // is only noted as their participation, so that they are not counted as missing.
+        if proposal.sig_state == VoteResult::Finish
+            && !proposal.trustee_list.iter().any(|(voter, _)| *voter == who)
+        {
+            info!(
+                "[apply_sig_withdraw] Trustee:{:?} voted for the finished proposal:{:?}",
+                who, txid
+            );
+            MissedProposalsOf::<T>::remove(&who);
+            return Ok(());
         }
 
         let (sig_num, total) = get_sig_num::<T>();
-        match tx {
-            Some(tx) => {
-                // check this tx is same to proposal, just check input and output, not include sigs
-                ensure_identical::<T>(&tx, &proposal.tx)?;
-
-                // sign
-                // check first and get signatures from commit transaction
-                let sigs_count = parse_and_check_signed_tx::<T>(&tx)?;
-                if sigs_count == 0 {
-                    error!("[apply_sig_withdraw] Tx sig should not be zero, zero is the source tx without any sig, tx{:?}", tx);
-                    return Err(Error::<T>::InvalidSignCount.into());
-                }
-
-                let confirmed_count = proposal
-                    .trustee_list
-                    .iter()
-                    .filter(|(_, vote)| *vote)
-                    .count() as u32;
-
-                if sigs_count != confirmed_count + 1 {
-                    error!(
-                        "[apply_sig_withdraw] Need to sign on the latest signature results, sigs count:{}, confirmed count:{}",
-                        sigs_count, confirmed_count
-                    );
-                    return Err(Error::<T>::InvalidSignCount.into());
-                }
-
-                insert_trustee_vote_state::<T>(true, &who, &mut proposal.trustee_list)?;
-                // check required count
-                // required count should be equal or more than (2/3)*total
-                // e.g. total=6 => required=2*6/3=4, thus equal to 4 should mark as finish
-                if sigs_count == sig_num {
-                    // mark as finish, can't do anything for this proposal
-                    info!("[apply_sig_withdraw] Signature completed:{}", sigs_count);
-                    proposal.sig_state = VoteResult::Finish;
-
-                    Self::deposit_event(Event::<T>::WithdrawalProposalCompleted(tx.hash()))
-                } else {
-                    proposal.sig_state = VoteResult::Unfinish;
-                }
-                // update tx
-                proposal.tx = tx;
-            }
-            None => {
-                // reject
-                insert_trustee_vote_state::<T>(false, &who, &mut proposal.trustee_list)?;
-
-                let reject_count = proposal
-                    .trustee_list
-                    .iter()
-                    .filter(|(_, vote)| !(*vote))
-                    .count() as u32;
-
-                // reject count just need  < (total-required) / total
-                // e.g. total=6 => required=2*6/3=4, thus, reject should more than (6-4) = 2
-                // > 2 equal to total - required + 1 = 6-4+1 = 3
-                let need_reject = total - sig_num + 1;
-                if reject_count == need_reject {
-                    info!(
-                        "[apply_sig_withdraw] {}/{} opposition, clear withdrawal proposal",
-                        reject_count, total
-                    );
-
-                    // release withdrawal for applications
-                    for id in proposal.withdrawal_id_list.iter() {
-                        let _ = xpallet_gateway_records::Module::<T>::recover_withdrawal(
-                            *id,
-                            Chain::Bitcoin,
-                        );
-                    }
-
-                    WithdrawalProposal::<T>::kill();
-
-                    Self::deposit_event(Event::<T>::WithdrawalProposalDropped(
-                        reject_count as u32,
-                        sig_num as u32,
-                        proposal.withdrawal_id_list,
-                    ));
-                    return Ok(());
-                }
+        if let Some(reject_count) = vote_proposal::<T>(&who, txid, tx, &mut proposal)? {
+            info!(
+                "[apply_sig_withdraw] {}/{} opposition, clear withdrawal proposal",
+                reject_count, total
+            );
+
+            // release withdrawal for applications
+            for id in proposal.withdrawal_id_list.iter() {
+                let _ =
+                    xpallet_gateway_records::Module::<T>::recover_withdrawal(*id, Chain::Bitcoin);
             }
+
+            WithdrawalProposals::<T>::remove(txid);
+            WithdrawalReplacements::<T>::remove(txid);
+
+            Self::deposit_event(Event::<T>::WithdrawalProposalDropped(
+                reject_count,
+                sig_num,
+                proposal.withdrawal_id_list,
+            ));
+            return Ok(());
+        }
+        if proposal.sig_state == VoteResult::Finish {
+            Self::note_missed_trustees(&proposal.trustee_list);
+            Self::deposit_event(Event::<T>::WithdrawalProposalCompleted(proposal.tx.hash()))
         }
 
         info!(
@@ -405,22 +373,120 @@ impl<T: Trait> Module<T> {
             proposal.sig_state, proposal.trustee_list
         );
 
-        WithdrawalProposal::<T>::put(proposal);
+        WithdrawalProposals::<T>::insert(txid, proposal);
         Ok(())
     }
 
-    pub fn force_replace_withdraw_tx(tx: Transaction) -> DispatchResult {
+    /// Propose a replacement tx (same inputs, same withdrawal outputs, but higher fee) for the
+    /// finished withdrawal proposal `txid`, which is stuck due to the low fee.
+    pub fn apply_propose_replacement(
+        who: T::AccountId,
+        txid: H256,
+        tx: Transaction,
+    ) -> DispatchResult {
+        let proposal: BtcWithdrawalProposal<T::AccountId> =
+            Self::withdrawal_proposals(txid).ok_or(Error::<T>::NoProposal)?;
+        ensure!(
+            proposal.sig_state == VoteResult::Finish,
+            Error::<T>::NotFinishProposal
+        );
+        ensure!(
+            Self::withdrawal_replacements(txid).is_none(),
+            Error::<T>::ExistingReplacement
+        );
+
+        check_replacement_tx::<T>(&tx, &proposal)?;
+
+        let sigs_count = parse_and_check_signed_tx::<T>(&tx)?;
+        ensure!(sigs_count <= 1, Error::<T>::InvalidSignCount);
+
+        let tx_hash = tx.hash();
+        let mut replacement = BtcWithdrawalProposal::new(
+            VoteResult::Unfinish,
+            proposal.withdrawal_id_list,
+            tx,
+            Vec::new(),
+            proposal.withdrawal_fee,
+        );
+        info!(
+            "[apply_propose_replacement] Propose replacement tx:{:?} for withdrawal tx:{:?}",
+            tx_hash,
+            proposal.tx.hash()
+        );
+        Self::deposit_event(Event::<T>::WithdrawalReplacementProposed(
+            who.clone(),
+            tx_hash,
+        ));
+
+        if sigs_count == 1 {
+            insert_trustee_vote_state::<T>(true, &who, txid, &mut replacement.trustee_list)?;
+        }
+        WithdrawalReplacements::<T>::insert(txid, replacement);
+        Ok(())
+    }
+
+    /// Trustees sign the replacement tx, the vote rule is same as the withdrawal proposal.
+    /// If the replacement is finished, it would replace the tx of the withdrawal proposal, and the
+    /// proposal would be re-keyed by the unsigned txid of the replacement.
+    pub fn apply_sig_replacement(
+        who: T::AccountId,
+        txid: H256,
+        tx: Option<Transaction>,
+    ) -> DispatchResult {
+        let mut replacement: BtcWithdrawalProposal<T::AccountId> =
+            Self::withdrawal_replacements(txid).ok_or(Error::<T>::NoReplacement)?;
+
+        let (sig_num, total) = get_sig_num::<T>();
+        if let Some(reject_count) = vote_proposal::<T>(&who, txid, tx, &mut replacement)? {
+            info!(
+                "[apply_sig_replacement] {}/{} opposition, clear replacement",
+                reject_count, total
+            );
+            WithdrawalReplacements::<T>::remove(txid);
+            Self::deposit_event(Event::<T>::WithdrawalReplacementDropped(
+                reject_count,
+                sig_num,
+                replacement.tx.hash(),
+            ));
+            return Ok(());
+        }
+
+        if replacement.sig_state == VoteResult::Finish {
+            let mut proposal: BtcWithdrawalProposal<T::AccountId> =
+                Self::withdrawal_proposals(txid).ok_or(Error::<T>::NoProposal)?;
+            let replaced_hash = proposal.tx.hash();
+            let tx_hash = replacement.tx.hash();
+            info!(
+                "[apply_sig_replacement] Replace withdrawal tx:{:?} with tx:{:?}",
+                replaced_hash, tx_hash
+            );
+            proposal.tx = replacement.tx;
+            WithdrawalProposals::<T>::remove(txid);
+            WithdrawalProposals::<T>::insert(unsigned_txid(&proposal.tx), proposal);
+            WithdrawalReplacements::<T>::remove(txid);
+            Self::deposit_event(Event::<T>::WithdrawalReplacementCompleted(
+                replaced_hash,
+                tx_hash,
+            ));
+        } else {
+            WithdrawalReplacements::<T>::insert(txid, replacement);
+        }
+        Ok(())
+    }
+
+    pub fn force_replace_withdraw_tx(txid: H256, tx: Transaction) -> DispatchResult {
         let mut proposal: BtcWithdrawalProposal<T::AccountId> =
-            Self::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+            Self::withdrawal_proposals(txid).ok_or(Error::<T>::NoProposal)?;
 
         ensure!(
             proposal.sig_state == VoteResult::Finish,
             "Only allow force change finished vote"
         );
 
-        // make sure withdrawal list is same as current proposal
-        let current_withdrawal_list = &proposal.withdrawal_id_list;
-        check_withdraw_tx_impl::<T>(&tx, current_withdrawal_list)?;
+        // make sure withdrawal list and fee are same as current proposal
+        check_withdraw_tx_impl::<T>(&tx, &proposal.withdrawal_id_list, proposal.withdrawal_fee)?;
+        // the new inputs must not be spent by other proposals
+        check_conflicting_inputs::<T>(&tx, Some(txid))?;
 
         // sign
         // check first and get signatures from commit transaction
@@ -433,9 +499,134 @@ impl<T: Trait> Module<T> {
         // replace old transaction
         proposal.tx = tx;
 
-        WithdrawalProposal::<T>::put(proposal);
+        WithdrawalProposals::<T>::remove(txid);
+        WithdrawalReplacements::<T>::remove(txid);
+        WithdrawalProposals::<T>::insert(unsigned_txid(&proposal.tx), proposal);
         Ok(())
     }
+
+    /// Credit the withdrawal fee income `fee_income` as X-BTC, the trustees who signed the
+    /// withdrawal tx are rewarded first, and the rest goes to the withdrawal fee destination.
+    ///
+    /// `fee_income` must exclude the miner fee, which has left the trustee utxos.
+    pub(crate) fn credit_withdrawal_fee(
+        trustee_list: &[(T::AccountId, bool)],
+        fee_income: BalanceOf<T>,
+    ) {
+        let reward = Self::reward_trustees(trustee_list, fee_income);
+        let fee = fee_income.saturating_sub(reward);
+        if fee.is_zero() {
+            return;
+        }
+        let destination = Self::withdrawal_fee_destination()
+            .unwrap_or_else(<T as xpallet_assets::Trait>::TreasuryAccount::treasury_account);
+        match xpallet_assets::Module::<T>::issue(&Self::ASSET_ID, &destination, fee) {
+            Ok(_) => Self::deposit_event(Event::<T>::WithdrawalFeeCredited(destination, fee)),
+            Err(err) => error!(
+                "[credit_withdrawal_fee] Issue withdrawal fee:{:?} to {:?} error:{:?}",
+                fee, destination, err
+            ),
+        }
+    }
+
+    /// Issue the share of the withdrawal fee income `fee_income` to the trustee reward pot, then
+    /// split the pot equally among the trustees who signed the withdrawal tx, returns the issued
+    /// reward.
+    ///
+    /// The indivisible remainder is kept in the pot for the next withdrawal.
+    pub(crate) fn reward_trustees(
+        trustee_list: &[(T::AccountId, bool)],
+        fee_income: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let signers = trustee_list
+            .iter()
+            .filter(|(_, vote)| *vote)
+            .map(|(trustee, _)| trustee)
+            .collect::<Vec<_>>();
+        if signers.is_empty() {
+            return Zero::zero();
+        }
+
+        let pot = Self::trustee_reward_pot();
+        let reward = Self::trustee_reward_ratio() * fee_income;
+        if !reward.is_zero() {
+            if let Err(err) = xpallet_assets::Module::<T>::issue(&Self::ASSET_ID, &pot, reward) {
+                error!(
+                    "[reward_trustees] Issue trustee reward:{:?} error:{:?}",
+                    reward, err
+                );
+                return Zero::zero();
+            }
+        }
+
+        let pot_balance = xpallet_assets::Module::<T>::usable_balance(&pot, &Self::ASSET_ID);
+        let share = pot_balance / (signers.len() as u32).saturated_into();
+        if share.is_zero() {
+            return reward;
+        }
+        for signer in signers {
+            match xpallet_assets::Module::<T>::move_usable_balance(
+                &Self::ASSET_ID,
+                &pot,
+                signer,
+                share,
+            ) {
+                Ok(_) => Self::deposit_event(Event::<T>::TrusteeRewarded(signer.clone(), share)),
+                Err(err) => error!(
+                    "[reward_trustees] Reward trustee:{:?} error:{:?}",
+                    signer, err
+                ),
+            }
+        }
+        reward
+    }
+
+    /// Note the trustees of current session who did not vote for the finished withdrawal
+    /// proposal, the ones missing `MaxMissedProposals` consecutive proposals would be reported
+    /// to staking as offenders.
+    ///
+    /// Any vote of a trustee resets its count, including the vote for a finished proposal, since
+    /// the trustees who come after the threshold is reached can't sign the tx anymore.
+    pub(crate) fn note_missed_trustees(trustee_list: &[(T::AccountId, bool)]) {
+        let max_missed = Self::max_missed_proposals();
+        if max_missed == 0 {
+            return;
+        }
+        let trustees = match current_trustee_session::<T>() {
+            Ok(session_info) => session_info.trustee_list,
+            Err(err) => {
+                error!(
+                    "[note_missed_trustees] Can not get trustee session:{:?}",
+                    err
+                );
+                return;
+            }
+        };
+
+        let mut offenders = Vec::new();
+        for trustee in trustees {
+            if trustee_list.iter().any(|(voter, _)| *voter == trustee) {
+                MissedProposalsOf::<T>::remove(&trustee);
+                continue;
+            }
+            let missed = Self::missed_proposals_of(&trustee) + 1;
+            if missed >= max_missed {
+                MissedProposalsOf::<T>::remove(&trustee);
+                offenders.push(trustee);
+            } else {
+                MissedProposalsOf::<T>::insert(&trustee, missed);
+            }
+        }
+
+        if !offenders.is_empty() {
+            warn!(
+                "[note_missed_trustees] Report trustees:{:?} for missing {} proposals",
+                offenders, max_missed
+            );
+            T::OffenderReporter::report_offenders(&offenders);
+            Self::deposit_event(Event::<T>::TrusteesReported(offenders));
+        }
+    }
 }
 
 /// Get the required number of signatures
@@ -493,12 +684,97 @@ pub(crate) fn create_multi_address<T: Trait>(
     })
 }
 
+/// Apply the vote of a trustee on the proposal, a `Some(tx)` means the trustee signs the tx
+/// and `None` means the trustee rejects the proposal.
+///
+/// Returns the reject count if the proposal is rejected by enough trustees.
+fn vote_proposal<T: Trait>(
+    who: &T::AccountId,
+    txid: H256,
+    tx: Option<Transaction>,
+    proposal: &mut BtcWithdrawalProposal<T::AccountId>,
+) -> Result<Option<u32>, DispatchError> {
+    if proposal.sig_state == VoteResult::Finish {
+        error!(
+            "[vote_proposal] Proposal is on FINISH state, can't sign for this proposal:{:?}",
+            proposal
+        );
+        return Err(Error::<T>::RejectSig.into());
+    }
+
+    let (sig_num, total) = get_sig_num::<T>();
+    match tx {
+        Some(tx) => {
+            // check this tx is same to proposal, just check input and output, not include sigs
+            ensure_identical::<T>(&tx, &proposal.tx)?;
+
+            // sign
+            // check first and get signatures from commit transaction
+            let sigs_count = parse_and_check_signed_tx::<T>(&tx)?;
+            if sigs_count == 0 {
+                error!("[vote_proposal] Tx sig should not be zero, zero is the source tx without any sig, tx{:?}", tx);
+                return Err(Error::<T>::InvalidSignCount.into());
+            }
+
+            let confirmed_count = proposal
+                .trustee_list
+                .iter()
+                .filter(|(_, vote)| *vote)
+                .count() as u32;
+
+            if sigs_count != confirmed_count + 1 {
+                error!(
+                    "[vote_proposal] Need to sign on the latest signature results, sigs count:{}, confirmed count:{}",
+                    sigs_count, confirmed_count
+                );
+                return Err(Error::<T>::InvalidSignCount.into());
+            }
+
+            insert_trustee_vote_state::<T>(true, who, txid, &mut proposal.trustee_list)?;
+            // check required count
+            // required count should be equal or more than (2/3)*total
+            // e.g. total=6 => required=2*6/3=4, thus equal to 4 should mark as finish
+            if sigs_count == sig_num {
+                // mark as finish, can't do anything for this proposal
+                info!("[vote_proposal] Signature completed:{}", sigs_count);
+                proposal.sig_state = VoteResult::Finish;
+            } else {
+                proposal.sig_state = VoteResult::Unfinish;
+            }
+            // update tx
+            proposal.tx = tx;
+            Ok(None)
+        }
+        None => {
+            // reject
+            insert_trustee_vote_state::<T>(false, who, txid, &mut proposal.trustee_list)?;
+
+            let reject_count = proposal
+                .trustee_list
+                .iter()
+                .filter(|(_, vote)| !(*vote))
+                .count() as u32;
+
+            // reject count just need  < (total-required) / total
+            // e.g. total=6 => required=2*6/3=4, thus, reject should more than (6-4) = 2
+            // > 2 equal to total - required + 1 = 6-4+1 = 3
+            let need_reject = total - sig_num + 1;
+            if reject_count == need_reject {
+                Ok(Some(reject_count))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
 /// Update the signature status of trustee
 /// state: false -> Veto signature, true -> Consent signature
 /// only allow inseRelayedTx once
 fn insert_trustee_vote_state<T: Trait>(
     state: bool,
     who: &T::AccountId,
+    txid: H256,
     trustee_list: &mut Vec<(T::AccountId, bool)>,
 ) -> DispatchResult {
     match trustee_list.iter_mut().find(|ref info| info.0 == *who) {
@@ -509,27 +785,124 @@ fn insert_trustee_vote_state<T: Trait>(
         }
         None => {
             trustee_list.push((who.clone(), state));
+            MissedProposalsOf::<T>::remove(who);
             debug!(
                 "[insert_trustee_vote_state] Insert new vote, who:{:?}, state:{}",
                 who, state
             );
         }
     }
-    Module::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(who.clone(), state));
+    Module::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(
+        who.clone(),
+        txid,
+        state,
+    ));
     Ok(())
 }
 
-/// Check that the cash withdrawal transaction is correct
-fn check_withdraw_tx<T: Trait>(tx: &Transaction, withdrawal_id_list: &[u32]) -> DispatchResult {
-    match Module::<T>::withdrawal_proposal() {
-        Some(_) => Err(Error::<T>::NotFinishProposal.into()),
-        None => check_withdraw_tx_impl::<T>(tx, withdrawal_id_list),
+/// Check that the cash withdrawal transaction is correct, and it does not conflict with the
+/// in-flight proposals.
+fn check_withdraw_tx<T: Trait>(
+    tx: &Transaction,
+    withdrawal_id_list: &[u32],
+    withdrawal_fee: u64,
+) -> DispatchResult {
+    let proposal_count = WithdrawalProposals::<T>::iter().count() as u32;
+    ensure!(
+        proposal_count < Module::<T>::max_proposal_count(),
+        Error::<T>::TooManyProposals
+    );
+    ensure!(
+        !WithdrawalProposals::<T>::contains_key(unsigned_txid(tx)),
+        Error::<T>::DuplicatedProposal
+    );
+    for (_, proposal) in WithdrawalProposals::<T>::iter() {
+        if let Some(id) = withdrawal_id_list
+            .iter()
+            .find(|id| proposal.withdrawal_id_list.contains(id))
+        {
+            error!(
+                "[check_withdraw_tx] Withdrawal ({}) is already in the proposal:{:?}",
+                id, proposal
+            );
+            return Err(Error::<T>::ConflictingProposal.into());
+        }
     }
+    check_conflicting_inputs::<T>(tx, None)?;
+    check_withdraw_tx_impl::<T>(tx, withdrawal_id_list, withdrawal_fee)
+}
+
+/// Check that the inputs of `tx` are not spent by the in-flight proposals (excluding `exclude`).
+fn check_conflicting_inputs<T: Trait>(tx: &Transaction, exclude: Option<H256>) -> DispatchResult {
+    for (txid, proposal) in WithdrawalProposals::<T>::iter() {
+        if Some(txid) == exclude {
+            continue;
+        }
+        let conflicting = tx.inputs.iter().any(|input| {
+            proposal
+                .tx
+                .inputs
+                .iter()
+                .any(|spent| spent.previous_output == input.previous_output)
+        });
+        if conflicting {
+            error!(
+                "[check_conflicting_inputs] Tx:{:?} spends the same utxo as the proposal:{:?}",
+                tx, proposal
+            );
+            return Err(Error::<T>::ConflictingProposal.into());
+        }
+    }
+    Ok(())
+}
+
+/// Check the replacement tx of the withdrawal proposal.
+///
+/// The replacement must spend the same inputs as the proposal tx (which must signal the
+/// replaceability, see BIP125), keep the withdrawal outputs and pay a higher fee, i.e. the
+/// total output value is lower.
+fn check_replacement_tx<T: Trait>(
+    tx: &Transaction,
+    proposal: &BtcWithdrawalProposal<T::AccountId>,
+) -> DispatchResult {
+    let origin = &proposal.tx;
+    ensure!(
+        origin
+            .inputs
+            .iter()
+            .any(|input| input.sequence <= MAX_BIP125_RBF_SEQUENCE),
+        Error::<T>::IrreplaceableTx
+    );
+    let same_inputs = tx.inputs.len() == origin.inputs.len()
+        && tx
+            .inputs
+            .iter()
+            .zip(origin.inputs.iter())
+            .all(|(a, b)| a.previous_output == b.previous_output);
+    if !same_inputs {
+        error!(
+            "[check_replacement_tx] Replacement should spend the same inputs, tx:{:?}, origin:{:?}",
+            tx, origin
+        );
+        return Err(Error::<T>::MismatchedTx.into());
+    }
+
+    check_withdraw_tx_impl::<T>(tx, &proposal.withdrawal_id_list, proposal.withdrawal_fee)?;
+
+    let output_value = |tx: &Transaction| -> u64 { tx.outputs.iter().map(|out| out.value).sum() };
+    ensure!(
+        output_value(tx) < output_value(origin),
+        Error::<T>::LowReplacementFee
+    );
+    Ok(())
 }
 
-fn check_withdraw_tx_impl<T: Trait>(
+/// Check that the withdrawal outputs of `tx` are the withdrawals `withdrawal_id_list`, each of
+/// which is charged `withdrawal_fee`.
+pub(crate) fn check_withdraw_tx_impl<T: Trait>(
     tx: &Transaction,
     withdrawal_id_list: &[u32],
+    withdrawal_fee: u64,
 ) -> DispatchResult {
     // withdrawal addr list for account withdrawal application
     let mut appl_withdrawal_list: Vec<(Address, u64)> = Vec::new();
@@ -545,14 +918,13 @@ fn check_withdraw_tx_impl<T: Trait>(
     // not allow deposit directly to cold address, only hot address allow
     let hot_trustee_address: Address = get_hot_trustee_address::<T>()?;
     // withdrawal addr list for tx outputs
-    let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
     let btc_network = Module::<T>::network_id();
     let mut tx_withdraw_list = Vec::new();
     for output in &tx.outputs {
         let addr = extract_output_addr(&output, btc_network).ok_or("not found addr in this out")?;
         if addr.hash != hot_trustee_address.hash {
             // expect change to trustee_addr output
-            tx_withdraw_list.push((addr, output.value + btc_withdrawal_fee));
+            tx_withdraw_list.push((addr, output.value.saturating_add(withdrawal_fee)));
         }
     }
 
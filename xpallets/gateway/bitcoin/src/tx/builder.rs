@@ -0,0 +1,186 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::dispatch::DispatchError;
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use light_bitcoin::{
+    chain::{OutPoint, Transaction, TransactionInput, TransactionOutput},
+    keys::{Address, Type},
+    primitives::Bytes,
+    script::Builder,
+};
+
+use xp_logging::{debug, error};
+use xpallet_gateway_common::{traits::TrusteeSession, utils::two_thirds_unsafe};
+
+use crate::{
+    trustee::{check_withdraw_tx_impl, get_hot_trustee_address, get_sig_num},
+    types::BtcUtxo,
+    Error, Module, Trait,
+};
+
+/// The outputs lower than the dust threshold would be rejected by bitcoin nodes.
+pub const DUST_THRESHOLD: u64 = 546;
+
+/// The max input sequence which signals the replaceability of the tx (BIP125).
+pub const MAX_BIP125_RBF_SEQUENCE: u32 = 0xffff_fffd;
+
+/// version(4) + input count(1) + output count(1) + lock time(4)
+const TX_OVERHEAD_SIZE: u64 = 10;
+/// prev txid(32) + prev index(4) + script length(3) + sequence(4)
+const INPUT_BASE_SIZE: u64 = 43;
+/// value(8) + script length(1)
+const OUTPUT_BASE_SIZE: u64 = 9;
+/// The max size of a DER signature with sighash type
+const SIGNATURE_SIZE: u64 = 73;
+/// The size of a compressed public key with its push opcode.
+const PUBKEY_SIZE: u64 = 34;
+/// OP_DUP + OP_HASH160 + push(20) + pubkey hash(20) + OP_EQUALVERIFY + OP_CHECKSIG
+const P2PKH_SCRIPT_SIZE: u64 = 25;
+
+/// Build the unsigned withdrawal transaction for the given withdrawal records.
+///
+/// The inputs are selected from `utxos` (the unspent outputs of the hot trustee address)
+/// by the largest-first strategy, until the outputs and the miner fee (`fee_rate` sat/vbyte)
+/// are covered. The inputs signal the replaceability (BIP125), so that the tx could be
+/// replaced by one with a higher fee if it's stuck. The rest would be sent back to the hot
+/// trustee address as the change, unless it's lower than the dust threshold. The built tx must
+/// pass `check_withdraw_tx_impl`.
+pub fn build_withdrawal_tx<T: Trait>(
+    withdrawal_id_list: &[u32],
+    fee_rate: u64,
+    mut utxos: Vec<BtcUtxo>,
+) -> Result<Transaction, DispatchError> {
+    let max_withdrawal_count = Module::<T>::max_withdrawal_count();
+    if withdrawal_id_list.is_empty() || withdrawal_id_list.len() > max_withdrawal_count as usize {
+        error!(
+            "[build_withdrawal_tx] Invalid withdrawal list length:{}, max withdrawal count:{}",
+            withdrawal_id_list.len(),
+            max_withdrawal_count
+        );
+        return Err(Error::<T>::WroungWithdrawalCount.into());
+    }
+    if fee_rate == 0 {
+        return Err(Error::<T>::InvalidFeeRate.into());
+    }
+
+    let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
+    let mut outputs = Vec::with_capacity(withdrawal_id_list.len() + 1);
+    for id in withdrawal_id_list {
+        let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+            .ok_or(Error::<T>::NoWithdrawalRecord)?;
+        let addr = Module::<T>::verify_btc_address(&record.addr())?;
+        let value = record
+            .balance()
+            .saturated_into::<u64>()
+            .checked_sub(btc_withdrawal_fee)
+            .ok_or(Error::<T>::InvalidProposal)?;
+        outputs.push(TransactionOutput {
+            value,
+            script_pubkey: script_pubkey_of(&addr),
+        });
+    }
+    let withdrawal_value: u64 = outputs.iter().map(|output| output.value).sum();
+
+    let hot_trustee_address = get_hot_trustee_address::<T>()?;
+    let change_script = script_pubkey_of(&hot_trustee_address);
+    let (sig_num, trustee_num) = get_sig_num::<T>();
+    let input_size = multisig_input_size(sig_num, trustee_num);
+    let outputs_size: u64 = outputs
+        .iter()
+        .map(|output| OUTPUT_BASE_SIZE + output.script_pubkey.len() as u64)
+        .sum();
+    let change_size = OUTPUT_BASE_SIZE + change_script.len() as u64;
+
+    // largest-first, minimize the count of inputs
+    utxos.sort_by(|a, b| b.value.cmp(&a.value));
+    let mut inputs = Vec::new();
+    let mut input_value: u64 = 0;
+    let mut change = None;
+    for utxo in utxos {
+        input_value = input_value.saturating_add(utxo.value);
+        inputs.push(TransactionInput {
+            previous_output: OutPoint {
+                txid: utxo.txid,
+                index: utxo.vout,
+            },
+            script_sig: Bytes::new(),
+            sequence: MAX_BIP125_RBF_SEQUENCE,
+            script_witness: Vec::new(),
+        });
+
+        let size = TX_OVERHEAD_SIZE + outputs_size + input_size * inputs.len() as u64;
+        let fee = fee_rate.saturating_mul(size);
+        let fee_with_change = fee_rate.saturating_mul(size + change_size);
+        if input_value >= withdrawal_value.saturating_add(fee_with_change) {
+            let rest = input_value - withdrawal_value - fee_with_change;
+            if rest >= DUST_THRESHOLD {
+                change = Some(rest);
+            }
+            break;
+        }
+        if input_value >= withdrawal_value.saturating_add(fee) {
+            // the rest is not enough for a change output, just leave it as the miner fee
+            break;
+        }
+    }
+
+    let size = TX_OVERHEAD_SIZE + outputs_size + input_size * inputs.len() as u64;
+    if input_value < withdrawal_value.saturating_add(fee_rate.saturating_mul(size)) {
+        error!(
+            "[build_withdrawal_tx] Insufficient utxos, input value:{}, withdrawal value:{}, fee rate:{}",
+            input_value, withdrawal_value, fee_rate
+        );
+        return Err(Error::<T>::InsufficientUtxo.into());
+    }
+    if let Some(value) = change {
+        outputs.push(TransactionOutput {
+            value,
+            script_pubkey: change_script,
+        });
+    }
+
+    let tx = Transaction {
+        version: 1,
+        inputs,
+        outputs,
+        lock_time: 0,
+    };
+    debug!(
+        "[build_withdrawal_tx] Build withdrawal tx:{:?}, withdrawal list:{:?}",
+        tx, withdrawal_id_list
+    );
+    check_withdraw_tx_impl::<T>(&tx, withdrawal_id_list, btc_withdrawal_fee)?;
+    Ok(tx)
+}
+
+fn script_pubkey_of(addr: &Address) -> Bytes {
+    let script = match addr.kind {
+        Type::P2SH => Builder::build_p2sh(&addr.hash),
+        _ => Builder::build_p2pkh(&addr.hash),
+    };
+    script.into()
+}
+
+/// The estimated size of a withdrawal in the withdrawal tx, i.e. a P2PKH output and an input
+/// of the hot trustee address, since an input is usually enough to cover an output.
+///
+/// Returns None if there is no trustee session.
+pub fn estimated_withdrawal_size<T: Trait>() -> Option<u64> {
+    let trustee_num = T::TrusteeSessionProvider::current_trustee_session()
+        .ok()?
+        .trustee_list
+        .len() as u32;
+    let sig_num = two_thirds_unsafe(trustee_num);
+    Some(OUTPUT_BASE_SIZE + P2PKH_SCRIPT_SIZE + multisig_input_size(sig_num, trustee_num))
+}
+
+/// The size of an input spending the `sig_num`-of-`trustee_num` P2SH multisig output.
+fn multisig_input_size(sig_num: u32, trustee_num: u32) -> u64 {
+    // OP_M + pubkeys + OP_N + OP_CHECKMULTISIG
+    let redeem_script_size = 3 + PUBKEY_SIZE * u64::from(trustee_num);
+    // OP_0 + signatures + OP_PUSHDATA(2) + redeem script
+    let script_sig_size = 1 + (1 + SIGNATURE_SIZE) * u64::from(sig_num) + 2 + redeem_script_size;
+    INPUT_BASE_SIZE + script_sig_size
+}
@@ -0,0 +1,101 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Estimate the withdrawal fee by the fee rates of the relayed withdrawal txs.
+//!
+//! The fee rate (sat/vbyte) of every relayed withdrawal tx is sampled, and the withdrawal fee
+//! is derived from the median of the recent samples and the estimated size of a withdrawal,
+//! within the bounds set by root. The withdrawal fee is set manually if there are no bounds.
+
+use frame_support::{StorageMap, StorageValue};
+use sp_std::prelude::*;
+
+use light_bitcoin::{chain::Transaction, serialization};
+
+use xp_logging::{debug, info};
+
+use crate::{
+    tx::builder::estimated_withdrawal_size, BtcWithdrawalFee, Event, Module, Trait, TrusteeUtxos,
+    WithdrawalFeeRates,
+};
+
+/// The max count of the fee rate samples of the recent withdrawal txs.
+pub const MAX_FEE_RATE_SAMPLES: usize = 16;
+
+/// Returns the total value of the trustee utxos spent by the `tx`, None if any input is not a
+/// tracked trustee utxo.
+///
+/// NOTE: it must be called before the spent trustee utxos are removed.
+pub fn trustee_input_value(tx: &Transaction) -> Option<u64> {
+    tx.inputs.iter().try_fold(0u64, |value, input| {
+        let outpoint = (input.previous_output.txid, input.previous_output.index);
+        TrusteeUtxos::get(&outpoint).map(|utxo| value.saturating_add(utxo.value))
+    })
+}
+
+/// Returns the miner fee of the `tx` which spends `input_value`.
+pub fn tx_fee(tx: &Transaction, input_value: u64) -> Option<u64> {
+    let output_value = tx
+        .outputs
+        .iter()
+        .fold(0u64, |value, output| value.saturating_add(output.value));
+    input_value.checked_sub(output_value)
+}
+
+/// Returns the fee rate (sat/vbyte) of the `tx` which spends `input_value`.
+///
+/// The trustee multisig inputs are not segwit, thus the size of the tx is its vsize.
+pub fn tx_fee_rate(tx: &Transaction, input_value: u64) -> Option<u64> {
+    let fee = tx_fee(tx, input_value)?;
+    let size = serialization::serialize(tx).len() as u64;
+    if size == 0 {
+        return None;
+    }
+    Some(fee / size)
+}
+
+/// Returns the median of the `rates`, the lower one of the middle two for even count.
+pub fn median(rates: &[u64]) -> Option<u64> {
+    if rates.is_empty() {
+        return None;
+    }
+    let mut rates = rates.to_vec();
+    rates.sort_unstable();
+    Some(rates[(rates.len() - 1) / 2])
+}
+
+/// Sample the fee rate of a relayed withdrawal tx, then update the withdrawal fee.
+pub fn note_withdrawal_fee_rate<T: Trait>(fee_rate: u64) {
+    debug!(
+        "[note_withdrawal_fee_rate] Withdrawal tx fee rate:{}",
+        fee_rate
+    );
+    WithdrawalFeeRates::mutate(|rates| {
+        rates.push(fee_rate);
+        if rates.len() > MAX_FEE_RATE_SAMPLES {
+            rates.remove(0);
+        }
+    });
+    update_withdrawal_fee::<T>();
+}
+
+/// Derive the withdrawal fee from the median fee rate of the samples, or just keep the current
+/// fee if there are no samples, within the bounds. Nothing happens if there are no bounds.
+pub fn update_withdrawal_fee<T: Trait>() {
+    let (min, max) = match Module::<T>::btc_withdrawal_fee_bounds() {
+        Some(bounds) => bounds,
+        None => return,
+    };
+    let current = Module::<T>::btc_withdrawal_fee();
+    let estimated = median(&Module::<T>::withdrawal_fee_rates())
+        .and_then(|rate| estimated_withdrawal_size::<T>().map(|size| rate.saturating_mul(size)))
+        .unwrap_or(current);
+    let fee = estimated.max(min).min(max);
+    if fee != current {
+        info!(
+            "[update_withdrawal_fee] Withdrawal fee:{} => {}, bounds:({}, {})",
+            current, fee, min, max
+        );
+        BtcWithdrawalFee::put(fee);
+        Module::<T>::deposit_event(Event::<T>::WithdrawalFeeUpdated(fee));
+    }
+}
@@ -1,11 +1,20 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+pub mod builder;
+pub mod fee;
 mod secp256k1_verifier;
 pub mod validator;
 
-use frame_support::{debug::native, dispatch::DispatchResult, StorageMap, StorageValue};
-use sp_runtime::{traits::Zero, SaturatedConversion};
-use sp_std::prelude::*;
+use codec::Encode;
+use frame_support::{
+    debug::native, dispatch::DispatchResult, IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_core::crypto::AccountId32;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+use sp_std::{cmp, convert::TryFrom, prelude::*};
 
 use light_bitcoin::{
     chain::Transaction,
@@ -13,29 +22,46 @@ use light_bitcoin::{
     primitives::{hash_rev, H256},
 };
 
-use chainx_primitives::AssetId;
-use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
-use xp_gateway_common::AccountExtractor;
+use chainx_primitives::{AssetId, ReferralId};
+use xp_gateway_bitcoin::{extract_output_addr, BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
+use xp_gateway_common::{to_ss58_check_with_version, AccountExtractor};
 use xp_logging::{debug, error, info, warn};
+use xp_protocol::MAINNET_ADDRESS_FORMAT_ID;
 use xpallet_assets::ChainT;
-use xpallet_gateway_common::traits::{AddressBinding, ReferralBinding};
+use xpallet_gateway_common::traits::{AddressBinding, ReferralBinding, TrusteeSession};
 use xpallet_support::try_str;
 
 pub use self::validator::validate_transaction;
 use crate::{
     native,
-    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState},
-    BalanceOf, Error, Event, Module, PendingDeposits, Trait, WithdrawalProposal,
+    trustee::check_withdraw_tx_impl,
+    types::{
+        AccountInfo, BtcAddress, BtcCreditedDeposit, BtcDepositCache, BtcTrusteeUtxo, BtcTxResult,
+        BtcTxState,
+    },
+    BalanceOf, BlockChanges, CreditedDeposits, Error, Event, Headers, Module, PendingDepositBlock,
+    PendingDeposits, ProcessedTxs, Trait, TrusteeUtxoBalance, TrusteeUtxos, TxState,
+    WithdrawalProposals, WithdrawalReplacements,
 };
 
 pub fn process_tx<T: Trait>(
     tx: Transaction,
     prev_tx: Option<Transaction>,
+    block_hash: H256,
     network: Network,
     min_deposit: u64,
     current_trustee_pair: (Address, Address),
     last_trustee_pair: Option<(Address, Address)>,
 ) -> BtcTxState {
+    let mut trustee_addrs = vec![
+        current_trustee_pair.0.clone(),
+        current_trustee_pair.1.clone(),
+    ];
+    if let Some((hot, cold)) = &last_trustee_pair {
+        trustee_addrs.push(hot.clone());
+        trustee_addrs.push(cold.clone());
+    }
+
     let btc_tx_detector = BtcTxTypeDetector::new(network, min_deposit);
     let meta_type = btc_tx_detector.detect_transaction_type::<T::AccountId, _>(
         &tx,
@@ -46,10 +72,43 @@ pub fn process_tx<T: Trait>(
     );
 
     let tx_type = meta_type.ref_into();
+    // the spent trustee utxos are removed below, thus take the input value of the withdrawal
+    // tx for its fee rate in advance
+    let input_value = match meta_type {
+        BtcTxMetaType::<_>::Withdrawal => fee::trustee_input_value(&tx),
+        _ => None,
+    };
+    // the tx is already on the bitcoin chain, thus update the trustee utxos even if the
+    // processing is failed (e.g. a deposit without valid account info).
+    //
+    // the settlement of a withdrawal is not reverted when its block leaves the main chain (the
+    // signed tx would be packed again in the new main chain), thus the utxo changes of the
+    // withdrawal are not recorded for undoing either.
+    match meta_type {
+        BtcTxMetaType::Irrelevance => {}
+        BtcTxMetaType::<_>::Withdrawal => {
+            update_trustee_utxos::<T>(&tx, &trustee_addrs, network, None)
+        }
+        _ => update_trustee_utxos::<T>(&tx, &trustee_addrs, network, Some(block_hash)),
+    }
     let result = match meta_type {
-        BtcTxMetaType::<_>::Deposit(deposit_info) => deposit::<T>(tx.hash(), deposit_info),
-        BtcTxMetaType::<_>::Withdrawal => withdraw::<T>(tx),
-        BtcTxMetaType::HotAndCold | BtcTxMetaType::TrusteeTransition => BtcTxResult::Success,
+        BtcTxMetaType::<_>::Deposit(deposit_info) => {
+            deposit::<T>(tx.hash(), block_hash, deposit_info)
+        }
+        BtcTxMetaType::<_>::Withdrawal => withdraw::<T>(tx, block_hash, input_value),
+        BtcTxMetaType::HotAndCold => BtcTxResult::Success,
+        BtcTxMetaType::TrusteeTransition => {
+            let receivers = tx
+                .outputs
+                .iter()
+                .filter_map(|output| extract_output_addr(output, network))
+                .map(|addr| addr2vecu8(&addr))
+                .collect::<Vec<_>>();
+            T::TrusteeSessionProvider::trustee_transition_relayed(|trustee| {
+                receivers.contains(&trustee.addr)
+            });
+            BtcTxResult::Success
+        }
         // mark `Irrelevance` be `Failure` so that it could be replayed in the future
         BtcTxMetaType::<_>::Irrelevance => BtcTxResult::Failure,
     };
@@ -57,7 +116,73 @@ pub fn process_tx<T: Trait>(
     BtcTxState { tx_type, result }
 }
 
-fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) -> BtcTxResult {
+/// Update the trustee utxo set by the relayed tx, remove the spent outputs and insert the new
+/// outputs to the trustee addresses.
+///
+/// It's idempotent, so a failed tx could be replayed safely. The changes are recorded in the
+/// `BlockChanges` of `block_hash` (if any), for undoing them when the block leaves the main chain.
+fn update_trustee_utxos<T: Trait>(
+    tx: &Transaction,
+    trustee_addrs: &[Address],
+    network: Network,
+    block_hash: Option<H256>,
+) {
+    let mut spent_utxos = Vec::new();
+    for input in tx.inputs.iter() {
+        let outpoint = (input.previous_output.txid, input.previous_output.index);
+        if let Some(utxo) = TrusteeUtxos::take(&outpoint) {
+            debug!("[update_trustee_utxos] Spend trustee utxo:{:?}", utxo);
+            TrusteeUtxoBalance::mutate(|balance| *balance = balance.saturating_sub(utxo.value));
+            spent_utxos.push(utxo);
+        }
+    }
+
+    let txid = tx.hash();
+    let mut created_utxos = Vec::new();
+    for (vout, output) in tx.outputs.iter().enumerate() {
+        let addr = match extract_output_addr(output, network) {
+            Some(addr) => addr,
+            None => continue,
+        };
+        if !trustee_addrs
+            .iter()
+            .any(|trustee| trustee.hash == addr.hash)
+        {
+            continue;
+        }
+        let outpoint = (txid, vout as u32);
+        if TrusteeUtxos::contains_key(&outpoint) {
+            continue;
+        }
+        let utxo = BtcTrusteeUtxo {
+            txid,
+            vout: vout as u32,
+            value: output.value,
+            addr: addr2vecu8(&addr),
+        };
+        debug!("[update_trustee_utxos] New trustee utxo:{:?}", utxo);
+        TrusteeUtxoBalance::mutate(|balance| *balance = balance.saturating_add(utxo.value));
+        TrusteeUtxos::insert(outpoint, utxo);
+        created_utxos.push(outpoint);
+    }
+
+    if let Some(block_hash) = block_hash {
+        if spent_utxos.is_empty() && created_utxos.is_empty() {
+            return;
+        }
+        BlockChanges::mutate(&block_hash, |changes| {
+            changes.txs.push(txid);
+            changes.spent_utxos.extend(spent_utxos);
+            changes.created_utxos.extend(created_utxos);
+        });
+    }
+}
+
+fn deposit<T: Trait>(
+    txid: H256,
+    block_hash: H256,
+    deposit_info: BtcDepositInfo<T::AccountId>,
+) -> BtcTxResult {
     let account_info = match (deposit_info.op_return, deposit_info.input_addr) {
         (Some((account, referral)), Some(input_addr)) => {
             let input_addr = addr2vecu8(&input_addr);
@@ -98,9 +223,9 @@ fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) ->
             T::ReferralBinding::update_binding(
                 &<Module<T> as ChainT<_>>::ASSET_ID,
                 &account,
-                referral,
+                referral.clone(),
             );
-            match deposit_token::<T>(txid, &account, deposit_info.deposit_value) {
+            match deposit_token::<T>(txid, &account, deposit_info.deposit_value, referral) {
                 Ok(_) => {
                     info!(
                         "[deposit] Deposit tx ({:?}) success, who:{:?}, balance:{}",
@@ -108,13 +233,21 @@ fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) ->
                         account,
                         deposit_info.deposit_value
                     );
+                    CreditedDeposits::<T>::append(
+                        &block_hash,
+                        BtcCreditedDeposit {
+                            txid,
+                            who: account,
+                            balance: deposit_info.deposit_value,
+                        },
+                    );
                     BtcTxResult::Success
                 }
                 Err(_) => BtcTxResult::Failure,
             }
         }
         AccountInfo::<_>::Address(input_addr) => {
-            insert_pending_deposit::<T>(&input_addr, txid, deposit_info.deposit_value);
+            insert_pending_deposit::<T>(&input_addr, txid, deposit_info.deposit_value, block_hash);
             info!(
                 "[deposit] Deposit tx ({:?}) into pending, addr:{:?}, balance:{}",
                 hash_rev(txid),
@@ -126,11 +259,23 @@ fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) ->
     }
 }
 
-fn deposit_token<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> DispatchResult {
+fn deposit_token<T: Trait>(
+    txid: H256,
+    who: &T::AccountId,
+    balance: u64,
+    referral: Option<ReferralId>,
+) -> DispatchResult {
     let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
 
     let value: BalanceOf<T> = balance.saturated_into();
-    match <xpallet_gateway_records::Module<T>>::deposit(&who, id, value) {
+    // record the txid in the byte order of the block explorers
+    match <xpallet_gateway_records::Module<T>>::deposit_with_tx(
+        &who,
+        id,
+        value,
+        Some(hash_rev(txid)),
+        referral,
+    ) {
         Ok(()) => {
             Module::<T>::deposit_event(Event::<T>::Deposited(txid, who.clone(), value));
             Ok(())
@@ -145,12 +290,160 @@ fn deposit_token<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> Disp
     }
 }
 
+/// Revert the deposits credited in the block which has left the main chain, and undo the
+/// pending deposits and the trustee utxo changes made by the block.
+///
+/// The tx state would be removed so that the tx could be relayed again if it's packed in the
+/// new main chain. If the depositor does not have enough usable balance, revert as much as
+/// possible and the rest must use root to fix it.
+pub fn revert_credited_deposits<T: Trait>(block_hash: H256) {
+    undo_block_changes::<T>(block_hash);
+
+    let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
+    for deposit in CreditedDeposits::<T>::take(&block_hash) {
+        TxState::remove(&deposit.txid);
+        ProcessedTxs::remove(&deposit.txid);
+        xpallet_gateway_records::Module::<T>::remove_deposit_records(&hash_rev(deposit.txid));
+
+        let value: BalanceOf<T> = deposit.balance.saturated_into();
+        let usable = xpallet_assets::Module::<T>::usable_balance(&deposit.who, &id);
+        let reverted = cmp::min(usable, value);
+        if let Err(err) =
+            xpallet_assets::Module::<T>::force_destroy_usable(&id, &deposit.who, reverted)
+        {
+            error!(
+                "[revert_credited_deposits] Destroy usable error:{:?}, must use root to fix it",
+                err
+            );
+            Module::<T>::deposit_event(Event::<T>::DepositRevertFatalErr(
+                deposit.txid,
+                block_hash,
+                deposit.who,
+                value,
+            ));
+            continue;
+        }
+
+        warn!(
+            "[revert_credited_deposits] Revert deposit tx ({:?}) of block ({:?}), who:{:?}, balance:{}, reverted:{:?}",
+            hash_rev(deposit.txid),
+            hash_rev(block_hash),
+            deposit.who,
+            deposit.balance,
+            reverted
+        );
+        Module::<T>::deposit_event(Event::<T>::DepositReverted(
+            deposit.txid,
+            block_hash,
+            deposit.who.clone(),
+            reverted,
+        ));
+        if reverted < value {
+            Module::<T>::deposit_event(Event::<T>::DepositRevertFatalErr(
+                deposit.txid,
+                block_hash,
+                deposit.who,
+                value - reverted,
+            ));
+        }
+    }
+}
+
+fn undo_block_changes<T: Trait>(block_hash: H256) {
+    let changes = BlockChanges::take(&block_hash);
+    for txid in changes.txs.iter() {
+        TxState::remove(txid);
+        ProcessedTxs::remove(txid);
+    }
+    // restore the spent utxos before removing the created ones, since an utxo may be created
+    // and spent in the same block
+    for utxo in changes.spent_utxos {
+        let outpoint = (utxo.txid, utxo.vout);
+        if TrusteeUtxos::contains_key(&outpoint) {
+            continue;
+        }
+        debug!("[undo_block_changes] Restore spent trustee utxo:{:?}", utxo);
+        TrusteeUtxoBalance::mutate(|balance| *balance = balance.saturating_add(utxo.value));
+        TrusteeUtxos::insert(outpoint, utxo);
+    }
+    for outpoint in changes.created_utxos {
+        if let Some(utxo) = TrusteeUtxos::take(&outpoint) {
+            debug!(
+                "[undo_block_changes] Remove created trustee utxo:{:?}",
+                utxo
+            );
+            TrusteeUtxoBalance::mutate(|balance| *balance = balance.saturating_sub(utxo.value));
+        }
+    }
+
+    // the claimed pending deposits have been moved into `CreditedDeposits` of the block
+    for (addr, txid) in changes.pending_deposits {
+        PendingDepositBlock::remove(&txid);
+        let mut reverted = false;
+        PendingDeposits::mutate_exists(&addr, |list| {
+            if let Some(caches) = list {
+                let len = caches.len();
+                caches.retain(|cache| cache.txid != txid);
+                reverted = caches.len() < len;
+                if caches.is_empty() {
+                    *list = None;
+                }
+            }
+        });
+        if reverted {
+            TxState::remove(&txid);
+            ProcessedTxs::remove(&txid);
+            warn!(
+                "[undo_block_changes] Revert pending deposit tx ({:?}) of block ({:?}), addr:{:?}",
+                hash_rev(txid),
+                hash_rev(block_hash),
+                try_str(&addr)
+            );
+            Module::<T>::deposit_event(Event::<T>::PendingDepositReverted(txid, block_hash, addr));
+        }
+    }
+}
+
+/// Verify that `who` owns the Bitcoin `address`, i.e. `signature` is the "Bitcoin Signed Message"
+/// signature of the SS58 text (in the ChainX address format) of `who` from the key of `address`.
+pub fn verify_address_ownership<T: Trait>(
+    who: &T::AccountId,
+    address: &[u8],
+    signature: &[u8],
+) -> DispatchResult {
+    let addr = Module::<T>::verify_btc_address(address)?;
+    let message = match AccountId32::try_from(who.encode().as_slice()) {
+        Ok(account) => to_ss58_check_with_version(&account, MAINNET_ADDRESS_FORMAT_ID),
+        Err(_) => {
+            error!(
+                "[verify_address_ownership] Can't encode the caller:{:?} as SS58 text",
+                who
+            );
+            return Err(Error::<T>::InvalidAccount.into());
+        }
+    };
+    secp256k1_verifier::verify_message_sig::<T>(&addr, &message, signature)
+}
+
 pub fn remove_pending_deposit<T: Trait>(input_address: &BtcAddress, who: &T::AccountId) {
     // notice this would delete this cache
     let records = PendingDeposits::take(input_address);
     for record in records {
         // ignore error
-        let _ = deposit_token::<T>(record.txid, who, record.balance);
+        let credited = deposit_token::<T>(record.txid, who, record.balance, None).is_ok();
+        // credited deposits would be reverted along with the block containing the deposit tx
+        if let Some(block_hash) = PendingDepositBlock::take(&record.txid) {
+            if credited && Headers::contains_key(&block_hash) {
+                CreditedDeposits::<T>::append(
+                    &block_hash,
+                    BtcCreditedDeposit {
+                        txid: record.txid,
+                        who: who.clone(),
+                        balance: record.balance,
+                    },
+                );
+            }
+        }
         info!(
             "[remove_pending_deposit] Use pending info to re-deposit, who:{:?}, balance:{}, cached_tx:{:?}",
             who, record.balance, record.txid,
@@ -165,7 +458,12 @@ pub fn remove_pending_deposit<T: Trait>(input_address: &BtcAddress, who: &T::Acc
     }
 }
 
-fn insert_pending_deposit<T: Trait>(input_address: &Address, txid: H256, balance: u64) {
+fn insert_pending_deposit<T: Trait>(
+    input_address: &Address,
+    txid: H256,
+    balance: u64,
+    block_hash: H256,
+) {
     let addr_bytes = addr2vecu8(input_address);
 
     let cache = BtcDepositCache { txid, balance };
@@ -180,82 +478,138 @@ fn insert_pending_deposit<T: Trait>(input_address: &Address, txid: H256, balance
                 balance
             );
             list.push(cache);
+            PendingDepositBlock::insert(&txid, block_hash);
+            BlockChanges::mutate(&block_hash, |changes| {
+                changes.pending_deposits.push((addr_bytes.clone(), txid));
+            });
 
             Module::<T>::deposit_event(Event::<T>::UnclaimedDeposit(txid, addr_bytes.clone()));
         }
     });
 }
 
-fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
-    if let Some(proposal) = WithdrawalProposal::<T>::take() {
+fn withdraw<T: Trait>(tx: Transaction, block_hash: H256, input_value: Option<u64>) -> BtcTxResult {
+    let tx_hash = tx.hash();
+    // the tx may be another version of the proposal tx (e.g. the one replaced by a higher fee
+    // tx, but still be packed by miners), which spends the same inputs and withdrawal outputs
+    let proposal = WithdrawalProposals::<T>::iter().find(|(txid, proposal)| {
+        *txid == unsigned_txid(&tx)
+            || (spends_same_inputs(&tx, &proposal.tx)
+                && check_withdraw_tx_impl::<T>(
+                    &tx,
+                    &proposal.withdrawal_id_list,
+                    proposal.withdrawal_fee,
+                )
+                .is_ok())
+    });
+    if let Some((txid, proposal)) = proposal {
         native::debug!(
             target: xp_logging::RUNTIME_TARGET,
             "[withdraw] Withdraw tx {:?}, proposal:{:?}",
             proposal,
             tx
         );
-        let proposal_hash = proposal.tx.hash();
-        let tx_hash = tx.hash();
-
-        if proposal_hash == tx_hash {
-            let mut total = BalanceOf::<T>::zero();
-            for number in proposal.withdrawal_id_list.iter() {
-                // just for event record
-                let withdraw_balance =
-                    xpallet_gateway_records::Module::<T>::pending_withdrawals(number)
-                        .map(|record| record.balance())
-                        .unwrap_or(BalanceOf::<T>::zero());
-                total += withdraw_balance;
-
-                match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
-                    Ok(_) => {
-                        info!("[withdraw] Withdrawal ({}) completion", *number);
-                    }
-                    Err(err) => {
-                        error!(
-                            "[withdraw] Withdrawal ({}) error:{:?}, must use root to fix it",
-                            *number, err
-                        );
-                    }
+        WithdrawalProposals::<T>::remove(txid);
+        WithdrawalReplacements::<T>::remove(txid);
+
+        let mut total = BalanceOf::<T>::zero();
+        for number in proposal.withdrawal_id_list.iter() {
+            // just for event record
+            let withdraw_balance =
+                xpallet_gateway_records::Module::<T>::pending_withdrawals(number)
+                    .map(|record| record.balance())
+                    .unwrap_or(BalanceOf::<T>::zero());
+            total = total.saturating_add(withdraw_balance);
+
+            match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
+                Ok(_) => {
+                    info!("[withdraw] Withdrawal ({}) completion", *number);
+                    // note the hashes in the byte order of the block explorers
+                    xpallet_gateway_records::Module::<T>::set_withdrawal_settlement(
+                        *number,
+                        hash_rev(tx_hash),
+                        Some(hash_rev(block_hash)),
+                    );
+                }
+                Err(err) => {
+                    error!(
+                        "[withdraw] Withdrawal ({}) error:{:?}, must use root to fix it",
+                        *number, err
+                    );
                 }
             }
+        }
 
-            let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
-            // real withdraw value would reduce withdraw_fee
-            total -=
-                (proposal.withdrawal_id_list.len() as u64 * btc_withdrawal_fee).saturated_into();
-            Module::<T>::deposit_event(Event::<T>::Withdrawn(
-                tx_hash,
-                proposal.withdrawal_id_list,
-                total,
-            ));
-            BtcTxResult::Success
-        } else {
-            error!(
-                "[withdraw] Withdraw error: mismatch (tx_hash:{:?}, proposal_hash:{:?}), id_list:{:?}, must use root to fix it",
-                tx_hash, proposal_hash, proposal.withdrawal_id_list
-            );
-            // re-store proposal into storage.
-            WithdrawalProposal::<T>::put(proposal);
-
-            Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(proposal_hash, tx_hash));
-            BtcTxResult::Failure
+        // the fee is the one charged when the proposal was created, the current fee may have
+        // been updated by the fee rates of the relayed withdrawal txs since then
+        let fee_income: BalanceOf<T> = (proposal.withdrawal_id_list.len() as u64)
+            .saturating_mul(proposal.withdrawal_fee)
+            .saturated_into();
+        // real withdraw value would reduce withdraw_fee
+        total = total.saturating_sub(fee_income);
+        // the miner fee is paid from the withdrawal fee, only the rest is left in the trustee
+        // utxos and could be credited as X-BTC. If the spent utxos are unknown, the whole fee
+        // is taken as the miner fee to never issue unbacked X-BTC.
+        let miner_fee: BalanceOf<T> = match input_value.and_then(|value| fee::tx_fee(&tx, value)) {
+            Some(miner_fee) => miner_fee.saturated_into(),
+            None => {
+                warn!(
+                    "[withdraw] Unknown miner fee of withdrawal tx:{:?}, no fee is credited",
+                    hash_rev(tx_hash)
+                );
+                fee_income
+            }
+        };
+        Module::<T>::credit_withdrawal_fee(
+            &proposal.trustee_list,
+            fee_income.saturating_sub(miner_fee),
+        );
+        Module::<T>::deposit_event(Event::<T>::Withdrawn(
+            tx_hash,
+            proposal.withdrawal_id_list,
+            total,
+            fee_income,
+            miner_fee,
+        ));
+        if let Some(fee_rate) = input_value.and_then(|value| fee::tx_fee_rate(&tx, value)) {
+            fee::note_withdrawal_fee_rate::<T>(fee_rate);
         }
+        BtcTxResult::Success
     } else {
         error!(
-            "[withdraw] Withdrawal error: proposal is EMPTY (tx_hash:{:?}), but receive a withdrawal tx, must use root to fix it",
-            tx.hash()
+            "[withdraw] Withdrawal error: no proposal matches the withdrawal tx (tx_hash:{:?}), must use root to fix it",
+            tx_hash
         );
         // no proposal, but find a withdraw tx, it's a fatal error in withdrawal
-        Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(
-            tx.hash(),
-            Default::default(),
-        ));
+        Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(tx_hash, Default::default()));
 
         BtcTxResult::Failure
     }
 }
 
+/// Returns the txid of `tx` without the signatures (script sigs and witnesses).
+///
+/// The signatures of the legacy P2SH multisig inputs would change the txid, the unsigned txid
+/// keeps the same during the signing of the withdrawal proposal, thus it's used as the key.
+pub fn unsigned_txid(tx: &Transaction) -> H256 {
+    let mut tx = tx.clone();
+    for input in tx.inputs.iter_mut() {
+        input.script_sig = Default::default();
+        input.script_witness.clear();
+    }
+    tx.hash()
+}
+
+/// Returns true if `tx1` and `tx2` spend the same inputs in the same order.
+fn spends_same_inputs(tx1: &Transaction, tx2: &Transaction) -> bool {
+    tx1.inputs.len() == tx2.inputs.len()
+        && tx1
+            .inputs
+            .iter()
+            .zip(tx2.inputs.iter())
+            .all(|(a, b)| a.previous_output == b.previous_output)
+}
+
 /// Returns Ok if `tx1` and `tx2` are the same transaction.
 pub fn ensure_identical<T: Trait>(tx1: &Transaction, tx2: &Transaction) -> DispatchResult {
     if tx1.version == tx2.version
@@ -2,7 +2,7 @@
 
 use frame_support::dispatch::DispatchResult;
 
-use light_bitcoin::{chain::Transaction, primitives::Bytes};
+use light_bitcoin::{chain::Transaction, keys::Address, primitives::Bytes};
 
 use crate::types::BtcTxVerifier;
 use crate::{Error, Module, Trait};
@@ -19,6 +19,14 @@ mod runtime_interface {
     ) -> DispatchResult {
         Err(Error::<T>::VerifySignFailed)?
     }
+
+    pub fn verify_message_sig_impl<T: Trait>(
+        _address: &Address,
+        _message: &[u8],
+        _sig: &[u8],
+    ) -> DispatchResult {
+        Err(Error::<T>::VerifySignFailed)?
+    }
 }
 
 pub fn verify_sig<T: Trait>(
@@ -39,3 +47,21 @@ pub fn verify_sig<T: Trait>(
         BtcTxVerifier::Test => Ok(()),
     }
 }
+
+/// Verify the signature of a standard "Bitcoin Signed Message" produced by the key of `address`.
+pub fn verify_message_sig<T: Trait>(
+    address: &Address,
+    message: &[u8],
+    sig: &[u8],
+) -> DispatchResult {
+    match Module::<T>::verifier() {
+        BtcTxVerifier::Recover => {
+            recover_verifier::verify_message_sig_impl::<T>(address, message, sig)
+        }
+        BtcTxVerifier::RuntimeInterface => {
+            runtime_interface::verify_message_sig_impl::<T>(address, message, sig)
+        }
+        #[cfg(any(feature = "runtime-benchmarks", test))]
+        BtcTxVerifier::Test => Ok(()),
+    }
+}
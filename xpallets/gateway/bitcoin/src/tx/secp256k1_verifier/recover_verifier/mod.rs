@@ -8,13 +8,17 @@ mod scalar;
 
 use frame_support::dispatch::DispatchResult;
 use sp_core::ecdsa::Public;
-use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, EcdsaVerifyError};
-use sp_std::convert::TryFrom;
+use sp_io::{
+    crypto::{secp256k1_ecdsa_recover, secp256k1_ecdsa_recover_compressed},
+    EcdsaVerifyError,
+};
+use sp_std::{convert::TryFrom, prelude::Vec};
 
 use light_bitcoin::{
     chain::Transaction,
-    keys::Message,
-    primitives::Bytes,
+    crypto::{dhash160, dhash256},
+    keys::{Address, Message, Type},
+    primitives::{Bytes, H256},
     script::{Script, SignatureVersion, TransactionInputSigner},
 };
 
@@ -80,6 +84,96 @@ pub fn verify_sig_impl<T: Trait>(
     )
 }
 
+/// The magic prefix of the standard "Bitcoin Signed Message".
+const BTC_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// Verify the compact signature (65 bytes, `header || r || s`) of a standard "Bitcoin Signed
+/// Message" produced by the key of `address`.
+///
+/// Header byte (BIP137):
+/// - 27-30: P2PKH address, uncompressed public key
+/// - 31-34: P2PKH address, compressed public key
+/// - 35-38: P2SH-P2WPKH address, compressed public key
+pub fn verify_message_sig_impl<T: Trait>(
+    address: &Address,
+    message: &[u8],
+    sig: &[u8],
+) -> DispatchResult {
+    if sig.len() != 65 {
+        Err(Error::<T>::ConstructBadSign)?
+    }
+    let header = sig[0];
+    if header < 27 || header > 38 {
+        Err(Error::<T>::ConstructBadSign)?
+    }
+    let mut rsv: [u8; 65] = [0; 65];
+    (&mut rsv[0..64]).copy_from_slice(&sig[1..65]);
+    rsv[64] = (header - 27) & 3;
+
+    let hash = message_hash(message);
+    let (kind, hash) = match header {
+        27..=30 => {
+            let recover_pub = secp256k1_ecdsa_recover(&rsv, hash.as_fixed_bytes())
+                .map_err(convert_recover_err::<T>)?;
+            let mut pubkey: [u8; 65] = [0; 65];
+            pubkey[0] = 0x04;
+            (&mut pubkey[1..65]).copy_from_slice(&recover_pub);
+            (Type::P2PKH, dhash160(&pubkey))
+        }
+        31..=34 => {
+            let recover_pub = secp256k1_ecdsa_recover_compressed(&rsv, hash.as_fixed_bytes())
+                .map_err(convert_recover_err::<T>)?;
+            (Type::P2PKH, dhash160(&recover_pub))
+        }
+        _ => {
+            let recover_pub = secp256k1_ecdsa_recover_compressed(&rsv, hash.as_fixed_bytes())
+                .map_err(convert_recover_err::<T>)?;
+            // redeem script of P2SH-P2WPKH: OP_0 <20-byte-pubkey-hash>
+            let mut redeem_script = Vec::with_capacity(22);
+            redeem_script.extend_from_slice(&[0x00, 0x14]);
+            redeem_script.extend_from_slice(dhash160(&recover_pub).as_bytes());
+            (Type::P2SH, dhash160(&redeem_script))
+        }
+    };
+
+    if address.kind == kind && address.hash == hash {
+        Ok(())
+    } else {
+        Err(Error::<T>::VerifySignFailed)?
+    }
+}
+
+/// `dhash256(prefix || compact_size(len) || message)`
+fn message_hash(message: &[u8]) -> H256 {
+    let len = message.len();
+    let mut data = Vec::with_capacity(BTC_SIGNED_MESSAGE_PREFIX.len() + 9 + len);
+    data.extend_from_slice(BTC_SIGNED_MESSAGE_PREFIX);
+    match len {
+        0..=0xfc => data.push(len as u8),
+        0xfd..=0xffff => {
+            data.push(0xfd);
+            data.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            data.push(0xfe);
+            data.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        _ => {
+            data.push(0xff);
+            data.extend_from_slice(&(len as u64).to_le_bytes());
+        }
+    }
+    data.extend_from_slice(message);
+    dhash256(&data)
+}
+
+fn convert_recover_err<T: Trait>(e: EcdsaVerifyError) -> Error<T> {
+    match e {
+        EcdsaVerifyError::BadRS | EcdsaVerifyError::BadV => Error::<T>::ConstructBadSign,
+        EcdsaVerifyError::BadSignature => Error::<T>::BadSignature,
+    }
+}
+
 pub struct TransactionSignatureChecker<T: Trait> {
     pub signer: TransactionInputSigner,
     pub input_index: usize,
@@ -83,12 +83,83 @@ pub struct BtcDepositCache {
     pub balance: u64,
 }
 
+/// An unspent output of the trustee address, used for building the withdrawal tx.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcUtxo {
+    pub txid: H256,
+    pub vout: u32,
+    pub value: u64,
+}
+
+/// An unspent output controlled by the trustee (hot or cold) addresses.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+pub struct BtcTrusteeUtxo {
+    pub txid: H256,
+    pub vout: u32,
+    pub value: u64,
+    /// the base58 trustee address which controls this output
+    pub addr: BtcAddress,
+}
+
+/// The reserve report of X-BTC, for checking whether X-BTC is fully backed by the BTC held by
+/// the trustees, i.e. `trustee_balance >= total_issuance + pending_deposits`.
+///
+/// The check is meaningful only if `utxo_set_seeded` is true, otherwise `trustee_balance` misses
+/// the trustee utxos created before the utxo tracking.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcReserveReport<Balance> {
+    /// total value (satoshi) of the tracked trustee utxos
+    pub trustee_balance: u64,
+    /// whether the trustee utxo set has been fully seeded
+    pub utxo_set_seeded: bool,
+    /// total issuance of X-BTC, including the balance locked by the pending withdrawals
+    pub total_issuance: Balance,
+    /// total balance of the applying and processing withdrawals, which are not settled on
+    /// bitcoin yet
+    pub pending_withdrawals: Balance,
+    /// total balance of the delayed and frozen withdrawals, which would not be processed until
+    /// root restores them
+    pub delayed_withdrawals: Balance,
+    /// total value (satoshi) of the unclaimed deposits
+    pub pending_deposits: u64,
+}
+
+/// A deposit which has been credited to `who`, it would be reverted once the block
+/// containing the deposit tx leaves the main chain.
+#[derive(PartialEq, Clone, Encode, Decode, Default, RuntimeDebug)]
+pub struct BtcCreditedDeposit<AccountId> {
+    pub txid: H256,
+    pub who: AccountId,
+    pub balance: u64,
+}
+
+/// The changes of the trustee utxos and the pending deposits made by the txs of a block, they
+/// would be undone once the block leaves the main chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+pub struct BtcBlockChanges {
+    /// txs which change the trustee utxos, their tx states would be removed when undoing
+    pub txs: Vec<H256>,
+    /// trustee utxos spent by the txs
+    pub spent_utxos: Vec<BtcTrusteeUtxo>,
+    /// trustee utxos created by the txs, (txid, vout)
+    pub created_utxos: Vec<(H256, u32)>,
+    /// pending deposits inserted by the txs, (input address, txid)
+    pub pending_deposits: Vec<(BtcAddress, H256)>,
+}
+
 #[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct BtcWithdrawalProposal<AccountId> {
     pub sig_state: VoteResult,
     pub withdrawal_id_list: Vec<u32>,
     pub tx: BtcTransaction,
     pub trustee_list: Vec<(AccountId, bool)>,
+    /// the withdrawal fee of each withdrawal when the proposal was created, which is deducted
+    /// from the withdrawal outputs of `tx`
+    pub withdrawal_fee: u64,
 }
 
 impl<AccountId> BtcWithdrawalProposal<AccountId> {
@@ -97,16 +168,39 @@ impl<AccountId> BtcWithdrawalProposal<AccountId> {
         withdrawal_id_list: Vec<u32>,
         tx: BtcTransaction,
         trustee_list: Vec<(AccountId, bool)>,
+        withdrawal_fee: u64,
     ) -> Self {
         Self {
             sig_state,
             withdrawal_id_list,
             tx,
             trustee_list,
+            withdrawal_fee,
         }
     }
 }
 
+/// The withdrawal proposal before the withdrawal fee was kept in it, only for the migration.
+#[derive(Encode, Decode)]
+pub struct LegacyBtcWithdrawalProposal<AccountId> {
+    pub sig_state: VoteResult,
+    pub withdrawal_id_list: Vec<u32>,
+    pub tx: BtcTransaction,
+    pub trustee_list: Vec<(AccountId, bool)>,
+}
+
+impl<AccountId> LegacyBtcWithdrawalProposal<AccountId> {
+    pub fn upgrade(self, withdrawal_fee: u64) -> BtcWithdrawalProposal<AccountId> {
+        BtcWithdrawalProposal::new(
+            self.sig_state,
+            self.withdrawal_id_list,
+            self.tx,
+            self.trustee_list,
+            withdrawal_fee,
+        )
+    }
+}
+
 #[derive(PartialEq, Clone, Copy, Eq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum VoteResult {
@@ -37,10 +37,20 @@ pub trait WeightInfo {
     fn set_best_index() -> Weight;
     fn set_confirmed_index() -> Weight;
     fn remove_pending() -> Weight;
+    fn claim_pending_deposits() -> Weight;
+    fn bind_btc_address() -> Weight;
     fn remove_proposal() -> Weight;
     fn force_replace_proposal_tx() -> Weight;
     fn set_btc_withdrawal_fee() -> Weight;
     fn set_btc_deposit_limit() -> Weight;
+    fn set_header_retention_window() -> Weight;
+    fn set_max_reorg_depth() -> Weight;
+    fn set_max_proposal_count() -> Weight;
+    fn set_trustee_reward_ratio() -> Weight;
+    fn set_max_missed_proposals() -> Weight;
+    fn set_withdrawal_fee_destination() -> Weight;
+    fn set_btc_withdrawal_fee_bounds() -> Weight;
+    fn seed_trustee_utxos(u: u32) -> Weight;
 }
 
 /// Weights for xpallet_gateway_bitcoin using the Substrate node and recommended hardware.
@@ -77,6 +87,16 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(9 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
+    fn claim_pending_deposits() -> Weight {
+        (612_735_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    fn bind_btc_address() -> Weight {
+        (97_384_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_proposal() -> Weight {
         (4_976_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
@@ -91,6 +111,41 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_header_retention_window() -> Weight {
+        (6_102_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_reorg_depth() -> Weight {
+        (6_102_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_proposal_count() -> Weight {
+        (4_312_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_reward_ratio() -> Weight {
+        (4_405_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_missed_proposals() -> Weight {
+        (4_298_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_fee_destination() -> Weight {
+        (4_867_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_btc_withdrawal_fee_bounds() -> Weight {
+        (21_395_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn seed_trustee_utxos(u: u32) -> Weight {
+        (27_462_000 as Weight)
+            .saturating_add((6_318_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -126,6 +181,16 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(9 as Weight))
             .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
+    fn claim_pending_deposits() -> Weight {
+        (612_735_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn bind_btc_address() -> Weight {
+        (97_384_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_proposal() -> Weight {
         (4_976_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
@@ -140,4 +205,39 @@ impl WeightInfo for () {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_header_retention_window() -> Weight {
+        (6_102_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_reorg_depth() -> Weight {
+        (6_102_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_proposal_count() -> Weight {
+        (4_312_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_reward_ratio() -> Weight {
+        (4_405_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_missed_proposals() -> Weight {
+        (4_298_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_fee_destination() -> Weight {
+        (4_867_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_btc_withdrawal_fee_bounds() -> Weight {
+        (21_395_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn seed_trustee_utxos(u: u32) -> Weight {
+        (27_462_000 as Weight)
+            .saturating_add((6_318_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
 }
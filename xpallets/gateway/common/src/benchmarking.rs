@@ -5,14 +5,14 @@ use frame_benchmarking::benchmarks;
 use frame_support::storage::StorageMap;
 use frame_system::RawOrigin;
 use sp_core::crypto::AccountId32;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::{traits::StaticLookup, SaturatedConversion};
 use sp_std::prelude::*;
 
 use chainx_primitives::AssetId;
 use xpallet_assets::{BalanceOf, Chain};
 use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalRecordId, WithdrawalState};
 
-use crate::{types::*, Call, Module, Trait, TrusteeMultiSigAddr};
+use crate::{traits::AddressBinding, types::*, Call, Module, Trait, TrusteeMultiSigAddr};
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
 
@@ -64,6 +64,28 @@ fn new_trustees<T: Trait>() -> Vec<(T::AccountId, Vec<u8>, Vec<u8>, Vec<u8>)> {
     ]
 }
 
+fn setup_trustee_session<T: Trait>() {
+    let mut trustees = vec![];
+    for (account, about, hot, cold) in new_trustees::<T>() {
+        Module::<T>::setup_trustee_impl(account.clone(), Chain::Bitcoin, about, hot, cold).unwrap();
+        trustees.push(account);
+    }
+    Module::<T>::transition_trustee_session_impl(Chain::Bitcoin, trustees).unwrap();
+}
+fn propose_key_rotation<T: Trait>() {
+    let hot =
+        hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    let cold =
+        hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5").unwrap();
+    Module::<T>::propose_key_rotation(
+        RawOrigin::Signed(alice::<T>()).into(),
+        Chain::Bitcoin,
+        hot,
+        cold,
+    )
+    .unwrap();
+}
+
 benchmarks! {
     _{ }
 
@@ -185,6 +207,75 @@ benchmarks! {
     verify {
         assert_eq!(Module::<T>::referral_binding_of(&who, Chain::Bitcoin), Some(who));
     }
+
+    unbind_address {
+        let who: T::AccountId = alice::<T>();
+        let address = b"1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_vec();
+        <Module<T> as AddressBinding<_, _>>::update_binding(Chain::Bitcoin, address.clone(), who.clone());
+    }: _(RawOrigin::Signed(who.clone()), Chain::Bitcoin, address.clone(), None)
+    verify {
+        assert_eq!(Module::<T>::address_binding_of(Chain::Bitcoin, &address), None);
+        assert!(Module::<T>::bound_addrs(&who).is_empty());
+    }
+
+    set_withdrawal_quota {
+        let quota = WithdrawalQuota {
+            window: 14400u32.into(),
+            per_account: 10_00000000u32.into(),
+            global: 100_00000000u64.saturated_into(),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(quota.clone()))
+    verify {
+        assert_eq!(Module::<T>::withdrawal_quota_of(ASSET_ID), Some(quota));
+    }
+
+    propose_key_rotation {
+        let caller: T::AccountId = alice::<T>();
+        setup_trustee_session::<T>();
+        let hot = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap();
+        let cold = hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+            .unwrap();
+    }: _(RawOrigin::Signed(caller), Chain::Bitcoin, hot, cold)
+    verify {
+        assert!(Module::<T>::key_rotation_of(Chain::Bitcoin).is_some());
+    }
+
+    approve_key_rotation {
+        setup_trustee_session::<T>();
+        propose_key_rotation::<T>();
+
+        // approve until the last approval required.
+        let info = Module::<T>::current_trustee_session_info(Chain::Bitcoin).unwrap();
+        let mut approvers = info.0.trustee_list.into_iter().filter(|who| *who != alice::<T>());
+        let mut approvals = 1;
+        while approvals + 1 < info.0.threshold {
+            let who = approvers.next().unwrap();
+            Module::<T>::approve_key_rotation(RawOrigin::Signed(who).into(), Chain::Bitcoin).unwrap();
+            approvals += 1;
+        }
+        let caller = approvers.next().unwrap();
+    }: _(RawOrigin::Signed(caller), Chain::Bitcoin)
+    verify {
+        assert_eq!(
+            Module::<T>::key_rotation_of(Chain::Bitcoin).map(|rotation| rotation.status),
+            Some(KeyRotationStatus::Sweeping(1))
+        );
+    }
+
+    set_trustee_election_interval {
+    }: _(RawOrigin::Root, 4)
+    verify {
+        assert_eq!(Module::<T>::trustee_election_interval(), 4);
+    }
+
+    cancel_key_rotation {
+        setup_trustee_session::<T>();
+        propose_key_rotation::<T>();
+    }: _(RawOrigin::Root, Chain::Bitcoin)
+    verify {
+        assert!(Module::<T>::key_rotation_of(Chain::Bitcoin).is_none());
+    }
 }
 
 #[cfg(test)]
@@ -203,6 +294,12 @@ mod tests {
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
             assert_ok!(test_benchmark_set_trustee_info_config::<Test>());
             assert_ok!(test_benchmark_force_set_referral_binding::<Test>());
+            assert_ok!(test_benchmark_unbind_address::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_quota::<Test>());
+            assert_ok!(test_benchmark_propose_key_rotation::<Test>());
+            assert_ok!(test_benchmark_approve_key_rotation::<Test>());
+            assert_ok!(test_benchmark_cancel_key_rotation::<Test>());
+            assert_ok!(test_benchmark_set_trustee_election_interval::<Test>());
         });
     }
 }
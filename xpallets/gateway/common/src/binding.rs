@@ -9,7 +9,7 @@ use xpallet_assets::Chain;
 use xpallet_support::{traits::Validator, try_addr, try_str};
 
 use crate::traits::{AddressBinding, ReferralBinding};
-use crate::{AddressBindingOf, BoundAddressOf, Module, Trait};
+use crate::{AddressBindingOf, BoundAddressOf, Event, Module, Trait};
 
 impl<T: Trait> ReferralBinding<T::AccountId> for Module<T> {
     fn update_binding(assert_id: &AssetId, who: &T::AccountId, referral_name: Option<ReferralId>) {
@@ -56,18 +56,27 @@ impl<T: Trait> ReferralBinding<T::AccountId> for Module<T> {
 impl<T: Trait, Address: Into<Vec<u8>>> AddressBinding<T::AccountId, Address> for Module<T> {
     fn update_binding(chain: Chain, address: Address, who: T::AccountId) {
         let address = address.into();
-        if let Some(accountid) = AddressBindingOf::<T>::get(chain, &address) {
-            if accountid != who {
+        match AddressBindingOf::<T>::get(chain, &address) {
+            Some(accountid) if accountid == who => {
+                // nothing changed, do not emit the event again for each deposit
+                debug!(
+                    "[update_address_binding] Already bound:[chain:{:?}, addr:{:?}, who:{:?}]",
+                    chain,
+                    try_addr(&address),
+                    who,
+                );
+                return;
+            }
+            Some(accountid) => {
                 debug!(
                     "[update_address_binding] Current address binding need to changed (old:{:?} => new:{:?})",
                     accountid, who
                 );
                 // old accountid is not equal to new accountid, means should change this addr bind to new account
                 // remove this addr for old accounid's CrossChainBindOf
-                BoundAddressOf::<T>::mutate(accountid, chain, |addr_list| {
-                    addr_list.retain(|addr| addr != &address);
-                });
+                Module::<T>::remove_bound_address(&accountid, chain, &address);
             }
+            None => {}
         }
         // insert or override binding relationship
         BoundAddressOf::<T>::mutate(&who, chain, |addr_list| {
@@ -82,7 +91,8 @@ impl<T: Trait, Address: Into<Vec<u8>>> AddressBinding<T::AccountId, Address> for
             try_addr(&address),
             who,
         );
-        AddressBindingOf::<T>::insert(chain, address, who);
+        AddressBindingOf::<T>::insert(chain, &address, who.clone());
+        Module::<T>::deposit_event(Event::<T>::AddressBinded(who, chain, address));
     }
 
     fn address(chain: Chain, address: Address) -> Option<T::AccountId> {
@@ -91,6 +101,38 @@ impl<T: Trait, Address: Into<Vec<u8>>> AddressBinding<T::AccountId, Address> for
     }
 }
 
+impl<T: Trait> Module<T> {
+    /// Remove the binding of `address`, both `AddressBindingOf` and `BoundAddressOf` would be
+    /// updated.
+    pub fn remove_address_binding(chain: Chain, address: &ChainAddress) -> Option<T::AccountId> {
+        let who = AddressBindingOf::<T>::take(chain, address)?;
+        Self::remove_bound_address(&who, chain, address);
+
+        info!(
+            "[remove_address_binding] Remove address binding:[chain:{:?}, addr:{:?}, who:{:?}]",
+            chain,
+            try_addr(address),
+            who,
+        );
+        Self::deposit_event(Event::<T>::AddressUnbinded(
+            who.clone(),
+            chain,
+            address.clone(),
+        ));
+        Some(who)
+    }
+
+    fn remove_bound_address(who: &T::AccountId, chain: Chain, address: &[u8]) {
+        let mut addr_list = BoundAddressOf::<T>::get(who, chain);
+        addr_list.retain(|addr| addr.as_slice() != address);
+        if addr_list.is_empty() {
+            BoundAddressOf::<T>::remove(who, chain);
+        } else {
+            BoundAddressOf::<T>::insert(who, chain, addr_list);
+        }
+    }
+}
+
 // export for runtime-api
 impl<T: Trait> Module<T> {
     pub fn bound_addrs(who: &T::AccountId) -> BTreeMap<Chain, Vec<ChainAddress>> {
@@ -27,20 +27,25 @@ use frame_support::{
     ensure, IterableStorageMap,
 };
 use frame_system::{ensure_root, ensure_signed};
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::{
+    traits::{Saturating, StaticLookup, Zero},
+    Perbill,
+};
 use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
 
+use orml_utilities::with_transaction_result;
+
 use chainx_primitives::{AddrStr, AssetId, ChainAddress, Text};
-use xp_logging::{error, info};
+use xp_logging::{error, info, warn};
 use xp_runtime::Memo;
 use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, ChainT, WithdrawalLimit};
 use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
-use xpallet_support::traits::{MultisigAddressFor, Validator};
+use xpallet_support::traits::{MultisigAddressFor, OnNewEra, Validator};
 
-use self::traits::TrusteeForChain;
+use self::traits::{AddressOwnership, TrusteeForChain};
 use self::types::{
-    GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
-    TrusteeIntentionProps,
+    AddressOwnershipProof, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, KeyRotation,
+    KeyRotationStatus, TrusteeInfoConfig, TrusteeIntentionProps, WithdrawalQuota, WithdrawalUsage,
 };
 pub use self::weights::WeightInfo;
 
@@ -52,13 +57,37 @@ pub trait Trait: xpallet_gateway_records::Trait {
     type DetermineMultisigAddress: MultisigAddressFor<Self::AccountId>;
 
     // for bitcoin
-    type Bitcoin: ChainT<BalanceOf<Self>>;
+    type Bitcoin: ChainT<BalanceOf<Self>> + AddressOwnership<Self::AccountId>;
     type BitcoinTrustee: TrusteeForChain<
         Self::AccountId,
         trustees::bitcoin::BtcTrusteeType,
         trustees::bitcoin::BtcTrusteeAddrInfo,
     >;
 
+    // for ethereum
+    type Ethereum: ChainT<BalanceOf<Self>>;
+    type EthereumTrustee: TrusteeForChain<
+        Self::AccountId,
+        trustees::ethereum::EthTrusteeType,
+        trustees::ethereum::EthTrusteeAddrInfo,
+    >;
+
+    // for polkadot
+    type Polkadot: ChainT<BalanceOf<Self>> + AddressOwnership<Self::AccountId>;
+    type PolkadotTrustee: TrusteeForChain<
+        Self::AccountId,
+        trustees::polkadot::DotTrusteeType,
+        trustees::polkadot::DotTrusteeAddrInfo,
+    >;
+
+    // for the dummy local chain
+    type Local: ChainT<BalanceOf<Self>>;
+    type LocalTrustee: TrusteeForChain<
+        Self::AccountId,
+        trustees::local::LocalTrusteeType,
+        trustees::local::LocalTrusteeAddrInfo,
+    >;
+
     type WeightInfo: WeightInfo;
 }
 
@@ -72,6 +101,24 @@ decl_event!(
         ReferralBinded(AccountId, Chain, AccountId),
         /// The trustee set of a chain was changed. [chain, session_number, session_info]
         TrusteeSetChanged(Chain, u32, GenericTrusteeSessionInfo<AccountId>),
+        /// An address of some chain was bound to an account. [who, chain, address]
+        AddressBinded(AccountId, Chain, ChainAddress),
+        /// An address of some chain was unbound from an account. [who, chain, address]
+        AddressUnbinded(AccountId, Chain, ChainAddress),
+        /// A trustee proposed to rotate its keys. [who, chain]
+        KeyRotationProposed(AccountId, Chain),
+        /// A trustee approved the key rotation. [who, chain]
+        KeyRotationApproved(AccountId, Chain),
+        /// The key rotation was approved and the new trustee session was generated, waiting for
+        /// the assets to be swept to the new trustee addresses. [chain, session_number]
+        KeyRotationSweeping(Chain, u32),
+        /// The `TrusteeTransition` tx of the key rotation was relayed. [chain, session_number]
+        KeyRotationFinished(Chain, u32),
+        /// The key rotation was canceled by root. [chain]
+        KeyRotationCanceled(Chain),
+        /// The `TrusteeTransition` tx to the new trustee session was relayed.
+        /// [chain, session_number]
+        TrusteeTransitionFinished(Chain, u32),
     }
 );
 
@@ -96,6 +143,28 @@ decl_error! {
         NotRegistered,
         /// just allow validator to register trustee
         NotValidator,
+        /// the address is not bound to any account
+        NotBound,
+        /// the caller is neither the bound account nor the owner of the address
+        NotAddressOwner,
+        /// the window of withdrawal quota should not be zero
+        InvalidWithdrawalQuota,
+        /// the withdrawal exceeds the withdrawal quota of the account within the window
+        ExceedAccountWithdrawalQuota,
+        /// the withdrawal exceeds the global withdrawal quota within the window
+        ExceedGlobalWithdrawalQuota,
+        /// the account is not in the current trustee session
+        NotTrustee,
+        /// there is already a key rotation in progress
+        KeyRotationInProgress,
+        /// no key rotation is waiting for approvals
+        NoProposedKeyRotation,
+        /// the trustee has approved the key rotation
+        DuplicatedApproval,
+        /// the trustee candidates are less than the minimum trustee count
+        NotEnoughTrusteeCandidates,
+        /// the assets are not swept to the new trustee session yet
+        TrusteeTransitionInProgress,
     }
 }
 
@@ -129,7 +198,7 @@ decl_storage! {
             => Option<GenericTrusteeIntentionProps>;
 
         /// The account of the corresponding chain and chain address.
-        pub AddressBindingOf:
+        pub AddressBindingOf get(fn address_binding_of):
             double_map hasher(twox_64_concat) Chain, hasher(blake2_128_concat) ChainAddress
             => Option<T::AccountId>;
 
@@ -142,6 +211,33 @@ decl_storage! {
         pub ReferralBindingOf get(fn referral_binding_of):
             double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) Chain
             => Option<T::AccountId>;
+
+        /// The rolling-window withdrawal quota of the asset, no limit if not set.
+        pub WithdrawalQuotaOf get(fn withdrawal_quota_of):
+            map hasher(twox_64_concat) AssetId
+            => Option<WithdrawalQuota<BalanceOf<T>, T::BlockNumber>>;
+
+        /// The used withdrawal quota of the corresponding asset and account.
+        pub AccountWithdrawalUsageOf get(fn account_withdrawal_usage_of):
+            double_map hasher(twox_64_concat) AssetId, hasher(blake2_128_concat) T::AccountId
+            => WithdrawalUsage<BalanceOf<T>, T::BlockNumber>;
+
+        /// The used global withdrawal quota of the asset.
+        pub GlobalWithdrawalUsageOf get(fn global_withdrawal_usage_of):
+            map hasher(twox_64_concat) AssetId => WithdrawalUsage<BalanceOf<T>, T::BlockNumber>;
+
+        /// The era interval of the automatic trustee election, the election is disabled if it's
+        /// zero.
+        pub TrusteeElectionInterval get(fn trustee_election_interval): u32;
+
+        /// The key rotation in progress of the corresponding chain.
+        pub KeyRotationOf get(fn key_rotation_of):
+            map hasher(twox_64_concat) Chain => Option<KeyRotation<T::AccountId>>;
+
+        /// The new trustee session of the corresponding chain, waiting for the assets of the
+        /// previous trustee session to be swept to it.
+        pub PendingTransitionOf get(fn pending_transition_of):
+            map hasher(twox_64_concat) Chain => Option<u32>;
     }
     add_extra_genesis {
         config(trustees): Vec<(Chain, TrusteeInfoConfig, Vec<(T::AccountId, Text, Vec<u8>, Vec<u8>)>)>;
@@ -194,8 +290,10 @@ decl_module! {
                 xpallet_assets::Error::<T>::ActionNotAllowed,
             );
             Self::verify_withdrawal(asset_id, value, &addr, &ext)?;
+            Self::check_account_withdrawal_quota(&who, asset_id, value)?;
 
             xpallet_gateway_records::Module::<T>::withdraw(&who, asset_id, value, addr, ext)?;
+            Self::use_withdrawal_quota(&who, asset_id, value);
             Ok(())
         }
 
@@ -248,6 +346,73 @@ decl_module! {
             Self::transition_trustee_session_impl(chain, new_trustees)
         }
 
+        /// Propose to rotate the keys of the caller, which must be a trustee of current session.
+        ///
+        /// Once approved by `threshold` trustees of current session (two thirds for Bitcoin), a
+        /// new trustee session with the same trustees is generated, and the key rotation is
+        /// finished after the `TrusteeTransition` tx is relayed.
+        #[weight = <T as Trait>::WeightInfo::propose_key_rotation()]
+        pub fn propose_key_rotation(
+            origin,
+            chain: Chain,
+            hot_entity: Vec<u8>,
+            cold_entity: Vec<u8>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let info = Self::current_trustee_session_info(chain)?;
+            ensure!(info.0.trustee_list.contains(&who), Error::<T>::NotTrustee);
+            ensure!(Self::key_rotation_of(chain).is_none(), Error::<T>::KeyRotationInProgress);
+            ensure!(
+                Self::pending_transition_of(chain).is_none(),
+                Error::<T>::TrusteeTransitionInProgress
+            );
+
+            let about = Self::trustee_intention_props_of(&who, chain)
+                .ok_or(Error::<T>::NotRegistered)?
+                .0
+                .about;
+            let props = Self::check_trustee_props(chain, about, hot_entity, cold_entity)?;
+            let rotation = KeyRotation {
+                trustee: who.clone(),
+                props,
+                approvals: vec![who.clone()],
+                status: KeyRotationStatus::Proposed,
+            };
+            with_transaction_result(|| {
+                Self::deposit_event(Event::<T>::KeyRotationProposed(who, chain));
+                Self::try_apply_key_rotation(chain, rotation, info.0.threshold)
+            })
+        }
+
+        /// Approve the key rotation of the chain, the caller must be a trustee of current session.
+        #[weight = <T as Trait>::WeightInfo::approve_key_rotation()]
+        pub fn approve_key_rotation(origin, chain: Chain) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let info = Self::current_trustee_session_info(chain)?;
+            ensure!(info.0.trustee_list.contains(&who), Error::<T>::NotTrustee);
+
+            let mut rotation = Self::key_rotation_of(chain)
+                .filter(|rotation| rotation.status == KeyRotationStatus::Proposed)
+                .ok_or(Error::<T>::NoProposedKeyRotation)?;
+            ensure!(!rotation.approvals.contains(&who), Error::<T>::DuplicatedApproval);
+            rotation.approvals.push(who.clone());
+            with_transaction_result(|| {
+                Self::deposit_event(Event::<T>::KeyRotationApproved(who, chain));
+                Self::try_apply_key_rotation(chain, rotation, info.0.threshold)
+            })
+        }
+
+        /// Cancel the key rotation of the chain.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::cancel_key_rotation()]
+        pub fn cancel_key_rotation(origin, chain: Chain) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(KeyRotationOf::<T>::take(chain).is_some(), Error::<T>::NoProposedKeyRotation);
+            Self::deposit_event(Event::<T>::KeyRotationCanceled(chain));
+            Ok(())
+        }
+
         /// Set the state of withdraw record by the trustees.
         #[weight = <T as Trait>::WeightInfo::set_withdrawal_state()]
         pub fn set_withdrawal_state(
@@ -276,6 +441,59 @@ decl_module! {
             Ok(())
         }
 
+        /// Set the era interval of the automatic trustee election, `0` to disable it.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_trustee_election_interval()]
+        pub fn set_trustee_election_interval(origin, #[compact] interval: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            TrusteeElectionInterval::put(interval);
+            Ok(())
+        }
+
+        /// Set the rolling-window withdrawal quota of `asset_id`, pass `None` to remove the
+        /// limit.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_quota()]
+        pub fn set_withdrawal_quota(
+            origin,
+            #[compact] asset_id: AssetId,
+            quota: Option<WithdrawalQuota<BalanceOf<T>, T::BlockNumber>>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match quota {
+                Some(quota) => {
+                    ensure!(!quota.window.is_zero(), Error::<T>::InvalidWithdrawalQuota);
+                    WithdrawalQuotaOf::<T>::insert(asset_id, quota);
+                }
+                None => WithdrawalQuotaOf::<T>::remove(asset_id),
+            }
+            Ok(())
+        }
+
+        /// Unbind the `address` of `chain` from its bound account.
+        ///
+        /// Callable by the bound account, or by anyone proving the ownership of `address` with
+        /// `proof` (e.g. the "Bitcoin Signed Message" signature over the caller's account).
+        #[weight = <T as Trait>::WeightInfo::unbind_address()]
+        pub fn unbind_address(
+            origin,
+            chain: Chain,
+            address: ChainAddress,
+            proof: Option<AddressOwnershipProof>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bound = Self::address_binding_of(chain, &address).ok_or(Error::<T>::NotBound)?;
+            if bound != who {
+                let proof = proof.ok_or(Error::<T>::NotAddressOwner)?;
+                Self::verify_address_ownership(chain, &who, &address, &proof)?;
+            }
+            Self::remove_address_binding(chain, &address);
+            Ok(())
+        }
+
         /// Set the referral binding of corresponding chain and account.
         ///
         /// This is a root-only operation.
@@ -303,6 +521,9 @@ impl<T: Trait> Module<T> {
         let chain = xpallet_assets_registrar::Module::<T>::chain_of(asset_id)?;
         match chain {
             Chain::Bitcoin => T::Bitcoin::withdrawal_limit(&asset_id),
+            Chain::Ethereum => T::Ethereum::withdrawal_limit(&asset_id),
+            Chain::Polkadot => T::Polkadot::withdrawal_limit(&asset_id),
+            Chain::Local => T::Local::withdrawal_limit(&asset_id),
             _ => Err(Error::<T>::NotSupportedChain.into()),
         }
     }
@@ -321,6 +542,15 @@ impl<T: Trait> Module<T> {
                 // bitcoin do not need memo
                 T::Bitcoin::check_addr(&addr, b"")?;
             }
+            Chain::Ethereum => {
+                T::Ethereum::check_addr(&addr, b"")?;
+            }
+            Chain::Polkadot => {
+                T::Polkadot::check_addr(&addr, b"")?;
+            }
+            Chain::Local => {
+                T::Local::check_addr(&addr, b"")?;
+            }
             _ => return Err(Error::<T>::NotSupportedChain.into()),
         };
         // we could only split withdrawal limit due to a runtime-api would call `withdrawal_limit`
@@ -330,8 +560,92 @@ impl<T: Trait> Module<T> {
         if value < limit.minimal_withdrawal {
             return Err(Error::<T>::InvalidWithdrawal.into());
         }
+        // the per-account quota is checked in `withdraw`, since the account is unknown here.
+        if let Some(quota) = Self::withdrawal_quota_of(asset_id) {
+            let used = Self::used_withdrawal_quota(
+                &Self::global_withdrawal_usage_of(asset_id),
+                quota.global,
+                quota.window,
+            );
+            ensure!(
+                used.saturating_add(value) <= quota.global,
+                Error::<T>::ExceedGlobalWithdrawalQuota
+            );
+        }
         Ok(())
     }
+
+    fn check_account_withdrawal_quota(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        if let Some(quota) = Self::withdrawal_quota_of(asset_id) {
+            let used = Self::used_withdrawal_quota(
+                &Self::account_withdrawal_usage_of(asset_id, who),
+                quota.per_account,
+                quota.window,
+            );
+            ensure!(
+                used.saturating_add(value) <= quota.per_account,
+                Error::<T>::ExceedAccountWithdrawalQuota
+            );
+        }
+        Ok(())
+    }
+
+    fn use_withdrawal_quota(who: &T::AccountId, asset_id: AssetId, value: BalanceOf<T>) {
+        if let Some(quota) = Self::withdrawal_quota_of(asset_id) {
+            let now = frame_system::Module::<T>::block_number();
+            AccountWithdrawalUsageOf::<T>::mutate(asset_id, who, |usage| {
+                let used = Self::used_withdrawal_quota(usage, quota.per_account, quota.window);
+                *usage = WithdrawalUsage {
+                    amount: used.saturating_add(value),
+                    updated_at: now,
+                };
+            });
+            GlobalWithdrawalUsageOf::<T>::mutate(asset_id, |usage| {
+                let used = Self::used_withdrawal_quota(usage, quota.global, quota.window);
+                *usage = WithdrawalUsage {
+                    amount: used.saturating_add(value),
+                    updated_at: now,
+                };
+            });
+        }
+    }
+
+    /// The used quota at current block, `cap` is released linearly over `window`.
+    fn used_withdrawal_quota(
+        usage: &WithdrawalUsage<BalanceOf<T>, T::BlockNumber>,
+        cap: BalanceOf<T>,
+        window: T::BlockNumber,
+    ) -> BalanceOf<T> {
+        let now = frame_system::Module::<T>::block_number();
+        let elapsed = now.saturating_sub(usage.updated_at);
+        if elapsed >= window {
+            return Zero::zero();
+        }
+        let released = Perbill::from_rational_approximation(elapsed, window) * cap;
+        usage.amount.saturating_sub(released)
+    }
+}
+
+// binding
+impl<T: Trait> Module<T> {
+    pub fn verify_address_ownership(
+        chain: Chain,
+        who: &T::AccountId,
+        address: &[u8],
+        proof: &AddressOwnershipProof,
+    ) -> DispatchResult {
+        match chain {
+            Chain::Bitcoin => T::Bitcoin::verify_address_ownership(who, address, &proof.signature),
+            Chain::Polkadot => {
+                T::Polkadot::verify_address_ownership(who, address, &proof.signature)
+            }
+            _ => Err(Error::<T>::NotSupportedChain.into()),
+        }
+    }
 }
 
 pub fn is_valid_about<T: Trait>(about: &[u8]) -> DispatchResult {
@@ -352,6 +666,17 @@ impl<T: Trait> Module<T> {
         hot_entity: Vec<u8>,
         cold_entity: Vec<u8>,
     ) -> DispatchResult {
+        let props = Self::check_trustee_props(chain, about, hot_entity, cold_entity)?;
+        Self::set_trustee_props(who, chain, props);
+        Ok(())
+    }
+
+    fn check_trustee_props(
+        chain: Chain,
+        about: Text,
+        hot_entity: Vec<u8>,
+        cold_entity: Vec<u8>,
+    ) -> Result<GenericTrusteeIntentionProps, DispatchError> {
         is_valid_about::<T>(&about)?;
 
         let (hot, cold) = match chain {
@@ -360,18 +685,36 @@ impl<T: Trait> Module<T> {
                 let cold = T::BitcoinTrustee::check_trustee_entity(&cold_entity)?;
                 (hot.into(), cold.into())
             }
+            Chain::Ethereum => {
+                let hot = T::EthereumTrustee::check_trustee_entity(&hot_entity)?;
+                let cold = T::EthereumTrustee::check_trustee_entity(&cold_entity)?;
+                (hot.into(), cold.into())
+            }
+            Chain::Polkadot => {
+                let hot = T::PolkadotTrustee::check_trustee_entity(&hot_entity)?;
+                let cold = T::PolkadotTrustee::check_trustee_entity(&cold_entity)?;
+                (hot.into(), cold.into())
+            }
+            Chain::Local => {
+                let hot = T::LocalTrustee::check_trustee_entity(&hot_entity)?;
+                let cold = T::LocalTrustee::check_trustee_entity(&cold_entity)?;
+                (hot.into(), cold.into())
+            }
             _ => return Err(Error::<T>::NotSupportedChain.into()),
         };
 
-        let props = GenericTrusteeIntentionProps(TrusteeIntentionProps::<Vec<u8>> {
+        Ok(GenericTrusteeIntentionProps(TrusteeIntentionProps::<
+            Vec<u8>,
+        > {
             about,
             hot_entity: hot,
             cold_entity: cold,
-        });
+        }))
+    }
 
+    fn set_trustee_props(who: T::AccountId, chain: Chain, props: GenericTrusteeIntentionProps) {
         TrusteeIntentionPropertiesOf::<T>::insert(&who, chain, props.clone());
         Self::deposit_event(Event::<T>::SetTrusteeProps(who, chain, props));
-        Ok(())
     }
 
     pub fn try_generate_session_info(
@@ -415,6 +758,53 @@ impl<T: Trait> Module<T> {
 
                 session_info.into()
             }
+            Chain::Ethereum => {
+                let props = props
+                    .into_iter()
+                    .map(|(id, prop)| {
+                        (
+                            id,
+                            TrusteeIntentionProps::<_>::try_from(prop)
+                                .expect("must decode succss from storage data"),
+                        )
+                    })
+                    .collect();
+                let session_info =
+                    T::EthereumTrustee::generate_trustee_session_info(props, config)?;
+
+                session_info.into()
+            }
+            Chain::Polkadot => {
+                let props = props
+                    .into_iter()
+                    .map(|(id, prop)| {
+                        (
+                            id,
+                            TrusteeIntentionProps::<_>::try_from(prop)
+                                .expect("must decode succss from storage data"),
+                        )
+                    })
+                    .collect();
+                let session_info =
+                    T::PolkadotTrustee::generate_trustee_session_info(props, config)?;
+
+                session_info.into()
+            }
+            Chain::Local => {
+                let props = props
+                    .into_iter()
+                    .map(|(id, prop)| {
+                        (
+                            id,
+                            TrusteeIntentionProps::<_>::try_from(prop)
+                                .expect("must decode succss from storage data"),
+                        )
+                    })
+                    .collect();
+                let session_info = T::LocalTrustee::generate_trustee_session_info(props, config)?;
+
+                session_info.into()
+            }
             _ => return Err(Error::<T>::NotSupportedChain.into()),
         };
         Ok(info)
@@ -426,7 +816,117 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         let info = Self::try_generate_session_info(chain, new_trustees)?;
         let multi_addr = Self::generate_multisig_addr(chain, &info)?;
+        let session_number = Self::apply_trustee_session(chain, info, multi_addr);
+        // the assets would be swept to the new trustee session, which supersedes the key rotation
+        if KeyRotationOf::<T>::take(chain).is_some() {
+            Self::deposit_event(Event::<T>::KeyRotationCanceled(chain));
+        }
+        // the genesis session has no previous assets to sweep, and only the gateways reporting
+        // the relayed `TrusteeTransition` tx are tracked
+        if session_number > 0 && matches!(chain, Chain::Bitcoin | Chain::Ethereum | Chain::Polkadot)
+        {
+            PendingTransitionOf::insert(chain, session_number);
+        }
+        Ok(())
+    }
+
+    /// Elect the top validators by the total nomination who registered the trustee intention
+    /// as the new trustees.
+    ///
+    /// The election is skipped if the trustees are unchanged, the gateway of the chain is
+    /// disabled, or a key rotation, a withdrawal proposal or the sweep of the previous trustee
+    /// transition is in progress. In the last case the election is postponed to the next
+    /// election era.
+    fn elect_trustees(chain: Chain) -> DispatchResult {
+        if !Self::is_gateway_enabled(chain) {
+            warn!(
+                "[elect_trustees] Skip the election since the gateway is disabled, chain:{:?}",
+                chain
+            );
+            return Ok(());
+        }
+        if Self::has_processing_withdrawals(chain) {
+            // the proposal is signed by the current trustees, thus wait for its settlement
+            warn!(
+                "[elect_trustees] Skip the election since a withdrawal proposal is in progress, chain:{:?}",
+                chain
+            );
+            return Ok(());
+        }
+        if Self::key_rotation_of(chain).is_some() {
+            warn!(
+                "[elect_trustees] Skip the election since a key rotation is in progress, chain:{:?}",
+                chain
+            );
+            return Ok(());
+        }
+        if let Some(number) = Self::pending_transition_of(chain) {
+            // the assets must be swept to the last elected trustees before electing the next ones
+            warn!(
+                "[elect_trustees] Skip the election since the assets are not swept to the trustee session:{}, chain:{:?}",
+                number, chain
+            );
+            return Ok(());
+        }
 
+        let config = Self::trustee_info_config_of(chain);
+        let candidates = T::Validator::sorted_validators()
+            .into_iter()
+            .filter(|who| Self::trustee_intention_props_of(who, chain).is_some())
+            .take(config.max_trustee_count as usize)
+            .collect::<Vec<_>>();
+        if candidates.len() < config.min_trustee_count as usize {
+            warn!(
+                "[elect_trustees] Not enough trustee candidates, chain:{:?}, candidates:{:?}, min:{}",
+                chain, candidates, config.min_trustee_count
+            );
+            return Err(Error::<T>::NotEnoughTrusteeCandidates.into());
+        }
+
+        if let Ok(info) = Self::current_trustee_session_info(chain) {
+            let mut current = info.0.trustee_list;
+            let mut elected = candidates.clone();
+            current.sort();
+            elected.sort();
+            if current == elected {
+                return Ok(());
+            }
+        }
+
+        info!(
+            "[elect_trustees] Try to transition trustees, chain:{:?}, new_trustees:{:?}",
+            chain, candidates
+        );
+        Self::transition_trustee_session_impl(chain, candidates)
+    }
+
+    /// Returns true if the gateway of `chain` is supported by the runtime and its native asset
+    /// is online.
+    fn is_gateway_enabled(chain: Chain) -> bool {
+        let asset_id = match chain {
+            Chain::Bitcoin => T::Bitcoin::ASSET_ID,
+            Chain::Ethereum => T::Ethereum::ASSET_ID,
+            Chain::Polkadot => T::Polkadot::ASSET_ID,
+            Chain::Local => T::Local::ASSET_ID,
+            _ => return false,
+        };
+        xpallet_assets_registrar::Module::<T>::is_online(&asset_id)
+            && xpallet_assets_registrar::Module::<T>::chain_of(&asset_id).ok() == Some(chain)
+    }
+
+    /// Returns true if any withdrawal of `chain` is in a proposal signed by the trustees.
+    fn has_processing_withdrawals(chain: Chain) -> bool {
+        xpallet_gateway_records::Module::<T>::withdrawals_list_by_chain(chain)
+            .values()
+            .any(|withdrawal| withdrawal.state == WithdrawalState::Processing)
+    }
+
+    /// Store the new trustee session, return the session number.
+    fn apply_trustee_session(
+        chain: Chain,
+        info: GenericTrusteeSessionInfo<T::AccountId>,
+        multi_addr: T::AccountId,
+    ) -> u32 {
         let session_number = Self::trustee_session_info_len(chain);
         // FIXME: rethink about the overflow case.
         let next_number = session_number.checked_add(1).unwrap_or(0u32);
@@ -436,7 +936,16 @@ impl<T: Trait> Module<T> {
         TrusteeMultiSigAddr::<T>::insert(chain, multi_addr);
 
         Self::deposit_event(Event::<T>::TrusteeSetChanged(chain, session_number, info));
-        Ok(())
+        session_number
+    }
+
+    fn current_trustee_session_info(
+        chain: Chain,
+    ) -> Result<GenericTrusteeSessionInfo<T::AccountId>, DispatchError> {
+        Self::trustee_session_info_len(chain)
+            .checked_sub(1)
+            .and_then(|number| Self::trustee_session_info_of(chain, number))
+            .ok_or_else(|| Error::<T>::InvalidTrusteeSession.into())
     }
 
     pub fn generate_multisig_addr(
@@ -457,12 +966,81 @@ impl<T: Trait> Module<T> {
         Ok(multi_addr)
     }
 
+    /// Save the key rotation, generate the new trustee session if it's approved by `threshold`
+    /// trustees.
+    fn try_apply_key_rotation(
+        chain: Chain,
+        mut rotation: KeyRotation<T::AccountId>,
+        threshold: u16,
+    ) -> DispatchResult {
+        if rotation.approvals.len() < threshold as usize {
+            KeyRotationOf::<T>::insert(chain, rotation);
+            return Ok(());
+        }
+
+        let trustees = Self::current_trustee_session_info(chain)?.0.trustee_list;
+        Self::set_trustee_props(rotation.trustee.clone(), chain, rotation.props.clone());
+        let info = Self::try_generate_session_info(chain, trustees)?;
+        // the trustee accounts are unchanged, so is the multisig account.
+        let multi_addr =
+            T::DetermineMultisigAddress::calc_multisig(&info.0.trustee_list, info.0.threshold);
+        let session_number = Self::apply_trustee_session(chain, info, multi_addr);
+
+        info!(
+            "[key_rotation] Generate new trustee session:{} for the key rotation of {:?}, chain:{:?}",
+            session_number, rotation.trustee, chain
+        );
+        rotation.status = KeyRotationStatus::Sweeping(session_number);
+        KeyRotationOf::<T>::insert(chain, rotation);
+        Self::deposit_event(Event::<T>::KeyRotationSweeping(chain, session_number));
+        Ok(())
+    }
+
+    /// Finish the key rotation which is waiting for the `TrusteeTransition` tx.
+    fn finish_key_rotation(chain: Chain) {
+        if let Some(KeyRotation {
+            status: KeyRotationStatus::Sweeping(session_number),
+            ..
+        }) = Self::key_rotation_of(chain)
+        {
+            KeyRotationOf::<T>::remove(chain);
+            Self::deposit_event(Event::<T>::KeyRotationFinished(chain, session_number));
+        }
+    }
+
+    /// Finish the trustee transition which is waiting for the `TrusteeTransition` tx.
+    fn finish_trustee_transition(chain: Chain) {
+        if let Some(session_number) = PendingTransitionOf::take(chain) {
+            Self::deposit_event(Event::<T>::TrusteeTransitionFinished(chain, session_number));
+        }
+    }
+
     fn set_referral_binding(chain: Chain, who: T::AccountId, referral: T::AccountId) {
         ReferralBindingOf::<T>::insert(&who, &chain, referral.clone());
         Self::deposit_event(Event::<T>::ReferralBinded(who, chain, referral))
     }
 }
 
+impl<T: Trait> OnNewEra for Module<T> {
+    fn on_new_era(era_index: u32) {
+        let interval = Self::trustee_election_interval();
+        if interval == 0 || era_index % interval != 0 {
+            return;
+        }
+        let chains = TrusteeInfoConfigOf::iter()
+            .map(|(chain, _)| chain)
+            .collect::<Vec<_>>();
+        for chain in chains {
+            if let Err(err) = Self::elect_trustees(chain) {
+                error!(
+                    "[on_new_era] Trustee election failed, era:{}, chain:{:?}, err:{:?}",
+                    era_index, chain, err
+                );
+            }
+        }
+    }
+}
+
 impl<T: Trait> Module<T> {
     pub fn trustee_multisigs() -> BTreeMap<Chain, T::AccountId> {
         TrusteeMultiSigAddr::<T>::iter().collect()
@@ -6,7 +6,7 @@ use codec::{Decode, Encode};
 use frame_support::traits::UnixTime;
 use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
 use frame_system::EnsureSignedBy;
-use sp_core::{crypto::UncheckedInto, H256};
+use sp_core::{crypto::UncheckedInto, ed25519, H256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
     testing::Header,
@@ -15,14 +15,17 @@ use sp_runtime::{
 };
 
 use chainx_primitives::AssetId;
-pub use xp_protocol::{X_BTC, X_ETH};
+pub use xp_protocol::{X_BTC, X_DOT, X_ETH, X_LOCAL};
 use xpallet_assets::{AssetRestrictions, BalanceOf, ChainT, WithdrawalLimit};
 use xpallet_assets_registrar::{AssetInfo, Chain};
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
 use crate::{
-    traits::TrusteeForChain,
+    traits::{AddressOwnership, TrusteeForChain},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeMultisig, BtcTrusteeType},
+    trustees::ethereum::{EthTrusteeAddrInfo, EthTrusteeType},
+    trustees::local::{LocalTrusteeAddrInfo, LocalTrusteeType},
+    trustees::polkadot::{DotTrusteeAddrInfo, DotTrusteeType},
     types::*,
 };
 
@@ -39,6 +42,8 @@ impl_outer_origin! {
 pub struct Test;
 pub type System = frame_system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
+pub type XGatewayCommon = crate::Module<Test>;
+pub type XGatewayCommonErr = crate::Error<Test>;
 
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
@@ -108,6 +113,8 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+    type WithdrawalHistoryRetention = ();
     type WeightInfo = ();
 }
 
@@ -137,6 +144,7 @@ impl xpallet_gateway_bitcoin::Trait for Test {
     type TrusteeOrigin = EnsureSignedBy<BtcTrusteeMultisig<Test>, AccountId>;
     type ReferralBinding = ();
     type AddressBinding = ();
+    type OffenderReporter = ();
     type WeightInfo = ();
 }
 
@@ -147,6 +155,9 @@ impl MultisigAddressFor<AccountId> for MultisigAddr {
         AccountId::decode(&mut &entropy[..]).unwrap_or_default()
     }
 }
+thread_local! {
+    pub static SORTED_VALIDATORS: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+}
 pub struct AlwaysValidator;
 impl Validator<AccountId> for AlwaysValidator {
     fn is_validator(_who: &AccountId) -> bool {
@@ -156,6 +167,10 @@ impl Validator<AccountId> for AlwaysValidator {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn sorted_validators() -> Vec<AccountId> {
+        SORTED_VALIDATORS.with(|v| v.borrow().clone())
+    }
 }
 pub struct MockBitcoin<T: xpallet_gateway_bitcoin::Trait>(sp_std::marker::PhantomData<T>);
 impl<T: xpallet_gateway_bitcoin::Trait> ChainT<BalanceOf<T>> for MockBitcoin<T> {
@@ -173,6 +188,20 @@ impl<T: xpallet_gateway_bitcoin::Trait> ChainT<BalanceOf<T>> for MockBitcoin<T>
         xpallet_gateway_bitcoin::Module::<T>::withdrawal_limit(asset_id)
     }
 }
+impl<T: xpallet_gateway_bitcoin::Trait> AddressOwnership<T::AccountId> for MockBitcoin<T> {
+    fn verify_address_ownership(
+        who: &T::AccountId,
+        address: &[u8],
+        signature: &[u8],
+    ) -> DispatchResult {
+        // the mock signature is `address ++ account`
+        if signature == [address, &who.encode()].concat().as_slice() {
+            Ok(())
+        } else {
+            Err("VerifySignFailed".into())
+        }
+    }
+}
 impl<T: xpallet_gateway_bitcoin::Trait>
     TrusteeForChain<T::AccountId, BtcTrusteeType, BtcTrusteeAddrInfo> for MockBitcoin<T>
 {
@@ -201,12 +230,149 @@ impl<T: xpallet_gateway_bitcoin::Trait>
         })
     }
 }
+pub struct MockEthereum;
+impl ChainT<Balance> for MockEthereum {
+    const ASSET_ID: u32 = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+impl TrusteeForChain<AccountId, EthTrusteeType, EthTrusteeAddrInfo> for MockEthereum {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        let trustee_type =
+            EthTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| "InvalidPublicKey")?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let len = props.len();
+        Ok(TrusteeSessionInfo {
+            trustee_list: props.into_iter().map(|(a, _)| a).collect::<_>(),
+            threshold: len as u16,
+            hot_address: EthTrusteeAddrInfo { signers: vec![] },
+            cold_address: EthTrusteeAddrInfo { signers: vec![] },
+        })
+    }
+}
+pub struct MockPolkadot;
+impl ChainT<Balance> for MockPolkadot {
+    const ASSET_ID: u32 = X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+impl AddressOwnership<AccountId> for MockPolkadot {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("VerifySignFailed".into())
+    }
+}
+impl TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo> for MockPolkadot {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        let trustee_type =
+            DotTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| "InvalidPublicKey")?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        let len = props.len();
+        Ok(TrusteeSessionInfo {
+            trustee_list: props.into_iter().map(|(a, _)| a).collect::<_>(),
+            threshold: len as u16,
+            hot_address: DotTrusteeAddrInfo {
+                addr: Default::default(),
+                signers: vec![],
+            },
+            cold_address: DotTrusteeAddrInfo {
+                addr: Default::default(),
+                signers: vec![],
+            },
+        })
+    }
+}
+/// The mock of the local gateway, the trustee address is derived from the sorted hot or cold
+/// keys and the threshold.
+pub struct MockLocal;
+impl MockLocal {
+    pub const WITHDRAWAL_FEE: Balance = 10;
+
+    fn trustee_addr_info(keys: Vec<LocalTrusteeType>, threshold: u16) -> LocalTrusteeAddrInfo {
+        let mut signers = keys
+            .into_iter()
+            .map(|key| ed25519::Public::from_slice(&key.0))
+            .collect::<Vec<_>>();
+        signers.sort();
+        LocalTrusteeAddrInfo {
+            addr: H256((b"xlocal", &signers, threshold).using_encoded(blake2_256)),
+            signers,
+        }
+    }
+}
+impl ChainT<Balance> for MockLocal {
+    const ASSET_ID: u32 = X_LOCAL;
+
+    fn chain() -> Chain {
+        Chain::Local
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        if addr.is_empty() {
+            return Err("InvalidAddress".into());
+        }
+        Ok(())
+    }
+
+    fn withdrawal_limit(_: &AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError> {
+        Ok(WithdrawalLimit {
+            minimal_withdrawal: Self::WITHDRAWAL_FEE * 3 / 2,
+            fee: Self::WITHDRAWAL_FEE,
+        })
+    }
+}
+impl TrusteeForChain<AccountId, LocalTrusteeType, LocalTrusteeAddrInfo> for MockLocal {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<LocalTrusteeType, DispatchError> {
+        let trustee_type =
+            LocalTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| "InvalidPublicKey")?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(AccountId, TrusteeIntentionProps<LocalTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, LocalTrusteeAddrInfo>, DispatchError> {
+        let threshold = props.len() as u16;
+        let (trustee_list, props): (Vec<_>, Vec<_>) = props.into_iter().unzip();
+        let (hot_keys, cold_keys): (Vec<_>, Vec<_>) = props
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+        Ok(TrusteeSessionInfo {
+            trustee_list,
+            threshold,
+            hot_address: Self::trustee_addr_info(hot_keys, threshold),
+            cold_address: Self::trustee_addr_info(cold_keys, threshold),
+        })
+    }
+}
 impl crate::Trait for Test {
     type Event = ();
     type Validator = AlwaysValidator;
     type DetermineMultisigAddress = MultisigAddr;
     type Bitcoin = MockBitcoin<Test>;
     type BitcoinTrustee = MockBitcoin<Test>;
+    type Ethereum = MockEthereum;
+    type EthereumTrustee = MockEthereum;
+    type Polkadot = MockPolkadot;
+    type PolkadotTrustee = MockPolkadot;
+    type Local = MockLocal;
+    type LocalTrustee = MockLocal;
     type WeightInfo = ();
 }
 
@@ -223,7 +389,11 @@ impl ExtBuilder {
             .unwrap();
 
         let btc_assets = btc();
-        let assets = vec![(btc_assets.0, btc_assets.1, btc_assets.2, true, true)];
+        let local_assets = local();
+        let assets = vec![
+            (btc_assets.0, btc_assets.1, btc_assets.2, true, true),
+            (local_assets.0, local_assets.1, local_assets.2, true, true),
+        ];
 
         let mut init_assets = vec![];
         let mut assets_restrictions = vec![];
@@ -268,6 +438,21 @@ fn btc() -> (AssetId, AssetInfo, AssetRestrictions) {
     )
 }
 
+fn local() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_LOCAL,
+        AssetInfo::new::<Test>(
+            b"X-LOCAL".to_vec(),
+            b"X-LOCAL".to_vec(),
+            Chain::Local,
+            xp_protocol::LOCAL_DECIMALS,
+            b"ChainX's dummy local chain asset".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
 fn trustees() -> Vec<(
     Chain,
     TrusteeInfoConfig,
@@ -309,5 +494,22 @@ fn trustees() -> Vec<(
         min_trustee_count: 3,
         max_trustee_count: 15,
     };
-    vec![(Chain::Bitcoin, btc_config, btc_trustees)]
+    let local_trustees = (1..=3)
+        .map(|i: u8| {
+            (
+                H256::repeat_byte(i).unchecked_into(),
+                b"".to_vec(),
+                vec![i; 32],
+                vec![i + 0x10; 32],
+            )
+        })
+        .collect();
+    let local_config = TrusteeInfoConfig {
+        min_trustee_count: 3,
+        max_trustee_count: 15,
+    };
+    vec![
+        (Chain::Bitcoin, btc_config, btc_trustees),
+        (Chain::Local, local_config, local_trustees),
+    ]
 }
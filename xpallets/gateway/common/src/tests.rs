@@ -1,8 +1,573 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use crate::mock::ExtBuilder;
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_core::{crypto::UncheckedInto, H256};
+
+use xpallet_assets::Chain;
+
+use xpallet_support::traits::OnNewEra;
+
+use crate::mock::{
+    AccountId, ExtBuilder, MockLocal, System, Test, XGatewayCommon, XGatewayCommonErr,
+    SORTED_VALIDATORS, X_BTC, X_LOCAL,
+};
+use crate::traits::{AddressBinding, TrusteeSession};
+use crate::trustees::bitcoin::BtcTrusteeSessionManager;
+use crate::types::{AddressOwnershipProof, KeyRotationStatus, TrusteeInfoConfig, WithdrawalQuota};
+
+fn account(byte: u8) -> AccountId {
+    H256::repeat_byte(byte).unchecked_into()
+}
+
+fn bind(address: &[u8], who: AccountId) {
+    <XGatewayCommon as AddressBinding<_, _>>::update_binding(Chain::Bitcoin, address.to_vec(), who);
+}
+
+fn bound_to(address: &[u8]) -> Option<AccountId> {
+    XGatewayCommon::address_binding_of(Chain::Bitcoin, address.to_vec())
+}
+
+fn bound_addrs(who: &AccountId) -> Vec<Vec<u8>> {
+    XGatewayCommon::bound_addrs(who)
+        .remove(&Chain::Bitcoin)
+        .unwrap_or_default()
+}
 
 #[test]
 fn base() {
     ExtBuilder::default().build().execute_with(|| {})
 }
+
+#[test]
+fn test_rebind_address() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (alice, bob) = (account(1), account(2));
+        let (addr1, addr2) = (b"addr1".to_vec(), b"addr2".to_vec());
+
+        bind(&addr1, alice.clone());
+        bind(&addr2, alice.clone());
+        // bind again would not duplicate the address
+        bind(&addr1, alice.clone());
+        assert_eq!(bound_addrs(&alice), vec![addr1.clone(), addr2.clone()]);
+
+        // rebind to bob
+        bind(&addr1, bob.clone());
+        assert_eq!(bound_to(&addr1), Some(bob.clone()));
+        assert_eq!(bound_addrs(&alice), vec![addr2.clone()]);
+        assert_eq!(bound_addrs(&bob), vec![addr1.clone()]);
+
+        // the last address of alice is rebound to bob, nothing left for alice
+        bind(&addr2, bob.clone());
+        assert!(XGatewayCommon::bound_addrs(&alice).is_empty());
+        assert_eq!(bound_addrs(&bob), vec![addr1, addr2]);
+    })
+}
+
+#[test]
+fn test_unbind_address() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (alice, bob) = (account(1), account(2));
+        let addr = b"addr".to_vec();
+        let unbind = |who: AccountId, proof: Option<AddressOwnershipProof>| {
+            XGatewayCommon::unbind_address(
+                RawOrigin::Signed(who).into(),
+                Chain::Bitcoin,
+                addr.clone(),
+                proof,
+            )
+        };
+
+        assert_noop!(unbind(alice.clone(), None), XGatewayCommonErr::NotBound);
+
+        // unbind by the bound account
+        bind(&addr, alice.clone());
+        assert_noop!(
+            unbind(bob.clone(), None),
+            XGatewayCommonErr::NotAddressOwner
+        );
+        assert_ok!(unbind(alice.clone(), None));
+        assert_eq!(bound_to(&addr), None);
+        assert!(XGatewayCommon::bound_addrs(&alice).is_empty());
+
+        // unbind by the owner of the address
+        bind(&addr, alice.clone());
+        let proof = AddressOwnershipProof {
+            signature: [addr.clone(), bob.encode()].concat(),
+        };
+        let bad_proof = AddressOwnershipProof {
+            signature: [addr.clone(), alice.encode()].concat(),
+        };
+        assert_noop!(unbind(bob.clone(), Some(bad_proof)), "VerifySignFailed");
+        assert_ok!(unbind(bob.clone(), Some(proof)));
+        assert_eq!(bound_to(&addr), None);
+        assert!(XGatewayCommon::bound_addrs(&alice).is_empty());
+        assert!(XGatewayCommon::bound_addrs(&bob).is_empty());
+
+        // bind the address again after unbinding
+        bind(&addr, bob.clone());
+        assert_eq!(bound_to(&addr), Some(bob.clone()));
+        assert_eq!(bound_addrs(&bob), vec![addr.clone()]);
+    })
+}
+
+#[test]
+fn test_withdrawal_quota() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (alice, bob) = (account(1), account(2));
+        for who in &[&alice, &bob] {
+            assert_ok!(xpallet_gateway_records::Module::<Test>::deposit(
+                who, X_BTC, 1000
+            ));
+        }
+        let withdraw = |who: AccountId, value: u128| {
+            XGatewayCommon::withdraw(
+                RawOrigin::Signed(who).into(),
+                X_BTC,
+                value,
+                b"addr".to_vec(),
+                b"".to_vec().into(),
+            )
+        };
+        System::set_block_number(1);
+
+        // no limit by default
+        assert_ok!(withdraw(alice.clone(), 100));
+
+        let quota = WithdrawalQuota {
+            window: 100,
+            per_account: 100,
+            global: 150,
+        };
+        assert_noop!(
+            XGatewayCommon::set_withdrawal_quota(
+                RawOrigin::Signed(alice.clone()).into(),
+                X_BTC,
+                Some(quota.clone())
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XGatewayCommon::set_withdrawal_quota(
+                RawOrigin::Root.into(),
+                X_BTC,
+                Some(WithdrawalQuota {
+                    window: 0,
+                    ..quota.clone()
+                })
+            ),
+            XGatewayCommonErr::InvalidWithdrawalQuota
+        );
+        assert_ok!(XGatewayCommon::set_withdrawal_quota(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(quota.clone())
+        ));
+
+        assert_ok!(withdraw(alice.clone(), 60));
+        assert_noop!(
+            withdraw(alice.clone(), 41),
+            XGatewayCommonErr::ExceedAccountWithdrawalQuota
+        );
+        assert_ok!(withdraw(alice.clone(), 40));
+        assert_noop!(
+            withdraw(bob.clone(), 51),
+            XGatewayCommonErr::ExceedGlobalWithdrawalQuota
+        );
+        assert_ok!(withdraw(bob.clone(), 50));
+
+        // the used quota is released linearly over the window
+        System::set_block_number(51);
+        assert_noop!(
+            withdraw(alice.clone(), 51),
+            XGatewayCommonErr::ExceedAccountWithdrawalQuota
+        );
+        assert_ok!(withdraw(alice.clone(), 50));
+        assert_eq!(
+            XGatewayCommon::account_withdrawal_usage_of(X_BTC, &alice).amount,
+            100
+        );
+
+        // fully released after the window
+        System::set_block_number(151);
+        assert_ok!(withdraw(alice.clone(), 100));
+
+        // raise the quota by root
+        assert_ok!(XGatewayCommon::set_withdrawal_quota(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalQuota {
+                per_account: 200,
+                global: 300,
+                ..quota
+            })
+        ));
+        assert_ok!(withdraw(alice.clone(), 100));
+
+        // remove the limit
+        assert_ok!(XGatewayCommon::set_withdrawal_quota(
+            RawOrigin::Root.into(),
+            X_BTC,
+            None
+        ));
+        assert_ok!(withdraw(alice, 500));
+    })
+}
+
+#[test]
+fn test_key_rotation() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        let trustees = vec![account(1), account(2), account(3)];
+        let hot = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap();
+        let cold =
+            hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+                .unwrap();
+        let propose = |who: AccountId| {
+            XGatewayCommon::propose_key_rotation(
+                RawOrigin::Signed(who).into(),
+                Chain::Bitcoin,
+                hot.clone(),
+                cold.clone(),
+            )
+        };
+        let approve = |who: AccountId| {
+            XGatewayCommon::approve_key_rotation(RawOrigin::Signed(who).into(), Chain::Bitcoin)
+        };
+        let status = || XGatewayCommon::key_rotation_of(Chain::Bitcoin).map(|r| r.status);
+
+        assert_noop!(
+            propose(trustees[0].clone()),
+            XGatewayCommonErr::InvalidTrusteeSession
+        );
+        assert_ok!(XGatewayCommon::transition_trustee_session(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            trustees.clone()
+        ));
+        let multisig = XGatewayCommon::trustee_multisig_addr(Chain::Bitcoin);
+
+        assert_noop!(propose(account(4)), XGatewayCommonErr::NotTrustee);
+        assert_ok!(propose(trustees[0].clone()));
+        assert_eq!(status(), Some(KeyRotationStatus::Proposed));
+        assert_noop!(
+            propose(trustees[1].clone()),
+            XGatewayCommonErr::KeyRotationInProgress
+        );
+        assert_noop!(
+            approve(trustees[0].clone()),
+            XGatewayCommonErr::DuplicatedApproval
+        );
+        assert_noop!(approve(account(4)), XGatewayCommonErr::NotTrustee);
+
+        // the mock threshold is the count of trustees
+        assert_ok!(approve(trustees[1].clone()));
+        assert_eq!(status(), Some(KeyRotationStatus::Proposed));
+        assert_eq!(XGatewayCommon::trustee_session_info_len(Chain::Bitcoin), 1);
+        assert_ok!(approve(trustees[2].clone()));
+        assert_eq!(status(), Some(KeyRotationStatus::Sweeping(1)));
+        assert_noop!(
+            approve(trustees[2].clone()),
+            XGatewayCommonErr::NoProposedKeyRotation
+        );
+
+        // a new session with the same trustees and the new keys
+        assert_eq!(XGatewayCommon::trustee_session_info_len(Chain::Bitcoin), 2);
+        let info = XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, 1).unwrap();
+        assert_eq!(info.0.trustee_list, trustees);
+        assert_eq!(
+            XGatewayCommon::trustee_multisig_addr(Chain::Bitcoin),
+            multisig
+        );
+        let props =
+            XGatewayCommon::trustee_intention_props_of(&trustees[0], Chain::Bitcoin).unwrap();
+        assert_eq!(props.0.hot_entity, hot);
+        assert_eq!(props.0.cold_entity, cold);
+
+        // finished after the `TrusteeTransition` tx to the new session is relayed
+        let new_hot = <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_session(1)
+            .unwrap()
+            .hot_address;
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |trustee| trustee.addr == b"not the new trustee address".to_vec(),
+        );
+        assert_eq!(status(), Some(KeyRotationStatus::Sweeping(1)));
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |trustee| *trustee == new_hot,
+        );
+        assert_eq!(status(), None);
+
+        // cancel by root
+        assert_ok!(propose(trustees[1].clone()));
+        assert_noop!(
+            XGatewayCommon::cancel_key_rotation(
+                RawOrigin::Signed(trustees[1].clone()).into(),
+                Chain::Bitcoin
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XGatewayCommon::cancel_key_rotation(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin
+        ));
+        assert_eq!(status(), None);
+        assert_noop!(
+            XGatewayCommon::cancel_key_rotation(RawOrigin::Root.into(), Chain::Bitcoin),
+            XGatewayCommonErr::NoProposedKeyRotation
+        );
+
+        // superseded by a new trustee session
+        assert_ok!(propose(trustees[1].clone()));
+        assert_ok!(XGatewayCommon::setup_trustee(
+            RawOrigin::Signed(account(4)).into(),
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")
+                .unwrap(),
+            hex::decode("02e493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd13")
+                .unwrap(),
+        ));
+        assert_ok!(XGatewayCommon::transition_trustee_session(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            vec![account(1), account(2), account(3), account(4)]
+        ));
+        assert_eq!(status(), None);
+        assert_noop!(
+            propose(trustees[0].clone()),
+            XGatewayCommonErr::TrusteeTransitionInProgress
+        );
+    })
+}
+
+#[test]
+fn test_trustee_election() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        let set_validators = |validators: &[u8]| {
+            SORTED_VALIDATORS
+                .with(|v| *v.borrow_mut() = validators.iter().map(|i| account(*i)).collect())
+        };
+        let session_len = || XGatewayCommon::trustee_session_info_len(Chain::Bitcoin);
+        let current_trustees = || {
+            XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, session_len() - 1)
+                .unwrap()
+                .0
+                .trustee_list
+        };
+        // account 1, 2, 3 are registered as trustees in genesis
+        assert_ok!(XGatewayCommon::setup_trustee(
+            RawOrigin::Signed(account(4)).into(),
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+            hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+                .unwrap(),
+        ));
+        set_validators(&[4, 5, 1, 2, 3]);
+
+        // disabled by default
+        XGatewayCommon::on_new_era(2);
+        assert_eq!(session_len(), 0);
+
+        assert_noop!(
+            XGatewayCommon::set_trustee_election_interval(RawOrigin::Signed(account(1)).into(), 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XGatewayCommon::set_trustee_election_interval(
+            RawOrigin::Root.into(),
+            2
+        ));
+        XGatewayCommon::on_new_era(3);
+        assert_eq!(session_len(), 0);
+
+        // account 5 has not registered as a trustee
+        XGatewayCommon::on_new_era(4);
+        assert_eq!(session_len(), 1);
+        assert_eq!(
+            current_trustees(),
+            vec![account(4), account(1), account(2), account(3)]
+        );
+
+        // respect the max trustee count
+        assert_ok!(XGatewayCommon::set_trustee_info_config(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            TrusteeInfoConfig {
+                min_trustee_count: 3,
+                max_trustee_count: 3,
+            }
+        ));
+        set_validators(&[3, 4, 5, 1, 2]);
+        XGatewayCommon::on_new_era(6);
+        assert_eq!(session_len(), 2);
+        assert_eq!(current_trustees(), vec![account(3), account(4), account(1)]);
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |_| true,
+        );
+
+        // no transition if the trustees are unchanged
+        set_validators(&[1, 3, 4, 2]);
+        XGatewayCommon::on_new_era(8);
+        assert_eq!(session_len(), 2);
+
+        // respect the min trustee count
+        set_validators(&[2, 5]);
+        XGatewayCommon::on_new_era(10);
+        assert_eq!(session_len(), 2);
+
+        // postponed while a withdrawal proposal is in progress
+        type XRecords = xpallet_gateway_records::Module<Test>;
+        set_validators(&[1, 2, 3]);
+        assert_ok!(XRecords::deposit(&account(1), X_BTC, 1000));
+        assert_ok!(XRecords::withdraw(
+            &account(1),
+            X_BTC,
+            100,
+            b"addr".to_vec(),
+            b"".to_vec().into()
+        ));
+        let id = XRecords::id() - 1;
+        assert_ok!(XRecords::process_withdrawal(id, Chain::Bitcoin));
+        XGatewayCommon::on_new_era(12);
+        assert_eq!(session_len(), 2);
+        assert_ok!(XRecords::finish_withdrawal(id, Some(Chain::Bitcoin)));
+        XGatewayCommon::on_new_era(14);
+        assert_eq!(session_len(), 3);
+        assert_eq!(current_trustees(), vec![account(1), account(2), account(3)]);
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |_| true,
+        );
+
+        // skip the disabled gateway
+        assert_ok!(xpallet_assets_registrar::Module::<Test>::deregister(
+            RawOrigin::Root.into(),
+            X_BTC
+        ));
+        set_validators(&[2, 3, 4]);
+        XGatewayCommon::on_new_era(16);
+        assert_eq!(session_len(), 3);
+    })
+}
+
+#[test]
+fn test_trustee_election_waits_for_transition() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        let set_validators = |validators: &[u8]| {
+            SORTED_VALIDATORS
+                .with(|v| *v.borrow_mut() = validators.iter().map(|i| account(*i)).collect())
+        };
+        let session_len = || XGatewayCommon::trustee_session_info_len(Chain::Bitcoin);
+        let pending = || XGatewayCommon::pending_transition_of(Chain::Bitcoin);
+        assert_ok!(XGatewayCommon::setup_trustee(
+            RawOrigin::Signed(account(4)).into(),
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+            hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+                .unwrap(),
+        ));
+        assert_ok!(XGatewayCommon::set_trustee_election_interval(
+            RawOrigin::Root.into(),
+            1
+        ));
+
+        // the first session has no previous assets to sweep
+        set_validators(&[1, 2, 3]);
+        XGatewayCommon::on_new_era(1);
+        assert_eq!(session_len(), 1);
+        assert_eq!(pending(), None);
+
+        set_validators(&[4, 1, 2, 3]);
+        XGatewayCommon::on_new_era(2);
+        assert_eq!(session_len(), 2);
+        assert_eq!(pending(), Some(1));
+
+        // the next eras are skipped until the assets are swept to the session 1
+        set_validators(&[1, 2, 3]);
+        XGatewayCommon::on_new_era(3);
+        assert_eq!(session_len(), 2);
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |_| false,
+        );
+        assert_eq!(pending(), Some(1));
+        XGatewayCommon::on_new_era(4);
+        assert_eq!(session_len(), 2);
+
+        <BtcTrusteeSessionManager<Test> as TrusteeSession<_, _>>::trustee_transition_relayed(
+            |_| true,
+        );
+        assert_eq!(pending(), None);
+        XGatewayCommon::on_new_era(5);
+        assert_eq!(session_len(), 3);
+        assert_eq!(pending(), Some(2));
+    })
+}
+
+#[test]
+fn test_local_chain_withdrawal() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        type XRecords = xpallet_gateway_records::Module<Test>;
+        type XAssets = xpallet_assets::Module<Test>;
+        let trustees = vec![account(1), account(2), account(3)];
+        assert_ok!(XGatewayCommon::transition_trustee_session(
+            RawOrigin::Root.into(),
+            Chain::Local,
+            trustees.clone()
+        ));
+        let info = XGatewayCommon::trustee_session_info_of(Chain::Local, 0).unwrap();
+        assert_eq!(info.0.trustee_list, trustees);
+        // the local gateway does not report the sweep of the trustee transition
+        assert_ok!(XGatewayCommon::transition_trustee_session(
+            RawOrigin::Root.into(),
+            Chain::Local,
+            trustees
+        ));
+        assert_eq!(XGatewayCommon::pending_transition_of(Chain::Local), None);
+
+        // deposit
+        let alice = account(4);
+        assert_ok!(XRecords::deposit(&alice, X_LOCAL, 1000));
+        assert_eq!(XAssets::usable_balance(&alice, &X_LOCAL), 1000);
+
+        // withdraw
+        let withdraw = |value: u128, addr: &[u8]| {
+            XGatewayCommon::withdraw(
+                RawOrigin::Signed(alice.clone()).into(),
+                X_LOCAL,
+                value,
+                addr.to_vec(),
+                b"".to_vec().into(),
+            )
+        };
+        assert_noop!(withdraw(100, b""), "InvalidAddress");
+        assert_noop!(
+            withdraw(MockLocal::WITHDRAWAL_FEE, b"local addr"),
+            XGatewayCommonErr::InvalidWithdrawal
+        );
+        assert_ok!(withdraw(100, b"local addr"));
+        assert_eq!(XAssets::usable_balance(&alice, &X_LOCAL), 900);
+        let (id, record) = XRecords::withdrawals_list_by_chain(Chain::Local)
+            .into_iter()
+            .next()
+            .unwrap();
+        assert_eq!(record.addr, b"local addr".to_vec());
+
+        // settled by the trustees of the local chain
+        assert_noop!(
+            XRecords::process_withdrawal(id, Chain::Bitcoin),
+            xpallet_gateway_records::Error::<Test>::UnexpectedChain
+        );
+        assert_ok!(XRecords::process_withdrawal(id, Chain::Local));
+        assert_ok!(XRecords::finish_withdrawal(id, Some(Chain::Local)));
+        assert!(XRecords::withdrawals_list_by_chain(Chain::Local).is_empty());
+        assert_eq!(XAssets::usable_balance(&alice, &X_LOCAL), 900);
+        assert_eq!(XAssets::total_issuance(&X_LOCAL), 900);
+    })
+}
@@ -1,6 +1,6 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::dispatch::DispatchError;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use sp_std::{convert::TryFrom, prelude::Vec};
 
 use chainx_primitives::{AssetId, ReferralId};
@@ -34,6 +34,10 @@ pub trait TrusteeSession<AccountId, TrusteeAddress: BytesLike> {
 
     fn last_trustee_session() -> Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, DispatchError>;
 
+    /// Notify that the `TrusteeTransition` tx of the chain was relayed, `is_receiver` tells
+    /// whether the trustee address received the assets swept by the tx.
+    fn trustee_transition_relayed<F: Fn(&TrusteeAddress) -> bool>(is_receiver: F);
+
     #[cfg(feature = "std")]
     fn genesis_trustee(chain: Chain, init: &[AccountId]);
 }
@@ -55,6 +59,8 @@ impl<AccountId, TrusteeAddress: BytesLike> TrusteeSession<AccountId, TrusteeAddr
         Err("NoTrustee".into())
     }
 
+    fn trustee_transition_relayed<F: Fn(&TrusteeAddress) -> bool>(_: F) {}
+
     #[cfg(feature = "std")]
     fn genesis_trustee(_: Chain, _: &[AccountId]) {}
 }
@@ -71,6 +77,21 @@ impl<AccountId> ReferralBinding<AccountId> for () {
     }
 }
 
+pub trait AddressOwnership<AccountId> {
+    /// Verify that `who` owns the `address` with the `signature` over the account of `who`.
+    fn verify_address_ownership(
+        who: &AccountId,
+        address: &[u8],
+        signature: &[u8],
+    ) -> DispatchResult;
+}
+
+impl<AccountId> AddressOwnership<AccountId> for () {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedOwnershipProof".into())
+    }
+}
+
 pub trait AddressBinding<AccountId, Address: Into<Vec<u8>>> {
     fn update_binding(chain: Chain, address: Address, who: AccountId);
     fn address(chain: Chain, address: Address) -> Option<AccountId>;
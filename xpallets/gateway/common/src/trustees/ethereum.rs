@@ -0,0 +1,130 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::Vec};
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{Chain, ChainT, WithdrawalLimit};
+
+use super::{TrusteeMultisigProvider, TrusteeSessionManager};
+use crate::traits::{ChainProvider, TrusteeForChain};
+use crate::types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+
+pub type EthTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>;
+pub type EthTrusteeIntentionProps = TrusteeIntentionProps<EthTrusteeType>;
+pub type EthTrusteeSessionManager<T> = TrusteeSessionManager<T, EthTrusteeAddrInfo>;
+pub type EthTrusteeMultisig<T> = TrusteeMultisigProvider<T, EthTrusteeType>;
+
+/// The signers of the trustee contract, i.e. the Ethereum addresses of the trustee keys.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthTrusteeAddrInfo {
+    pub signers: Vec<H160>,
+}
+
+impl From<EthTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: EthTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for EthTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+/// The compressed secp256k1 public key (33 bytes) of the trustee.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthTrusteeType(
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] pub Vec<u8>,
+);
+
+impl From<EthTrusteeType> for Vec<u8> {
+    fn from(value: EthTrusteeType) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() == 33 && (value[0] == 2 || value[0] == 3) {
+            Ok(EthTrusteeType(value))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ChainProvider for EthTrusteeType {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+/// The placeholder of Ethereum for the runtimes without the Ethereum gateway, which rejects all
+/// the withdrawals and trustees of Ethereum.
+pub struct EthereumChainDisabled;
+
+impl<Balance: Default> ChainT<Balance> for EthereumChainDisabled {
+    const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn check_addr(_: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+
+    fn withdrawal_limit(_: &AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl<AccountId> TrusteeForChain<AccountId, EthTrusteeType, EthTrusteeAddrInfo>
+    for EthereumChainDisabled
+{
+    fn check_trustee_entity(_: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+#[test]
+fn test_serde_eth_trustee_type() {
+    let pubkey = EthTrusteeType::try_from([&[2u8][..], &[0u8; 32][..]].concat()).unwrap();
+    let ser = serde_json::to_string(&pubkey).unwrap();
+    assert_eq!(
+        ser,
+        "\"0x020000000000000000000000000000000000000000000000000000000000000000\""
+    );
+    let de = serde_json::from_str::<EthTrusteeType>(&ser).unwrap();
+    assert_eq!(de, pubkey);
+
+    assert!(EthTrusteeType::try_from(vec![4u8; 65]).is_err());
+}
@@ -0,0 +1,133 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::{ed25519, H256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::Vec};
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{Chain, ChainT, WithdrawalLimit};
+
+use super::{TrusteeMultisigProvider, TrusteeSessionManager};
+use crate::traits::{ChainProvider, TrusteeForChain};
+use crate::types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+
+pub type LocalTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, LocalTrusteeAddrInfo>;
+pub type LocalTrusteeIntentionProps = TrusteeIntentionProps<LocalTrusteeType>;
+pub type LocalTrusteeSessionManager<T> = TrusteeSessionManager<T, LocalTrusteeAddrInfo>;
+pub type LocalTrusteeMultisig<T> = TrusteeMultisigProvider<T, LocalTrusteeType>;
+
+/// The trustee address of the local chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LocalTrusteeAddrInfo {
+    /// the address, derived from the sorted signers and the threshold
+    pub addr: H256,
+    /// the sorted signers of the address
+    pub signers: Vec<ed25519::Public>,
+}
+
+impl From<LocalTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: LocalTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for LocalTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for LocalTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Local
+    }
+}
+
+/// The ed25519 public key (32 bytes) of the trustee.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LocalTrusteeType(
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] pub Vec<u8>,
+);
+
+impl From<LocalTrusteeType> for Vec<u8> {
+    fn from(value: LocalTrusteeType) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for LocalTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() == 32 {
+            Ok(LocalTrusteeType(value))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ChainProvider for LocalTrusteeType {
+    fn chain() -> Chain {
+        Chain::Local
+    }
+}
+
+/// The placeholder of the local chain for the runtimes without the local gateway, which rejects
+/// all the withdrawals and trustees of the local chain.
+pub struct LocalChainDisabled;
+
+impl<Balance: Default> ChainT<Balance> for LocalChainDisabled {
+    const ASSET_ID: AssetId = xp_protocol::X_LOCAL;
+
+    fn chain() -> Chain {
+        Chain::Local
+    }
+
+    fn check_addr(_: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+
+    fn withdrawal_limit(_: &AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl<AccountId> TrusteeForChain<AccountId, LocalTrusteeType, LocalTrusteeAddrInfo>
+    for LocalChainDisabled
+{
+    fn check_trustee_entity(_: &[u8]) -> Result<LocalTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<LocalTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, LocalTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+#[test]
+fn test_serde_local_trustee_type() {
+    let pubkey = LocalTrusteeType::try_from(vec![1u8; 32]).unwrap();
+    let ser = serde_json::to_string(&pubkey).unwrap();
+    assert_eq!(
+        ser,
+        "\"0x0101010101010101010101010101010101010101010101010101010101010101\""
+    );
+    let de = serde_json::from_str::<LocalTrusteeType>(&ser).unwrap();
+    assert_eq!(de, pubkey);
+
+    assert!(LocalTrusteeType::try_from(vec![1u8; 31]).is_err());
+}
@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 pub mod bitcoin;
+pub mod ethereum;
+pub mod local;
+pub mod polkadot;
 
 use frame_support::{dispatch::DispatchError, traits::Contains};
 use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
@@ -10,7 +13,7 @@ use xpallet_assets::Chain;
 use xpallet_support::traits::MultiSig;
 
 use crate::traits::{BytesLike, ChainProvider, TrusteeSession};
-use crate::types::TrusteeSessionInfo;
+use crate::types::{KeyRotation, KeyRotationStatus, TrusteeSessionInfo};
 use crate::{Error, Module, Trait};
 
 pub struct TrusteeSessionManager<T: Trait, TrusteeAddress>(
@@ -64,6 +67,41 @@ impl<T: Trait, TrusteeAddress: BytesLike + ChainProvider>
         })
     }
 
+    fn trustee_transition_relayed<F: Fn(&TrusteeAddress) -> bool>(is_receiver: F) {
+        let chain = TrusteeAddress::chain();
+        // the transition is finished only if the assets are swept to its new trustee session
+        let is_swept_to = |number: u32| match Self::trustee_session(number) {
+            Ok(info) => is_receiver(&info.hot_address) || is_receiver(&info.cold_address),
+            Err(_) => false,
+        };
+
+        if let Some(number) = Module::<T>::pending_transition_of(chain) {
+            if is_swept_to(number) {
+                Module::<T>::finish_trustee_transition(chain)
+            } else {
+                warn!(
+                    "[trustee_transition_relayed] The assets are not swept to the elected trustee session:{}, chain:{:?}",
+                    number, chain
+                )
+            }
+        }
+
+        if let Some(KeyRotation {
+            status: KeyRotationStatus::Sweeping(number),
+            ..
+        }) = Module::<T>::key_rotation_of(chain)
+        {
+            if is_swept_to(number) {
+                Module::<T>::finish_key_rotation(chain)
+            } else {
+                warn!(
+                    "[trustee_transition_relayed] The assets are not swept to the trustee session:{} of the key rotation, chain:{:?}",
+                    number, chain
+                )
+            }
+        }
+    }
+
     #[cfg(feature = "std")]
     fn genesis_trustee(chain: Chain, trustees: &[T::AccountId]) {
         Module::<T>::transition_trustee_session_impl(chain, trustees.to_vec())
@@ -0,0 +1,138 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_runtime::{AccountId32, RuntimeDebug};
+use sp_std::{convert::TryFrom, prelude::Vec};
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{Chain, ChainT, WithdrawalLimit};
+
+use super::{TrusteeMultisigProvider, TrusteeSessionManager};
+use crate::traits::{AddressOwnership, ChainProvider, TrusteeForChain};
+use crate::types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+
+pub type DotTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>;
+pub type DotTrusteeIntentionProps = TrusteeIntentionProps<DotTrusteeType>;
+pub type DotTrusteeSessionManager<T> = TrusteeSessionManager<T, DotTrusteeAddrInfo>;
+pub type DotTrusteeMultisig<T> = TrusteeMultisigProvider<T, DotTrusteeType>;
+
+/// The multisig account of the trustees on the relay chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotTrusteeAddrInfo {
+    /// the multisig account, derived from the sorted signers and the threshold
+    pub addr: AccountId32,
+    /// the sorted signers of the multisig account
+    pub signers: Vec<AccountId32>,
+}
+
+impl From<DotTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: DotTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for DotTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for DotTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+
+/// The sr25519 public key (32 bytes) of the trustee on the relay chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DotTrusteeType(
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] pub Vec<u8>,
+);
+
+impl From<DotTrusteeType> for Vec<u8> {
+    fn from(value: DotTrusteeType) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for DotTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() == 32 {
+            Ok(DotTrusteeType(value))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ChainProvider for DotTrusteeType {
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+
+/// The placeholder of Polkadot for the runtimes without the Polkadot gateway, which rejects all
+/// the withdrawals, address bindings and trustees of Polkadot.
+pub struct PolkadotChainDisabled;
+
+impl<Balance: Default> ChainT<Balance> for PolkadotChainDisabled {
+    const ASSET_ID: AssetId = xp_protocol::X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+
+    fn check_addr(_: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+
+    fn withdrawal_limit(_: &AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl<AccountId> AddressOwnership<AccountId> for PolkadotChainDisabled {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl<AccountId> TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo>
+    for PolkadotChainDisabled
+{
+    fn check_trustee_entity(_: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+#[test]
+fn test_serde_dot_trustee_type() {
+    let pubkey = DotTrusteeType::try_from(vec![1u8; 32]).unwrap();
+    let ser = serde_json::to_string(&pubkey).unwrap();
+    assert_eq!(
+        ser,
+        "\"0x0101010101010101010101010101010101010101010101010101010101010101\""
+    );
+    let de = serde_json::from_str::<DotTrusteeType>(&ser).unwrap();
+    assert_eq!(de, pubkey);
+
+    assert!(DotTrusteeType::try_from(vec![1u8; 33]).is_err());
+}
@@ -11,6 +11,63 @@ use chainx_primitives::Text;
 
 use crate::traits::BytesLike;
 
+/// The proof of the ownership of an address on the target chain, e.g. the signature of the
+/// "Bitcoin Signed Message".
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AddressOwnershipProof {
+    pub signature: Vec<u8>,
+}
+
+/// The rolling-window withdrawal caps of an asset.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalQuota<Balance, BlockNumber> {
+    /// The length of the rolling window, in blocks.
+    pub window: BlockNumber,
+    /// The max withdrawal of an account within the window.
+    pub per_account: Balance,
+    /// The max withdrawal of all accounts within the window.
+    pub global: Balance,
+}
+
+/// The used withdrawal quota, which is released linearly over the window.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalUsage<Balance, BlockNumber> {
+    /// The used quota at `updated_at`.
+    pub amount: Balance,
+    pub updated_at: BlockNumber,
+}
+
+/// The progress of a trustee key rotation.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum KeyRotationStatus {
+    /// Waiting for the approvals of the current trustees.
+    Proposed,
+    /// The new trustee session (with the given number) was generated, waiting for the
+    /// `TrusteeTransition` tx which sweeps the assets to the new trustee addresses.
+    Sweeping(u32),
+}
+
+/// The key rotation of a trustee, which keeps the trustee set unchanged.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct KeyRotation<AccountId> {
+    /// The trustee who rotates the keys.
+    pub trustee: AccountId,
+    /// The trustee properties with the new keys.
+    pub props: GenericTrusteeIntentionProps,
+    /// The trustees who approved the rotation.
+    pub approvals: Vec<AccountId>,
+    pub status: KeyRotationStatus,
+}
+
 /// The config of trustee info.
 #[derive(PartialEq, Clone, Encode, Decode, Default, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
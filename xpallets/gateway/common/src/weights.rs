@@ -37,15 +37,21 @@ pub trait WeightInfo {
     fn set_withdrawal_state() -> Weight;
     fn set_trustee_info_config() -> Weight;
     fn force_set_referral_binding() -> Weight;
+    fn unbind_address() -> Weight;
+    fn set_withdrawal_quota() -> Weight;
+    fn propose_key_rotation() -> Weight;
+    fn approve_key_rotation() -> Weight;
+    fn cancel_key_rotation() -> Weight;
+    fn set_trustee_election_interval() -> Weight;
 }
 
 /// Weights for xpallet_gateway_common using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn withdraw() -> Weight {
-        (226_245_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(10 as Weight))
-            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+        (239_862_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
     }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
@@ -74,14 +80,40 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn unbind_address() -> Weight {
+        (42_518_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_withdrawal_quota() -> Weight {
+        (7_104_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn propose_key_rotation() -> Weight {
+        (52_637_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn approve_key_rotation() -> Weight {
+        (164_285_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn cancel_key_rotation() -> Weight {
+        (18_406_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_election_interval() -> Weight {
+        (5_871_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn withdraw() -> Weight {
-        (226_245_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+        (239_862_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
     }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
@@ -110,4 +142,30 @@ impl WeightInfo for () {
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn unbind_address() -> Weight {
+        (42_518_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_withdrawal_quota() -> Weight {
+        (7_104_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn propose_key_rotation() -> Weight {
+        (52_637_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn approve_key_rotation() -> Weight {
+        (164_285_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn cancel_key_rotation() -> Weight {
+        (18_406_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_election_interval() -> Weight {
+        (5_871_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
@@ -0,0 +1,153 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::storage::{StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use sp_core::{H160, H256};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
+use xpallet_assets_registrar::{AssetInfo, Module as XAssetsRegistrar};
+use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
+
+use crate::{
+    header::insert_header,
+    proof::single_leaf_proof,
+    receipt::{event_topic, DEPOSIT_EVENT},
+    types::{encode_header, encode_receipt},
+    BestIndex, Call, ConfirmedIndex, EthHeader, EthHeaderIndex, EthLog, MainChain, Module, Trait,
+    TrusteeContract,
+};
+
+const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+fn ensure_eth_asset<T: Trait>() {
+    if !XAssetsRegistrar::<T>::exists(&ASSET_ID) {
+        let info = AssetInfo::new::<T>(
+            b"XETH".to_vec(),
+            b"ChainX Ethereum".to_vec(),
+            Chain::Ethereum,
+            xp_protocol::ETH_DECIMALS,
+            b"ChainX's Cross-chain Ethereum".to_vec(),
+        )
+        .unwrap();
+        XAssetsRegistrar::<T>::register(RawOrigin::Root.into(), ASSET_ID, info, true, false)
+            .unwrap();
+    }
+}
+
+/// Insert a confirmed main chain header with the given receipts root, returns its hash.
+fn prepare_confirmed_header<T: Trait>(number: u64, receipts_root: H256) -> H256 {
+    let raw = encode_header(H256::repeat_byte(1), number, 1_600_000_000, receipts_root);
+    let (hash, header) = EthHeader::decode_rlp(&raw).unwrap();
+    insert_header::<T>(hash, header);
+    MainChain::insert(&hash, true);
+    let index = EthHeaderIndex { hash, number };
+    BestIndex::put(index);
+    ConfirmedIndex::put(index);
+    hash
+}
+
+benchmarks! {
+    _{ }
+
+    push_header {
+        let parent = prepare_confirmed_header::<T>(11_000_000, H256::zero());
+        let raw = encode_header(parent, 11_000_001, 1_600_000_013, H256::zero());
+        let (hash, _) = EthHeader::decode_rlp(&raw).unwrap();
+    }: _(RawOrigin::Root, raw)
+    verify {
+        assert_eq!(Module::<T>::best_index().hash, hash);
+    }
+
+    push_receipt {
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_eth_asset::<T>();
+        let contract = H160::repeat_byte(0x11);
+        TrusteeContract::put(contract);
+
+        let amount = 1_000_000_000_000_000_000u64;
+        let log = EthLog {
+            address: contract,
+            topics: vec![
+                event_topic(DEPOSIT_EVENT),
+                H256::zero(),
+                H256::from_slice(&caller.encode()),
+            ],
+            data: H256::from_low_u64_be(amount).as_bytes().to_vec(),
+        };
+        let tx_index = 0u64;
+        let (root, proof) = single_leaf_proof(&rlp::encode(&tx_index), &encode_receipt(&[log]));
+        let block_hash = prepare_confirmed_header::<T>(11_000_000, root);
+    }: _(RawOrigin::Signed(caller.clone()), block_hash, tx_index, proof)
+    verify {
+        assert!(Module::<T>::processed_receipt((block_hash, tx_index)));
+        let expected: BalanceOf<T> = amount.saturated_into();
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), expected);
+    }
+
+    process_withdrawals {
+        let n in 1 .. 100 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_eth_asset::<T>();
+        let balance: BalanceOf<T> = 1_000_000_000_000_000_000u128.saturated_into();
+        let addr = b"0x7e5f4552091a69125d5dfcb7b8c2659029395bdf".to_vec();
+        let mut ids = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            ids.push(xpallet_gateway_records::Module::<T>::id());
+            XGatewayRecords::<T>::deposit(&caller, ASSET_ID, balance).unwrap();
+            XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, balance, addr.clone(), b"".to_vec().into()).unwrap();
+        }
+    }: _(RawOrigin::Root, ids.clone())
+    verify {
+        for id in ids {
+            assert_eq!(XGatewayRecords::<T>::state_of(id), Some(WithdrawalState::Processing));
+        }
+    }
+
+    set_erc20_asset {
+        let asset_id = 1000;
+        let info = AssetInfo::new::<T>(
+            b"USDT".to_vec(),
+            b"ChainX Tether USD".to_vec(),
+            Chain::Ethereum,
+            6,
+            b"ChainX's Cross-chain ERC-20 USDT".to_vec(),
+        )
+        .unwrap();
+        XAssetsRegistrar::<T>::register(RawOrigin::Root.into(), asset_id, info, true, false).unwrap();
+        let token = H160::repeat_byte(0xda);
+    }: _(RawOrigin::Root, asset_id, Some(token))
+    verify {
+        assert_eq!(Module::<T>::erc20_asset_of(token), Some(asset_id));
+    }
+
+    set_withdrawal_fee {
+        let fee: BalanceOf<T> = 1_000_000_000_000_000u128.saturated_into();
+    }: _(RawOrigin::Root, ASSET_ID, fee)
+    verify {
+        assert_eq!(Module::<T>::withdrawal_fee_of(ASSET_ID), fee);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_push_header::<Test>());
+            assert_ok!(test_benchmark_push_receipt::<Test>());
+            assert_ok!(test_benchmark_process_withdrawals::<Test>());
+            assert_ok!(test_benchmark_set_erc20_asset::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_fee::<Test>());
+        });
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{StorageMap, StorageValue};
+use sp_core::H256;
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::prelude::*;
+
+use xp_logging::{error, info};
+
+use crate::types::{EthHeader, EthHeaderIndex};
+use crate::{BestIndex, BlockHashFor, ConfirmedIndex, Error, Headers, MainChain, Module, Trait};
+
+/// Check that the header links to its stored parent, which is the only check of the relay.
+///
+/// NOTE: this is NOT a light client verification. The seal (the signatures of the beacon sync
+/// committee after the merge) is not verified, since the BLS12-381 signatures could not be
+/// verified by the runtime, thus the headers are attested by the trustee multisig instead.
+pub fn check_header<T: Trait>(header: &EthHeader) -> Result<EthHeader, DispatchError> {
+    let parent = Module::<T>::headers(&header.parent_hash).ok_or_else(|| {
+        error!(
+            "[check_header] Can not find parent header, parent hash:{:?}",
+            header.parent_hash
+        );
+        Error::<T>::PrevHeaderNotExisted
+    })?;
+    if header.number != parent.number + 1 {
+        error!(
+            "[check_header] Header number {} is not the next of parent number {}",
+            header.number, parent.number
+        );
+        return Err(Error::<T>::InvalidHeaderNumber.into());
+    }
+    if header.timestamp <= parent.timestamp {
+        error!(
+            "[check_header] Header timestamp {} is not later than parent timestamp {}",
+            header.timestamp, parent.timestamp
+        );
+        return Err(Error::<T>::InvalidTimestamp.into());
+    }
+    ensure_gas::<T>(header)?;
+    Ok(parent)
+}
+
+fn ensure_gas<T: Trait>(header: &EthHeader) -> DispatchResult {
+    if header.gas_used > header.gas_limit {
+        error!(
+            "[check_header] Gas used {} exceeds gas limit {}",
+            header.gas_used, header.gas_limit
+        );
+        return Err(Error::<T>::InvalidGas.into());
+    }
+    Ok(())
+}
+
+/// Returns the indexes of the header and its ancestors not on the main chain, from high to low.
+///
+/// Reject the header if it forks from the main chain before the confirmed header, thus a
+/// confirmed header would never leave the main chain.
+pub fn fork_path<T: Trait>(
+    hash: H256,
+    header: &EthHeader,
+) -> Result<Vec<EthHeaderIndex>, DispatchError> {
+    let mut path = vec![EthHeaderIndex {
+        hash,
+        number: header.number,
+    }];
+    let mut parent_hash = header.parent_hash;
+    while !Module::<T>::main_chain(&parent_hash) {
+        let parent = Module::<T>::headers(&parent_hash).ok_or(Error::<T>::HeaderNotFound)?;
+        path.push(EthHeaderIndex {
+            hash: parent_hash,
+            number: parent.number,
+        });
+        parent_hash = parent.parent_hash;
+    }
+    // the parent of the lowest header on the path is the common ancestor
+    let ancestor_number = path
+        .last()
+        .map(|index| index.number - 1)
+        .unwrap_or_default();
+    if let Some(confirmed) = Module::<T>::confirmed_index() {
+        if ancestor_number < confirmed.number {
+            error!(
+                "[fork_path] Header ({:?}) forks at {}, which is lower than the confirmed {:?}",
+                hash, ancestor_number, confirmed
+            );
+            return Err(Error::<T>::AncientFork.into());
+        }
+    }
+    Ok(path)
+}
+
+/// Switch the main chain to the `path` ended at the new best header, and update the confirmed
+/// index.
+///
+/// The definition of confirmations is same to the Bitcoin gateway, i.e. the best header itself
+/// has 1 confirmation.
+pub fn update_main_chain<T: Trait>(path: Vec<EthHeaderIndex>) -> Option<EthHeaderIndex> {
+    let best = match path.first() {
+        Some(best) => *best,
+        None => return Module::<T>::confirmed_index(),
+    };
+    for index in path {
+        for hash in Module::<T>::block_hash_for(index.number) {
+            if hash == index.hash {
+                MainChain::insert(&hash, true);
+            } else if MainChain::take(&hash) {
+                info!(
+                    "[update_main_chain] Header ({:?}) at {} leaves the main chain",
+                    hash, index.number
+                );
+            }
+        }
+    }
+    BestIndex::put(best);
+
+    let confirmations = Module::<T>::confirmation_number().max(1) as u64;
+    let confirmed_number = best.number.checked_sub(confirmations - 1)?;
+    let confirmed = Module::<T>::block_hash_for(confirmed_number)
+        .into_iter()
+        .find(|hash| Module::<T>::main_chain(hash))
+        .map(|hash| EthHeaderIndex {
+            hash,
+            number: confirmed_number,
+        });
+    if let Some(confirmed) = confirmed {
+        ConfirmedIndex::put(confirmed);
+    }
+    confirmed
+}
+
+/// Insert the header into storage, it's the caller's duty to verify the header.
+pub fn insert_header<T: Trait>(hash: H256, header: EthHeader) {
+    BlockHashFor::mutate(header.number, |hashes| {
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    });
+    Headers::insert(&hash, header);
+}
@@ -0,0 +1,405 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for eth-bridge, X-ETH and the ERC-20 assets are backed by the trustee contract.
+//!
+//! The header relay is attested by the trustee multisig rather than a light client, i.e. only
+//! the link of the headers is checked on chain, the seal of the header is not verified. Thus the
+//! bridge trusts the trustees for both the headers and the withdrawals, and it's only enabled in
+//! the dev runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod header;
+pub mod proof;
+pub mod receipt;
+pub mod trustee;
+mod types;
+pub mod weights;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use sp_core::{H160, H256};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::EnsureOrigin,
+    weights::Pays,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{traits::TrusteeSession, trustees::ethereum::EthTrusteeAddrInfo};
+use xpallet_support::try_addr;
+
+pub use self::types::{EthHeader, EthHeaderIndex, EthLog, EthReceipt};
+pub use self::weights::WeightInfo;
+
+pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    type TrusteeSessionProvider: TrusteeSession<Self::AccountId, EthTrusteeAddrInfo>;
+    type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Ethereum module
+    pub enum Error for Module<T: Trait> {
+        /// cannot decode the rlp encoded header or receipt
+        DeserializeErr,
+        /// header already exists
+        ExistingHeader,
+        /// can't find previous header
+        PrevHeaderNotExisted,
+        /// header not found
+        HeaderNotFound,
+        /// the header number is not the next of its parent
+        InvalidHeaderNumber,
+        /// the header timestamp is not later than its parent
+        InvalidTimestamp,
+        /// the gas used exceeds the gas limit
+        InvalidGas,
+        /// fork is deeper than the confirmed header
+        AncientFork,
+        /// the block of receipt is not yet confirmed or not on the main chain
+        UnconfirmedReceipt,
+        /// the receipt trie proof is invalid
+        BadReceiptProof,
+        /// the transaction of receipt failed
+        FailedReceipt,
+        /// none of the logs in the receipt is processed
+        ProcessReceiptFailed,
+        /// reject replay processed receipt
+        ReplayedReceipt,
+        /// invalid ethereum address
+        InvalidAddress,
+        /// invalid secp256k1 public key
+        InvalidPublicKey,
+        /// duplicated pubkey for trustees
+        DuplicatedKeys,
+        /// invalid trustee count
+        InvalidTrusteeCount,
+        /// the token is not bound to any asset
+        UnknownToken,
+        /// the token or the asset is already bound
+        ExistingToken,
+        /// the amount exceeds the max balance
+        InvalidAmount,
+        /// no withdrawal record for this id
+        NoWithdrawalRecord,
+        /// the withdrawal in contract does not match the withdrawal record
+        MismatchedWithdrawal,
+        /// the signers of contract are not the current trustees
+        MismatchedSigners,
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>
+    {
+        /// An Ethereum header was inserted. [eth_header_hash]
+        HeaderInserted(H256),
+        /// The logs of an Ethereum receipt were processed. [block_hash, tx_index, processed_logs]
+        ReceiptProcessed(H256, u64, u32),
+        /// An account deposited some token. [block_hash, who, asset_id, amount]
+        Deposited(H256, AccountId, AssetId, Balance),
+        /// A withdrawal was sent by the trustee contract. [block_hash, withdrawal_id]
+        Withdrawn(H256, u32),
+        /// An ERC-20 token was bound to an asset or unbound. [asset_id, token]
+        Erc20AssetSet(AssetId, Option<H160>),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayEthereum {
+        /// best header info
+        pub BestIndex get(fn best_index): EthHeaderIndex;
+        /// confirmed header info
+        pub ConfirmedIndex get(fn confirmed_index): Option<EthHeaderIndex>;
+        /// block hash list for a number, include forked header hash
+        pub BlockHashFor get(fn block_hash_for): map hasher(twox_64_concat) u64 => Vec<H256>;
+        /// mark this blockhash is in mainchain
+        pub MainChain get(fn main_chain): map hasher(identity) H256 => bool;
+        /// all valid headers (include forked headers)
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<EthHeader>;
+
+        /// mark the receipt (block_hash, tx_index) has been processed
+        pub ProcessedReceipts get(fn processed_receipt): map hasher(twox_64_concat) (H256, u64) => bool;
+
+        /// token address => asset id of the ERC-20 token
+        pub Erc20AssetOf get(fn erc20_asset_of): map hasher(twox_64_concat) H160 => Option<AssetId>;
+        /// asset id => token address of the ERC-20 token
+        pub Erc20TokenOf get(fn erc20_token_of): map hasher(twox_64_concat) AssetId => Option<H160>;
+        /// withdrawal fee of X-ETH and the ERC-20 assets
+        pub WithdrawalFeeOf get(fn withdrawal_fee_of): map hasher(twox_64_concat) AssetId => BalanceOf<T>;
+
+        /// get ConfirmationNumber from genesis_config
+        pub ConfirmationNumber get(fn confirmation_number) config(): u32;
+        /// the address of the trustee contract
+        pub TrusteeContract get(fn trustee_contract) config(): H160;
+    }
+    add_extra_genesis {
+        /// rlp encoded genesis header, the relay would not start if it's empty
+        config(genesis_header): Vec<u8>;
+        config(genesis_trustees): Vec<T::AccountId>;
+        build(|config| {
+            if !config.genesis_header.is_empty() {
+                let (hash, header) = EthHeader::decode_rlp(&config.genesis_header)
+                    .expect("genesis header must be valid; qed");
+                let index = EthHeaderIndex {
+                    hash,
+                    number: header.number,
+                };
+                header::insert_header::<T>(hash, header);
+                MainChain::insert(&hash, true);
+                BestIndex::put(index);
+            }
+
+            if !config.genesis_trustees.is_empty() {
+                T::TrusteeSessionProvider::genesis_trustee(Module::<T>::chain(), &config.genesis_trustees);
+            }
+        })
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+        fn deposit_event() = default;
+
+        /// Relay the rlp encoded Ethereum header, the origin must be the trustee multisig or root.
+        ///
+        /// NOTE: the seal of header is not verified on chain and the fork is chosen by the
+        /// height, thus the headers are only trusted if attested by the trustees as the
+        /// withdrawals.
+        #[weight = <T as Trait>::WeightInfo::push_header()]
+        pub fn push_header(origin, header: Vec<u8>) -> DispatchResultWithPostInfo {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            let (hash, header) = EthHeader::decode_rlp(&header).map_err(|_| Error::<T>::DeserializeErr)?;
+            debug!("[push_header] hash:{:?}, header:{:?}", hash, header);
+
+            Self::apply_push_header(hash, header)?;
+
+            // Relayer does not pay a fee.
+            Ok(Pays::No.into())
+        }
+
+        /// Relay the receipt of the `tx_index`th transaction in the confirmed block `block_hash`,
+        /// `proof` is the nodes of receipt trie from the root to the receipt.
+        #[weight = <T as Trait>::WeightInfo::push_receipt()]
+        pub fn push_receipt(
+            origin,
+            block_hash: H256,
+            #[compact] tx_index: u64,
+            proof: Vec<Vec<u8>>
+        ) -> DispatchResultWithPostInfo {
+            let _from = ensure_signed(origin)?;
+            debug!("[push_receipt] from:{:?}, block:{:?}, tx_index:{}", _from, block_hash, tx_index);
+
+            Self::apply_push_receipt(block_hash, tx_index, proof)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Mark the withdrawals as processing before sending them by the trustee contract.
+        #[weight = <T as Trait>::WeightInfo::process_withdrawals(ids.len() as u32)]
+        pub fn process_withdrawals(origin, ids: Vec<u32>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            xpallet_gateway_records::Module::<T>::process_withdrawals(&ids, Chain::Ethereum)
+        }
+
+        /// Bind the ERC-20 `token` to the asset on Ethereum, or unbind it if `token` is None.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_erc20_asset()]
+        pub fn set_erc20_asset(origin, #[compact] asset_id: AssetId, token: Option<H160>) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(asset_id != <Self as ChainT<_>>::ASSET_ID, xpallet_assets::Error::<T>::ActionNotAllowed);
+            ensure!(
+                xpallet_assets_registrar::Module::<T>::chain_of(&asset_id)? == Chain::Ethereum,
+                xpallet_assets::Error::<T>::ActionNotAllowed
+            );
+            if let Some(token) = token {
+                ensure!(!token.is_zero() && Self::erc20_asset_of(&token).is_none(), Error::<T>::ExistingToken);
+            }
+
+            if let Some(old) = Erc20TokenOf::take(asset_id) {
+                Erc20AssetOf::remove(&old);
+            }
+            if let Some(token) = token {
+                Erc20AssetOf::insert(&token, asset_id);
+                Erc20TokenOf::insert(asset_id, token);
+            }
+            Self::deposit_event(Event::<T>::Erc20AssetSet(asset_id, token));
+            Ok(())
+        }
+
+        /// Set the withdrawal fee of X-ETH or the ERC-20 asset.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee()]
+        pub fn set_withdrawal_fee(origin, #[compact] asset_id: AssetId, #[compact] fee: BalanceOf<T>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            WithdrawalFeeOf::<T>::insert(asset_id, fee);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        let address = Self::parse_address(addr).ok_or_else(|| {
+            error!("[check_addr] Invalid ethereum address:{:?}", try_addr(addr));
+            Error::<T>::InvalidAddress
+        })?;
+        // do not allow withdraw to the trustee contract
+        ensure!(
+            address != Self::trustee_contract(),
+            Error::<T>::InvalidAddress
+        );
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        if *asset_id != Self::ASSET_ID && Self::erc20_token_of(asset_id).is_none() {
+            return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
+        }
+        let fee = Self::withdrawal_fee_of(asset_id);
+        let limit = WithdrawalLimit::<BalanceOf<T>> {
+            minimal_withdrawal: fee * 3u32.saturated_into() / 2u32.saturated_into(),
+            fee,
+        };
+        Ok(limit)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Parse the hex address with `0x` prefix, the mixed-case address must match the EIP-55
+    /// checksum.
+    pub fn parse_address(addr: &[u8]) -> Option<H160> {
+        if addr.len() != 42 || !addr.starts_with(b"0x") {
+            return None;
+        }
+        let hex = &addr[2..];
+        let mut address = H160::zero();
+        for (i, byte) in address.as_bytes_mut().iter_mut().enumerate() {
+            *byte = (hex_value(hex[2 * i])? << 4) | hex_value(hex[2 * i + 1])?;
+        }
+
+        let has_lower = hex.iter().any(u8::is_ascii_lowercase);
+        let has_upper = hex.iter().any(u8::is_ascii_uppercase);
+        if has_lower && has_upper {
+            let lower = hex.to_ascii_lowercase();
+            let hash = sp_io::hashing::keccak_256(&lower);
+            let checksum_ok = hex.iter().enumerate().all(|(i, c)| {
+                let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+                !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+            });
+            if !checksum_ok {
+                return None;
+            }
+        }
+        Some(address)
+    }
+
+    fn apply_push_header(hash: H256, header: EthHeader) -> DispatchResult {
+        // current should not exist
+        if Self::headers(&hash).is_some() {
+            error!(
+                "[apply_push_header] The ETH header already exists, hash:{:?}",
+                hash
+            );
+            return Err(Error::<T>::ExistingHeader.into());
+        }
+        header::check_header::<T>(&header)?;
+        let path = header::fork_path::<T>(hash, &header)?;
+
+        with_transaction_result(|| {
+            let number = header.number;
+            header::insert_header::<T>(hash, header);
+
+            let best_index = Self::best_index();
+            if number > best_index.number {
+                let confirmed_index = header::update_main_chain::<T>(path);
+                info!(
+                    "[apply_push_header] Update new number:{}, hash:{:?}, confirm:{:?}",
+                    number, hash, confirmed_index
+                );
+            } else {
+                // forked chain
+                info!(
+                    "[apply_push_header] Best index {} larger than this number {}",
+                    best_index.number, number
+                );
+            }
+            Self::deposit_event(Event::<T>::HeaderInserted(hash));
+            Ok(())
+        })
+    }
+
+    fn apply_push_receipt(block_hash: H256, tx_index: u64, proof: Vec<Vec<u8>>) -> DispatchResult {
+        let header = Self::headers(&block_hash).ok_or(Error::<T>::HeaderNotFound)?;
+        // only the receipts in the confirmed main chain blocks could be processed
+        ensure!(
+            Self::main_chain(&block_hash),
+            Error::<T>::UnconfirmedReceipt
+        );
+        let confirmed = Self::confirmed_index().ok_or(Error::<T>::UnconfirmedReceipt)?;
+        if header.number > confirmed.number {
+            error!(
+                "[apply_push_receipt] Receive an unconfirmed receipt (number:{}, hash:{:?}), confirmed index:{:?}",
+                header.number, block_hash, confirmed
+            );
+            return Err(Error::<T>::UnconfirmedReceipt.into());
+        }
+        ensure!(
+            !Self::processed_receipt((block_hash, tx_index)),
+            Error::<T>::ReplayedReceipt
+        );
+
+        let key = rlp::encode(&tx_index);
+        let raw = proof::verify_proof(&header.receipts_root, &key, &proof)
+            .ok_or(Error::<T>::BadReceiptProof)?;
+        let receipt = EthReceipt::decode_rlp(&raw).map_err(|_| Error::<T>::DeserializeErr)?;
+        ensure!(receipt.success, Error::<T>::FailedReceipt);
+
+        with_transaction_result(|| {
+            let processed = receipt::process_logs::<T>(block_hash, tx_index, &receipt.logs);
+            ensure!(processed > 0, Error::<T>::ProcessReceiptFailed);
+            ProcessedReceipts::insert((block_hash, tx_index), true);
+            Self::deposit_event(Event::<T>::ReceiptProcessed(
+                block_hash, tx_index, processed,
+            ));
+            Ok(())
+        })
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use hex_literal::hex;
+
+use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
+use frame_system::EnsureSignedBy;
+use sp_core::{H160, H256};
+use sp_keyring::sr25519;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, DispatchError, DispatchResult, Perbill,
+};
+
+use chainx_primitives::AssetId;
+use xp_assets_registrar::Chain;
+pub use xp_protocol::{X_BTC, X_DOT, X_ETH};
+use xpallet_assets::{AssetRestrictions, ChainT};
+use xpallet_assets_registrar::AssetInfo;
+use xpallet_gateway_common::{
+    traits::{AddressOwnership, TrusteeForChain},
+    trustees::{
+        bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
+        ethereum::{EthTrusteeMultisig, EthTrusteeSessionManager},
+        local::LocalChainDisabled,
+        polkadot::{DotTrusteeAddrInfo, DotTrusteeType},
+    },
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+};
+
+use crate::{types::encode_header, Error, GenesisConfig, Module, Trait};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+pub(crate) type Amount = i128;
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+// assets
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = ();
+    type Currency = Balances;
+    type Amount = Amount;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_records::Trait for Test {
+    type Event = ();
+    type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+    type WithdrawalHistoryRetention = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_common::Trait for Test {
+    type Event = ();
+    type Validator = ();
+    type DetermineMultisigAddress = ();
+    type Bitcoin = MockBitcoin;
+    type BitcoinTrustee = MockBitcoin;
+    type Ethereum = XGatewayEthereum;
+    type EthereumTrustee = XGatewayEthereum;
+    type Polkadot = MockPolkadot;
+    type PolkadotTrustee = MockPolkadot;
+    type Local = LocalChainDisabled;
+    type LocalTrustee = LocalChainDisabled;
+    type WeightInfo = ();
+}
+
+pub struct MockBitcoin;
+impl ChainT<Balance> for MockBitcoin {
+    const ASSET_ID: AssetId = X_BTC;
+
+    fn chain() -> Chain {
+        Chain::Bitcoin
+    }
+}
+impl TrusteeForChain<AccountId, BtcTrusteeType, BtcTrusteeAddrInfo> for MockBitcoin {
+    fn check_trustee_entity(_: &[u8]) -> Result<BtcTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<BtcTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, BtcTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+pub struct MockPolkadot;
+impl ChainT<Balance> for MockPolkadot {
+    const ASSET_ID: AssetId = X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+impl AddressOwnership<AccountId> for MockPolkadot {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+}
+impl TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo> for MockPolkadot {
+    fn check_trustee_entity(_: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl Trait for Test {
+    type Event = ();
+    type TrusteeSessionProvider = EthTrusteeSessionManager<Test>;
+    type TrusteeOrigin = EnsureSignedBy<EthTrusteeMultisig<Test>, AccountId>;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XGatewayRecords = xpallet_gateway_records::Module<Test>;
+pub type XGatewayEthereum = Module<Test>;
+pub type XGatewayEthereumErr = Error<Test>;
+
+/// The ERC-20 asset used in tests.
+pub const USDT: AssetId = 1000;
+
+pub(crate) fn eth() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_ETH,
+        AssetInfo::new::<Test>(
+            b"X-ETH".to_vec(),
+            b"X-ETH".to_vec(),
+            Chain::Ethereum,
+            xp_protocol::ETH_DECIMALS,
+            b"ChainX's cross-chain Ethereum".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+pub(crate) fn usdt() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        USDT,
+        AssetInfo::new::<Test>(
+            b"X-USDT".to_vec(),
+            b"X-USDT".to_vec(),
+            Chain::Ethereum,
+            6,
+            b"ChainX's cross-chain ERC-20 USDT".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+/// The address of the trustee contract.
+pub fn trustee_contract() -> H160 {
+    H160::repeat_byte(0x11)
+}
+
+/// The genesis header of the relay, number 11_000_000.
+pub fn genesis_header() -> Vec<u8> {
+    encode_header(
+        H256::repeat_byte(1),
+        11_000_000,
+        1_600_000_000,
+        H256::zero(),
+    )
+}
+
+pub const CONFIRMATION_NUMBER: u32 = 3;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let assets = vec![
+            (eth().0, eth().1, eth().2, true, true),
+            (usdt().0, usdt().1, usdt().2, true, true),
+        ];
+
+        let mut init_assets = vec![];
+        let mut assets_restrictions = vec![];
+        for (a, b, c, d, e) in assets {
+            init_assets.push((a, b, d, e));
+            assets_restrictions.push((a, c))
+        }
+
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: init_assets,
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions,
+            endowed: Default::default(),
+        }
+        .assimilate_storage(&mut storage);
+
+        let info = trustees_info();
+        let genesis_trustees = info[0].2.iter().map(|i| (i.0).clone()).collect();
+
+        let _ = xpallet_gateway_common::GenesisConfig::<Test> { trustees: info }
+            .assimilate_storage(&mut storage);
+
+        let _ = GenesisConfig::<Test> {
+            genesis_header: genesis_header(),
+            genesis_trustees,
+            confirmation_number: CONFIRMATION_NUMBER,
+            trustee_contract: trustee_contract(),
+        }
+        .assimilate_storage(&mut storage);
+
+        sp_io::TestExternalities::new(storage)
+    }
+
+    pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
+
+pub fn alice() -> AccountId32 {
+    sr25519::Keyring::Alice.to_account_id()
+}
+pub fn bob() -> AccountId32 {
+    sr25519::Keyring::Bob.to_account_id()
+}
+pub fn charlie() -> AccountId32 {
+    sr25519::Keyring::Charlie.to_account_id()
+}
+
+/// The public keys of the private keys 1, 2 and 3.
+pub fn trustees() -> Vec<(AccountId32, Vec<u8>, Vec<u8>, Vec<u8>)> {
+    vec![
+        (
+            alice(),
+            b"Alice".to_vec(),
+            hex!("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").to_vec(),
+            hex!("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").to_vec(),
+        ),
+        (
+            bob(),
+            b"Bob".to_vec(),
+            hex!("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5").to_vec(),
+            hex!("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5").to_vec(),
+        ),
+        (
+            charlie(),
+            b"Charlie".to_vec(),
+            hex!("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").to_vec(),
+            hex!("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").to_vec(),
+        ),
+    ]
+}
+
+fn trustees_info() -> Vec<(
+    Chain,
+    TrusteeInfoConfig,
+    Vec<(AccountId, Vec<u8>, Vec<u8>, Vec<u8>)>,
+)> {
+    let eth_config = TrusteeInfoConfig {
+        min_trustee_count: 3,
+        max_trustee_count: 15,
+    };
+    vec![(Chain::Ethereum, eth_config, trustees())]
+}
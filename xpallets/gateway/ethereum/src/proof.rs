@@ -0,0 +1,212 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verifier of the Merkle Patricia proof, e.g. the proof of a receipt in the receipt trie.
+
+use rlp::Rlp;
+
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// The reference of a child node, a child node shorter than 32 bytes is inlined in its parent.
+enum NodeRef {
+    Hash(H256),
+    Inline(Vec<u8>),
+}
+
+impl NodeRef {
+    fn from_rlp(rlp: &Rlp) -> Option<Self> {
+        if rlp.is_list() {
+            return Some(NodeRef::Inline(rlp.as_raw().to_vec()));
+        }
+        match rlp.data().ok()? {
+            hash if hash.len() == 32 => Some(NodeRef::Hash(H256::from_slice(hash))),
+            // the empty child, i.e. the key does not exist
+            _ => None,
+        }
+    }
+}
+
+/// Verify the proof of `key` in the trie of `root`.
+///
+/// `proof` is the list of the nodes on the path from the root to the value (inlined nodes are not
+/// included), returns the value if the proof is valid and the key exists.
+pub fn verify_proof(root: &H256, key: &[u8], proof: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let nibbles = to_nibbles(key);
+    let mut path = &nibbles[..];
+    let mut proof = proof.iter();
+    let mut next = NodeRef::Hash(*root);
+    loop {
+        let raw = match next {
+            NodeRef::Hash(hash) => {
+                let node = proof.next()?;
+                if keccak_256(node) != hash.0 {
+                    return None;
+                }
+                node.clone()
+            }
+            NodeRef::Inline(node) => node,
+        };
+        let node = Rlp::new(&raw);
+        match node.item_count().ok()? {
+            // branch node
+            17 => {
+                if path.is_empty() {
+                    let value = node.at(16).ok()?.data().ok()?;
+                    return if value.is_empty() {
+                        None
+                    } else {
+                        Some(value.to_vec())
+                    };
+                }
+                next = NodeRef::from_rlp(&node.at(path[0] as usize).ok()?)?;
+                path = &path[1..];
+            }
+            // extension or leaf node
+            2 => {
+                let (is_leaf, partial) = decode_hex_prefix(node.at(0).ok()?.data().ok()?)?;
+                if !path.starts_with(&partial) {
+                    return None;
+                }
+                path = &path[partial.len()..];
+                if is_leaf {
+                    return if path.is_empty() {
+                        Some(node.at(1).ok()?.data().ok()?.to_vec())
+                    } else {
+                        None
+                    };
+                }
+                next = NodeRef::from_rlp(&node.at(1).ok()?)?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Decode the hex-prefix encoded path, returns whether the node is a leaf and the nibbles.
+fn decode_hex_prefix(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let first = *encoded.first()?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return None;
+    }
+    let is_leaf = flag >= 2;
+    let mut nibbles = Vec::with_capacity(encoded.len() * 2);
+    if flag % 2 == 1 {
+        // odd length, the first nibble is in the prefix byte
+        nibbles.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return None;
+    }
+    nibbles.extend(to_nibbles(&encoded[1..]));
+    Some((is_leaf, nibbles))
+}
+
+/// Encode the nibbles with hex-prefix, the inverse of `decode_hex_prefix`.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn encode_hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let (mut encoded, rest) = if nibbles.len() % 2 == 1 {
+        (vec![((flag + 1) << 4) | nibbles[0]], &nibbles[1..])
+    } else {
+        (vec![flag << 4], nibbles)
+    };
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+/// Build the trie containing only one `(key, value)`, returns the root and the proof of the key.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn single_leaf_proof(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    let mut stream = rlp::RlpStream::new_list(2);
+    stream.append(&encode_hex_prefix(&to_nibbles(key), true));
+    stream.append(&value.to_vec());
+    let leaf = stream.out();
+    (H256(keccak_256(&leaf)), vec![leaf])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(2);
+        stream.append(&encode_hex_prefix(nibbles, true));
+        stream.append(&value.to_vec());
+        stream.out()
+    }
+
+    #[test]
+    fn test_hex_prefix() {
+        for (nibbles, is_leaf) in vec![
+            (vec![], true),
+            (vec![1], false),
+            (vec![1, 2], true),
+            (vec![0, 15, 1], false),
+        ] {
+            let encoded = encode_hex_prefix(&nibbles, is_leaf);
+            assert_eq!(decode_hex_prefix(&encoded), Some((is_leaf, nibbles)));
+        }
+        assert_eq!(encode_hex_prefix(&[1, 2, 3], true), vec![0x31, 0x23]);
+        assert_eq!(decode_hex_prefix(&[0x01]), None);
+        assert_eq!(decode_hex_prefix(&[0x40]), None);
+    }
+
+    #[test]
+    fn test_single_leaf_proof() {
+        let key = rlp::encode(&1u64);
+        let value = vec![7u8; 40];
+        let (root, proof) = single_leaf_proof(&key, &value);
+        assert_eq!(verify_proof(&root, &key, &proof), Some(value.clone()));
+        // wrong key
+        assert_eq!(verify_proof(&root, &rlp::encode(&2u64), &proof), None);
+        // wrong root
+        assert_eq!(verify_proof(&H256::repeat_byte(1), &key, &proof), None);
+        // missing node
+        assert_eq!(verify_proof(&root, &key, &[]), None);
+    }
+
+    #[test]
+    fn test_branch_proof() {
+        // keys: rlp(0) = 0x80, rlp(1) = 0x01
+        let long_value = vec![1u8; 40];
+        let short_value = vec![2u8; 2];
+        let hashed_leaf = leaf(&[0], &long_value);
+        let inline_leaf = leaf(&[1], &short_value);
+        assert!(inline_leaf.len() < 32);
+
+        let mut branch = rlp::RlpStream::new_list(17);
+        for i in 0..16 {
+            match i {
+                0 => {
+                    branch.append_raw(&inline_leaf, 1);
+                }
+                8 => {
+                    branch.append(&keccak_256(&hashed_leaf).to_vec());
+                }
+                _ => {
+                    branch.append_empty_data();
+                }
+            }
+        }
+        branch.append_empty_data();
+        let branch = branch.out();
+        let root = H256(keccak_256(&branch));
+
+        let proof = vec![branch.clone(), hashed_leaf];
+        assert_eq!(
+            verify_proof(&root, &rlp::encode(&0u64), &proof),
+            Some(long_value)
+        );
+        assert_eq!(
+            verify_proof(&root, &rlp::encode(&1u64), &[branch.clone()]),
+            Some(short_value)
+        );
+        // empty child
+        assert_eq!(verify_proof(&root, &rlp::encode(&0x20u64), &[branch]), None);
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Process the logs emitted by the trustee contract.
+//!
+//! The trustee contract emits:
+//! - `Deposit(address indexed token, bytes32 indexed recipient, uint256 amount)`, the zero
+//!   `token` means ETH.
+//! - `Withdrawal(uint32 indexed id, address indexed token, address to, uint256 amount)`.
+//! - `SignersChanged(address[] signers)`, when the signers were changed to the new trustees.
+
+use codec::Decode;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::Saturating, RuntimeDebug, SaturatedConversion};
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xp_logging::{error, info, warn};
+use xpallet_assets::{BalanceOf, Chain, ChainT};
+use xpallet_gateway_common::traits::TrusteeSession;
+
+use crate::trustee::current_trustee_session;
+use crate::types::EthLog;
+use crate::{Error, Event, Module, Trait};
+
+pub const DEPOSIT_EVENT: &[u8] = b"Deposit(address,bytes32,uint256)";
+pub const WITHDRAWAL_EVENT: &[u8] = b"Withdrawal(uint32,address,address,uint256)";
+pub const SIGNERS_CHANGED_EVENT: &[u8] = b"SignersChanged(address[])";
+
+/// Returns the topic of the event signature.
+pub fn event_topic(signature: &[u8]) -> H256 {
+    H256(keccak_256(signature))
+}
+
+/// The log of the trustee contract.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub enum ContractLog<AccountId> {
+    Deposit {
+        token: H160,
+        recipient: AccountId,
+        amount: U256,
+    },
+    Withdrawal {
+        id: u32,
+        token: H160,
+        to: H160,
+        amount: U256,
+    },
+    SignersChanged(Vec<H160>),
+}
+
+impl<AccountId: Decode> ContractLog<AccountId> {
+    /// Parse the log, returns `None` if it's not a known event of the trustee contract.
+    pub fn parse(log: &EthLog) -> Option<Self> {
+        let topic = log.topics.first()?;
+        if *topic == event_topic(DEPOSIT_EVENT) {
+            if log.topics.len() != 3 || log.data.len() != 32 {
+                return None;
+            }
+            Some(ContractLog::Deposit {
+                token: word_to_address(log.topics[1].as_bytes())?,
+                recipient: AccountId::decode(&mut log.topics[2].as_bytes()).ok()?,
+                amount: U256::from_big_endian(&log.data),
+            })
+        } else if *topic == event_topic(WITHDRAWAL_EVENT) {
+            if log.topics.len() != 3 || log.data.len() != 64 {
+                return None;
+            }
+            let id = U256::from_big_endian(log.topics[1].as_bytes());
+            if id > U256::from(u32::max_value()) {
+                return None;
+            }
+            Some(ContractLog::Withdrawal {
+                id: id.low_u32(),
+                token: word_to_address(log.topics[2].as_bytes())?,
+                to: word_to_address(&log.data[..32])?,
+                amount: U256::from_big_endian(&log.data[32..]),
+            })
+        } else if *topic == event_topic(SIGNERS_CHANGED_EVENT) {
+            // abi encoding of the dynamic array: offset | length | items
+            let words = log.data.chunks(32).collect::<Vec<_>>();
+            if log.topics.len() != 1 || log.data.len() % 32 != 0 || words.len() < 2 {
+                return None;
+            }
+            if U256::from_big_endian(words[0]) != U256::from(32) {
+                return None;
+            }
+            let len = U256::from_big_endian(words[1]);
+            if len != U256::from(words.len() - 2) {
+                return None;
+            }
+            let signers = words[2..]
+                .iter()
+                .map(|word| word_to_address(word))
+                .collect::<Option<Vec<_>>>()?;
+            Some(ContractLog::SignersChanged(signers))
+        } else {
+            None
+        }
+    }
+}
+
+/// The address is left padded with zeros to 32 bytes.
+fn word_to_address(word: &[u8]) -> Option<H160> {
+    if word.len() != 32 || word[..12].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(H160::from_slice(&word[12..]))
+}
+
+fn to_balance<T: Trait>(amount: U256) -> Result<BalanceOf<T>, DispatchError> {
+    if amount > U256::from(u128::max_value()) {
+        return Err(Error::<T>::InvalidAmount.into());
+    }
+    Ok(amount.low_u128().saturated_into())
+}
+
+/// Returns the asset of the token, the zero address means ETH.
+pub fn asset_of_token<T: Trait>(token: &H160) -> Result<AssetId, DispatchError> {
+    if token.is_zero() {
+        Ok(<Module<T> as ChainT<_>>::ASSET_ID)
+    } else {
+        Module::<T>::erc20_asset_of(token).ok_or_else(|| Error::<T>::UnknownToken.into())
+    }
+}
+
+/// Process the logs of the trustee contract in a receipt, returns the count of the processed logs.
+///
+/// The failed logs are skipped, and would not be processed again.
+pub fn process_logs<T: Trait>(block_hash: H256, tx_index: u64, logs: &[EthLog]) -> u32 {
+    let contract = Module::<T>::trustee_contract();
+    let mut processed = 0;
+    for log in logs.iter().filter(|log| log.address == contract) {
+        let result = match ContractLog::<T::AccountId>::parse(log) {
+            Some(ContractLog::Deposit {
+                token,
+                recipient,
+                amount,
+            }) => deposit::<T>(block_hash, recipient, token, amount),
+            Some(ContractLog::Withdrawal {
+                id,
+                token,
+                to,
+                amount,
+            }) => withdraw::<T>(block_hash, id, token, to, amount),
+            Some(ContractLog::SignersChanged(signers)) => change_signers::<T>(signers),
+            None => continue,
+        };
+        match result {
+            Ok(()) => processed += 1,
+            Err(err) => error!(
+                "[process_logs] Process log failed, block:{:?}, tx index:{}, log:{:?}, err:{:?}",
+                block_hash, tx_index, log, err
+            ),
+        }
+    }
+    processed
+}
+
+fn deposit<T: Trait>(
+    block_hash: H256,
+    who: T::AccountId,
+    token: H160,
+    amount: U256,
+) -> DispatchResult {
+    let asset_id = asset_of_token::<T>(&token)?;
+    let balance = to_balance::<T>(amount)?;
+    // the receipt does not carry the tx hash, which is left unknown in the deposit record
+    xpallet_gateway_records::Module::<T>::deposit_with_tx(&who, asset_id, balance, None, None)?;
+    info!(
+        "[deposit] block:{:?}, who:{:?}, asset id:{}, balance:{:?}",
+        block_hash, who, asset_id, balance
+    );
+    Module::<T>::deposit_event(Event::<T>::Deposited(block_hash, who, asset_id, balance));
+    Ok(())
+}
+
+fn withdraw<T: Trait>(
+    block_hash: H256,
+    id: u32,
+    token: H160,
+    to: H160,
+    amount: U256,
+) -> DispatchResult {
+    let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+        .ok_or(Error::<T>::NoWithdrawalRecord)?;
+    let asset_id = asset_of_token::<T>(&token)?;
+    if record.asset_id() != asset_id || Module::<T>::parse_address(record.addr()) != Some(to) {
+        error!(
+            "[withdraw] Withdrawal (id:{}) does not match the record, token:{:?}, to:{:?}, record:{:?}",
+            id, token, to, record
+        );
+        return Err(Error::<T>::MismatchedWithdrawal.into());
+    }
+    let fee = Module::<T>::withdrawal_fee_of(asset_id);
+    if to_balance::<T>(amount)? < record.balance().saturating_sub(fee) {
+        error!(
+            "[withdraw] Withdrawal (id:{}) amount {:?} is less than the record balance {:?} minus fee {:?}",
+            id, amount, record.balance(), fee
+        );
+        return Err(Error::<T>::MismatchedWithdrawal.into());
+    }
+    xpallet_gateway_records::Module::<T>::finish_withdrawal(id, Some(Chain::Ethereum))?;
+    Module::<T>::deposit_event(Event::<T>::Withdrawn(block_hash, id));
+    Ok(())
+}
+
+fn change_signers<T: Trait>(signers: Vec<H160>) -> DispatchResult {
+    let session_info = current_trustee_session::<T>()?;
+    if session_info.hot_address.signers != signers {
+        warn!(
+            "[change_signers] Signers {:?} of the contract are not the current trustees {:?}",
+            signers, session_info.hot_address.signers
+        );
+        return Err(Error::<T>::MismatchedSigners.into());
+    }
+    T::TrusteeSessionProvider::trustee_transition_relayed(|trustee| trustee.signers == signers);
+    Ok(())
+}
@@ -0,0 +1,417 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use hex_literal::hex;
+use sp_core::{H160, H256};
+use sp_runtime::AccountId32;
+
+use codec::Encode;
+use xpallet_assets::ChainT;
+use xpallet_gateway_records::WithdrawalState;
+
+use crate::mock::*;
+use crate::{
+    proof::single_leaf_proof,
+    receipt::{event_topic, ContractLog, DEPOSIT_EVENT, SIGNERS_CHANGED_EVENT, WITHDRAWAL_EVENT},
+    trustee::current_trustee_session,
+    types::{encode_header, encode_receipt},
+    EthHeader, EthLog,
+};
+
+fn push_header(parent: H256, number: u64, timestamp: u64, receipts_root: H256) -> H256 {
+    let raw = encode_header(parent, number, timestamp, receipts_root);
+    let (hash, _) = EthHeader::decode_rlp(&raw).unwrap();
+    assert_ok!(XGatewayEthereum::push_header(Origin::root(), raw));
+    hash
+}
+
+/// Push a header on the best header with the receipts root.
+fn push_best_header(receipts_root: H256) -> H256 {
+    let best = XGatewayEthereum::best_index();
+    let timestamp = XGatewayEthereum::headers(&best.hash).unwrap().timestamp + 13;
+    push_header(best.hash, best.number + 1, timestamp, receipts_root)
+}
+
+/// Push a header with the receipts root and confirm it, returns the hash of the header.
+fn push_confirmed_header(receipts_root: H256) -> H256 {
+    let hash = push_best_header(receipts_root);
+    for _ in 1..CONFIRMATION_NUMBER {
+        push_best_header(H256::zero());
+    }
+    assert_eq!(XGatewayEthereum::confirmed_index().unwrap().hash, hash);
+    hash
+}
+
+fn word(address: H160) -> H256 {
+    let mut word = H256::zero();
+    word.as_bytes_mut()[12..].copy_from_slice(address.as_bytes());
+    word
+}
+
+fn deposit_log(token: H160, who: &AccountId32, amount: u64) -> EthLog {
+    EthLog {
+        address: trustee_contract(),
+        topics: vec![
+            event_topic(DEPOSIT_EVENT),
+            word(token),
+            H256::from_slice(&who.encode()),
+        ],
+        data: H256::from_low_u64_be(amount).as_bytes().to_vec(),
+    }
+}
+
+fn withdrawal_log(id: u32, token: H160, to: H160, amount: u64) -> EthLog {
+    let mut data = word(to).as_bytes().to_vec();
+    data.extend_from_slice(H256::from_low_u64_be(amount).as_bytes());
+    EthLog {
+        address: trustee_contract(),
+        topics: vec![
+            event_topic(WITHDRAWAL_EVENT),
+            H256::from_low_u64_be(id as u64),
+            word(token),
+        ],
+        data,
+    }
+}
+
+/// Push the receipt containing the logs in a new confirmed block.
+fn push_receipt(logs: &[EthLog]) -> (H256, Result<(), sp_runtime::DispatchError>) {
+    let tx_index = 3u64;
+    let (root, proof) = single_leaf_proof(&rlp::encode(&tx_index), &encode_receipt(logs));
+    let block_hash = push_confirmed_header(root);
+    let result = XGatewayEthereum::push_receipt(Origin::signed(bob()), block_hash, tx_index, proof)
+        .map(|_| ())
+        .map_err(|err| err.error);
+    (block_hash, result)
+}
+
+#[test]
+fn test_generate_trustee_session_info() {
+    ExtBuilder::default().build_and_execute(|| {
+        let info = current_trustee_session::<Test>().unwrap();
+        assert_eq!(info.trustee_list, vec![alice(), bob(), charlie()]);
+        assert_eq!(info.threshold, 2);
+        let signers = vec![
+            H160::from(hex!("7e5f4552091a69125d5dfcb7b8c2659029395bdf")),
+            H160::from(hex!("2b5ad5c4795c026514f8317c7a215e218dccd6cf")),
+            H160::from(hex!("6813eb9362372eef6200f3b1dbc3f819671cba69")),
+        ];
+        assert_eq!(info.hot_address.signers, signers);
+        assert_eq!(info.cold_address.signers, signers);
+    })
+}
+
+#[test]
+fn test_check_trustee_entity() {
+    use xpallet_gateway_common::traits::TrusteeForChain;
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayEthereum::check_trustee_entity(&hex!(
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        )));
+        // x exceeds the field size
+        assert_noop!(
+            XGatewayEthereum::check_trustee_entity(&[&[2u8][..], &[0xffu8; 32][..]].concat()),
+            XGatewayEthereumErr::InvalidPublicKey
+        );
+        // uncompressed
+        assert_noop!(
+            XGatewayEthereum::check_trustee_entity(&[4u8; 65]),
+            XGatewayEthereumErr::InvalidPublicKey
+        );
+    })
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        let expected = H160::from(hex!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+        // lowercase, uppercase and checksummed
+        for addr in &[
+            &b"0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"[..],
+            &b"0x7E5F4552091A69125D5DFCB7B8C2659029395BDF"[..],
+            &b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"[..],
+        ] {
+            assert_eq!(XGatewayEthereum::parse_address(addr), Some(expected));
+            assert_ok!(XGatewayEthereum::check_addr(addr, b""));
+        }
+        // bad checksum
+        assert_eq!(
+            XGatewayEthereum::parse_address(b"0x7e5F4552091A69125d5DfCb7b8C2659029395Bdf"),
+            None
+        );
+        // bad length or prefix
+        assert_eq!(
+            XGatewayEthereum::parse_address(b"0x7e5f4552091a69125d5dfcb7b8c2659029395b"),
+            None
+        );
+        assert_eq!(
+            XGatewayEthereum::parse_address(b"7e5f4552091a69125d5dfcb7b8c2659029395bdf00"),
+            None
+        );
+        // the trustee contract
+        assert_noop!(
+            XGatewayEthereum::check_addr(b"0x1111111111111111111111111111111111111111", b""),
+            XGatewayEthereumErr::InvalidAddress
+        );
+    })
+}
+
+#[test]
+fn test_push_header() {
+    ExtBuilder::default().build_and_execute(|| {
+        let genesis = XGatewayEthereum::best_index();
+        assert_eq!(genesis.number, 11_000_000);
+        assert_eq!(XGatewayEthereum::confirmed_index(), None);
+
+        // only the trustee multisig could relay headers, a single trustee is not enough
+        let raw = encode_header(genesis.hash, 11_000_001, 1_600_000_013, H256::zero());
+        assert_noop!(
+            XGatewayEthereum::push_header(Origin::signed(alice()), raw.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // invalid number and timestamp
+        assert_noop!(
+            XGatewayEthereum::push_header(
+                Origin::root(),
+                encode_header(genesis.hash, 11_000_002, 1_600_000_013, H256::zero())
+            ),
+            XGatewayEthereumErr::InvalidHeaderNumber
+        );
+        assert_noop!(
+            XGatewayEthereum::push_header(
+                Origin::root(),
+                encode_header(genesis.hash, 11_000_001, 1_600_000_000, H256::zero())
+            ),
+            XGatewayEthereumErr::InvalidTimestamp
+        );
+        assert_noop!(
+            XGatewayEthereum::push_header(
+                Origin::root(),
+                encode_header(
+                    H256::repeat_byte(2),
+                    11_000_001,
+                    1_600_000_013,
+                    H256::zero()
+                )
+            ),
+            XGatewayEthereumErr::PrevHeaderNotExisted
+        );
+
+        let h1 = push_header(genesis.hash, 11_000_001, 1_600_000_013, H256::zero());
+        assert_noop!(
+            XGatewayEthereum::push_header(Origin::root(), raw),
+            XGatewayEthereumErr::ExistingHeader
+        );
+        let h2 = push_header(h1, 11_000_002, 1_600_000_026, H256::zero());
+        assert_eq!(
+            XGatewayEthereum::confirmed_index().unwrap().hash,
+            genesis.hash
+        );
+        let h3 = push_header(h2, 11_000_003, 1_600_000_039, H256::zero());
+        assert_eq!(XGatewayEthereum::best_index().hash, h3);
+        assert_eq!(XGatewayEthereum::confirmed_index().unwrap().hash, h1);
+
+        // fork from h1, switch the main chain when it becomes the longest
+        let f2 = push_header(h1, 11_000_002, 1_600_000_027, H256::zero());
+        let f3 = push_header(f2, 11_000_003, 1_600_000_040, H256::zero());
+        assert_eq!(XGatewayEthereum::best_index().hash, h3);
+        assert!(!XGatewayEthereum::main_chain(&f2));
+        let f4 = push_header(f3, 11_000_004, 1_600_000_053, H256::zero());
+        assert_eq!(XGatewayEthereum::best_index().hash, f4);
+        assert_eq!(XGatewayEthereum::confirmed_index().unwrap().hash, f2);
+        for hash in &[h1, f2, f3, f4] {
+            assert!(XGatewayEthereum::main_chain(hash));
+        }
+        assert!(!XGatewayEthereum::main_chain(&h2));
+        assert!(!XGatewayEthereum::main_chain(&h3));
+
+        // the fork before the confirmed header is rejected
+        assert_noop!(
+            XGatewayEthereum::push_header(
+                Origin::root(),
+                encode_header(h3, 11_000_004, 1_600_000_052, H256::zero())
+            ),
+            XGatewayEthereumErr::AncientFork
+        );
+    })
+}
+
+#[test]
+fn test_push_receipt_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        let token = H160::repeat_byte(0xda);
+        assert_ok!(XGatewayEthereum::set_erc20_asset(
+            RawOrigin::Root.into(),
+            USDT,
+            Some(token)
+        ));
+
+        let logs = vec![
+            deposit_log(H160::zero(), &alice(), 1_000_000_000),
+            deposit_log(token, &bob(), 2_000_000),
+            // emitted by other contract
+            EthLog {
+                address: H160::repeat_byte(0x22),
+                ..deposit_log(H160::zero(), &alice(), 1_000_000_000)
+            },
+        ];
+        let tx_index = 3u64;
+        let (root, proof) = single_leaf_proof(&rlp::encode(&tx_index), &encode_receipt(&logs));
+
+        // the block is not confirmed yet
+        let block_hash = push_best_header(root);
+        assert_noop!(
+            XGatewayEthereum::push_receipt(
+                Origin::signed(bob()),
+                block_hash,
+                tx_index,
+                proof.clone()
+            ),
+            XGatewayEthereumErr::UnconfirmedReceipt
+        );
+        push_best_header(H256::zero());
+        push_best_header(H256::zero());
+
+        // bad proof
+        assert_noop!(
+            XGatewayEthereum::push_receipt(Origin::signed(bob()), block_hash, 4, proof.clone()),
+            XGatewayEthereumErr::BadReceiptProof
+        );
+        assert_ok!(XGatewayEthereum::push_receipt(
+            Origin::signed(bob()),
+            block_hash,
+            tx_index,
+            proof.clone()
+        ));
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 1_000_000_000);
+        assert_eq!(XAssets::usable_balance(&bob(), &USDT), 2_000_000);
+        assert!(XGatewayEthereum::processed_receipt((block_hash, tx_index)));
+        // the deposits are recorded without the tx hash
+        assert!(XGatewayRecords::deposits_by_txid(block_hash).is_empty());
+        let records = XGatewayRecords::deposits_of(bob(), 0, 10);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1.txid, None);
+
+        // replay
+        assert_noop!(
+            XGatewayEthereum::push_receipt(Origin::signed(bob()), block_hash, tx_index, proof),
+            XGatewayEthereumErr::ReplayedReceipt
+        );
+
+        // no log could be processed, the token is unknown
+        let (_, result) = push_receipt(&[deposit_log(H160::repeat_byte(0xee), &alice(), 1)]);
+        assert_eq!(
+            result,
+            Err(XGatewayEthereumErr::ProcessReceiptFailed.into())
+        );
+    })
+}
+
+#[test]
+fn test_push_receipt_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        let fee = 1_000_000;
+        assert_ok!(XGatewayEthereum::set_withdrawal_fee(
+            RawOrigin::Root.into(),
+            X_ETH,
+            fee
+        ));
+        assert_eq!(
+            XGatewayEthereum::withdrawal_limit(&X_ETH)
+                .unwrap()
+                .minimal_withdrawal,
+            fee * 3 / 2
+        );
+        // USDT is not bound to any token
+        assert!(XGatewayEthereum::withdrawal_limit(&USDT).is_err());
+
+        let to = H160::from(hex!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+        let balance = 100_000_000u128;
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_ETH, balance));
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice(),
+            X_ETH,
+            balance,
+            b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_vec(),
+            b"".to_vec().into()
+        ));
+        assert_ok!(XGatewayEthereum::process_withdrawals(
+            RawOrigin::Root.into(),
+            vec![0]
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+
+        // mismatched receiver or amount
+        let other = H160::repeat_byte(0x33);
+        let (_, result) = push_receipt(&[withdrawal_log(0, H160::zero(), other, 99_000_000)]);
+        assert_eq!(
+            result,
+            Err(XGatewayEthereumErr::ProcessReceiptFailed.into())
+        );
+        let (_, result) = push_receipt(&[withdrawal_log(0, H160::zero(), to, 98_999_999)]);
+        assert_eq!(
+            result,
+            Err(XGatewayEthereumErr::ProcessReceiptFailed.into())
+        );
+
+        let (_, result) = push_receipt(&[withdrawal_log(0, H160::zero(), to, 99_000_000)]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(XGatewayRecords::pending_withdrawals(0), None);
+        assert_eq!(XAssets::total_issuance(&X_ETH), 0);
+    })
+}
+
+#[test]
+fn test_parse_signers_changed() {
+    let signers = vec![H160::repeat_byte(1), H160::repeat_byte(2)];
+    let mut data = H256::from_low_u64_be(32).as_bytes().to_vec();
+    data.extend_from_slice(H256::from_low_u64_be(2).as_bytes());
+    for signer in &signers {
+        data.extend_from_slice(word(*signer).as_bytes());
+    }
+    let log = EthLog {
+        address: trustee_contract(),
+        topics: vec![event_topic(SIGNERS_CHANGED_EVENT)],
+        data: data.clone(),
+    };
+    assert_eq!(
+        ContractLog::<AccountId32>::parse(&log),
+        Some(ContractLog::SignersChanged(signers))
+    );
+
+    // inconsistent length
+    data.truncate(data.len() - 32);
+    let log = EthLog { data, ..log };
+    assert_eq!(ContractLog::<AccountId32>::parse(&log), None);
+}
+
+#[test]
+fn test_signers_changed() {
+    ExtBuilder::default().build_and_execute(|| {
+        let signers = current_trustee_session::<Test>()
+            .unwrap()
+            .hot_address
+            .signers;
+        let mut data = H256::from_low_u64_be(32).as_bytes().to_vec();
+        data.extend_from_slice(H256::from_low_u64_be(signers.len() as u64).as_bytes());
+        for signer in &signers {
+            data.extend_from_slice(word(*signer).as_bytes());
+        }
+        let log = EthLog {
+            address: trustee_contract(),
+            topics: vec![event_topic(SIGNERS_CHANGED_EVENT)],
+            data,
+        };
+        let (_, result) = push_receipt(&[log]);
+        assert_eq!(result, Ok(()));
+        // still the same session
+        assert_eq!(
+            current_trustee_session::<Test>().unwrap().trustee_list,
+            vec![alice(), bob(), charlie()]
+        );
+    })
+}
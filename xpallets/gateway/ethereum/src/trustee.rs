@@ -0,0 +1,113 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::H160;
+use sp_io::hashing::keccak_256;
+use sp_std::{convert::TryFrom, prelude::*};
+
+use xp_logging::{error, info};
+use xpallet_gateway_common::{
+    traits::{TrusteeForChain, TrusteeSession},
+    trustees::ethereum::{EthTrusteeAddrInfo, EthTrusteeType},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+
+use crate::{Error, Module, Trait};
+
+pub fn current_trustee_session<T: Trait>(
+) -> Result<TrusteeSessionInfo<T::AccountId, EthTrusteeAddrInfo>, DispatchError> {
+    T::TrusteeSessionProvider::current_trustee_session()
+}
+
+/// Returns the Ethereum address of the compressed secp256k1 public key.
+pub fn eth_address(pubkey: &EthTrusteeType) -> Option<H160> {
+    let mut compressed = [0u8; 33];
+    if pubkey.0.len() != compressed.len() {
+        return None;
+    }
+    compressed.copy_from_slice(&pubkey.0);
+    let public = secp256k1::PublicKey::parse_compressed(&compressed).ok()?;
+    // the address is the last 20 bytes of the hash of the uncompressed key (without prefix)
+    let hash = keccak_256(&public.serialize()[1..]);
+    Some(H160::from_slice(&hash[12..]))
+}
+
+fn check_keys<T: Trait>(keys: &[EthTrusteeType]) -> DispatchResult {
+    let has_duplicate = (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+    if has_duplicate {
+        error!("[generate_new_trustees] Keys contains duplicate pubkey");
+        return Err(Error::<T>::DuplicatedKeys.into());
+    }
+    Ok(())
+}
+
+fn signers<T: Trait>(keys: &[EthTrusteeType]) -> Result<Vec<H160>, DispatchError> {
+    keys.iter()
+        .map(|key| eth_address(key).ok_or_else(|| Error::<T>::InvalidPublicKey.into()))
+        .collect()
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, EthTrusteeType, EthTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        let trustee_type = EthTrusteeType::try_from(raw_addr.to_vec())
+            .map_err(|_| Error::<T>::InvalidPublicKey)?;
+        if eth_address(&trustee_type).is_none() {
+            error!(
+                "[check_trustee_entity] Not a valid secp256k1 public key:{:?}",
+                raw_addr
+            );
+            return Err(Error::<T>::InvalidPublicKey.into());
+        }
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<EthTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<EthTrusteeType>, Vec<EthTrusteeType>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_keys::<T>(&hot_keys)?;
+        check_keys::<T>(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people, \
+                can't generate trustee addr",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let sig_num = two_thirds_unsafe(trustees.len() as u32);
+        let hot_address = EthTrusteeAddrInfo {
+            signers: signers::<T>(&hot_keys)?,
+        };
+        let cold_address = EthTrusteeAddrInfo {
+            signers: signers::<T>(&cold_keys)?,
+        };
+
+        info!(
+            "[generate_trustee_session_info] hot_addr:{:?}, cold_addr:{:?}, trustee_list:{:?}",
+            hot_address, cold_address, trustees
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold: sig_num as u16,
+            hot_address,
+            cold_address,
+        })
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use rlp::{DecoderError, Rlp};
+
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The fields of Ethereum header used by the relay.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthHeader {
+    pub parent_hash: H256,
+    pub receipts_root: H256,
+    pub difficulty: U256,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+}
+
+impl EthHeader {
+    /// Decodes the RLP encoded header, returns the hash and the header.
+    ///
+    /// The fields appended by the later hard forks (e.g. `baseFeePerGas`) are allowed but ignored.
+    pub fn decode_rlp(raw: &[u8]) -> Result<(H256, Self), DecoderError> {
+        let rlp = Rlp::new(raw);
+        ensure_exact_list(&rlp, raw)?;
+        if rlp.item_count()? < 15 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let header = Self {
+            parent_hash: decode_h256(&rlp.at(0)?)?,
+            receipts_root: decode_h256(&rlp.at(5)?)?,
+            difficulty: decode_u256(&rlp.at(7)?)?,
+            number: rlp.val_at(8)?,
+            gas_limit: rlp.val_at(9)?,
+            gas_used: rlp.val_at(10)?,
+            timestamp: rlp.val_at(11)?,
+        };
+        Ok((H256(sp_io::hashing::keccak_256(raw)), header))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthHeaderIndex {
+    pub hash: H256,
+    pub number: u64,
+}
+
+/// The log emitted by the contract.
+#[derive(PartialEq, Eq, Clone, Default, RuntimeDebug)]
+pub struct EthLog {
+    pub address: H160,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+/// The transaction receipt, only the status and logs are kept.
+#[derive(PartialEq, Eq, Clone, Default, RuntimeDebug)]
+pub struct EthReceipt {
+    pub success: bool,
+    pub logs: Vec<EthLog>,
+}
+
+impl EthReceipt {
+    /// Decodes the receipt, which is the value of the receipt trie.
+    ///
+    /// The typed receipt (EIP-2718) is `type || rlp([status, gas, bloom, logs])`, and the
+    /// legacy receipt is `rlp([status, gas, bloom, logs])`.
+    pub fn decode_rlp(raw: &[u8]) -> Result<Self, DecoderError> {
+        let payload = match raw.first() {
+            Some(tx_type) if *tx_type < 0x7f => &raw[1..],
+            _ => raw,
+        };
+        let rlp = Rlp::new(payload);
+        ensure_exact_list(&rlp, payload)?;
+        if rlp.item_count()? != 4 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        // the receipts before Byzantium have the state root instead of the status,
+        // which are treated as failed.
+        let success = rlp.at(0)?.data()? == &[1u8][..];
+        let logs = rlp
+            .at(3)?
+            .iter()
+            .map(|log| {
+                if log.item_count()? != 3 {
+                    return Err(DecoderError::RlpIncorrectListLen);
+                }
+                let address = log.at(0)?.data()?;
+                if address.len() != 20 {
+                    return Err(DecoderError::RlpInvalidLength);
+                }
+                let topics = log
+                    .at(1)?
+                    .iter()
+                    .map(|topic| decode_h256(&topic))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(EthLog {
+                    address: H160::from_slice(address),
+                    topics,
+                    data: log.at(2)?.data()?.to_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { success, logs })
+    }
+}
+
+fn ensure_exact_list(rlp: &Rlp, raw: &[u8]) -> Result<(), DecoderError> {
+    if !rlp.is_list() {
+        return Err(DecoderError::RlpExpectedToBeList);
+    }
+    // reject the trailing bytes
+    if rlp.payload_info()?.total() != raw.len() {
+        return Err(DecoderError::RlpInconsistentLengthAndData);
+    }
+    Ok(())
+}
+
+fn decode_h256(rlp: &Rlp) -> Result<H256, DecoderError> {
+    let data = rlp.data()?;
+    if data.len() != 32 {
+        return Err(DecoderError::RlpInvalidLength);
+    }
+    Ok(H256::from_slice(data))
+}
+
+fn decode_u256(rlp: &Rlp) -> Result<U256, DecoderError> {
+    let data = rlp.data()?;
+    if data.len() > 32 {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    if data.first() == Some(&0) {
+        return Err(DecoderError::RlpInvalidIndirection);
+    }
+    Ok(U256::from_big_endian(data))
+}
+
+/// Encode the header with the given fields, the other fields are filled with zero.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn encode_header(
+    parent_hash: H256,
+    number: u64,
+    timestamp: u64,
+    receipts_root: H256,
+) -> Vec<u8> {
+    let mut stream = rlp::RlpStream::new_list(15);
+    stream.append(&parent_hash.as_bytes().to_vec());
+    // ommers hash, beneficiary, state root, transactions root
+    stream.append(&vec![0u8; 32]);
+    stream.append(&vec![0u8; 20]);
+    stream.append(&vec![0u8; 32]);
+    stream.append(&vec![0u8; 32]);
+    stream.append(&receipts_root.as_bytes().to_vec());
+    // logs bloom, difficulty
+    stream.append(&vec![0u8; 256]);
+    stream.append(&0u64);
+    stream.append(&number);
+    // gas limit, gas used
+    stream.append(&30_000_000u64);
+    stream.append(&21_000u64);
+    stream.append(&timestamp);
+    // extra data, mix hash, nonce
+    stream.append(&Vec::<u8>::new());
+    stream.append(&vec![0u8; 32]);
+    stream.append(&vec![0u8; 8]);
+    stream.out()
+}
+
+/// Encode the successful EIP-1559 (type 2) receipt with the logs.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn encode_receipt(logs: &[EthLog]) -> Vec<u8> {
+    let mut stream = rlp::RlpStream::new_list(4);
+    stream.append(&1u64);
+    stream.append(&21_000u64);
+    stream.append(&vec![0u8; 256]);
+    stream.begin_list(logs.len());
+    for log in logs {
+        stream.begin_list(3);
+        stream.append(&log.address.as_bytes().to_vec());
+        stream.begin_list(log.topics.len());
+        for topic in &log.topics {
+            stream.append(&topic.as_bytes().to_vec());
+        }
+        stream.append(&log.data);
+    }
+    let mut receipt = vec![2u8];
+    receipt.extend(stream.out());
+    receipt
+}
@@ -0,0 +1,165 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_core::{crypto::key_types::ACCOUNT, ed25519, H256};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
+use xpallet_assets_registrar::{AssetInfo, Module as XAssetsRegistrar};
+use xpallet_gateway_common::{types::TrusteeInfoConfig, Module as XGatewayCommon};
+use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalRecordId, WithdrawalState};
+
+use crate::{Call, LocalDeposit, Module, Trait};
+
+const ASSET_ID: AssetId = xp_protocol::X_LOCAL;
+
+fn ensure_local_asset<T: Trait>() {
+    if !XAssetsRegistrar::<T>::exists(&ASSET_ID) {
+        let info = AssetInfo::new::<T>(
+            b"XLOCAL".to_vec(),
+            b"ChainX Local".to_vec(),
+            Chain::Local,
+            xp_protocol::LOCAL_DECIMALS,
+            b"ChainX's dummy local chain asset".to_vec(),
+        )
+        .unwrap();
+        XAssetsRegistrar::<T>::register(RawOrigin::Root.into(), ASSET_ID, info, true, false)
+            .unwrap();
+    }
+}
+
+/// Setup `n` trustees whose hot keys are generated in the keystore, returns the hot keys.
+fn setup_trustees<T: Trait>(n: u32) -> Vec<ed25519::Public> {
+    XGatewayCommon::<T>::set_trustee_info_config(
+        RawOrigin::Root.into(),
+        Chain::Local,
+        TrusteeInfoConfig {
+            min_trustee_count: 3,
+            max_trustee_count: 15,
+        },
+    )
+    .unwrap();
+    let mut trustees = Vec::with_capacity(n as usize);
+    let mut keys = Vec::with_capacity(n as usize);
+    for i in 0..n {
+        let trustee: T::AccountId = account("trustee", i, 0);
+        let hot = sp_io::crypto::ed25519_generate(ACCOUNT, None);
+        let cold = sp_io::crypto::ed25519_generate(ACCOUNT, None);
+        XGatewayCommon::<T>::setup_trustee_impl(
+            trustee.clone(),
+            Chain::Local,
+            b"about".to_vec(),
+            hot.0.to_vec(),
+            cold.0.to_vec(),
+        )
+        .unwrap();
+        trustees.push(trustee);
+        keys.push(hot);
+    }
+    XGatewayCommon::<T>::transition_trustee_session(RawOrigin::Root.into(), Chain::Local, trustees)
+        .unwrap();
+    keys
+}
+
+fn prepare_withdrawals<T: Trait>(n: u32) -> Vec<WithdrawalRecordId> {
+    let caller: T::AccountId = whitelisted_caller();
+    ensure_local_asset::<T>();
+    let balance: BalanceOf<T> = 100_000_000_000u128.saturated_into();
+    let mut ids = Vec::with_capacity(n as usize);
+    for _ in 0..n {
+        ids.push(XGatewayRecords::<T>::id());
+        XGatewayRecords::<T>::deposit(&caller, ASSET_ID, balance).unwrap();
+        XGatewayRecords::<T>::withdraw(
+            &caller,
+            ASSET_ID,
+            balance,
+            b"local".to_vec(),
+            b"".to_vec().into(),
+        )
+        .unwrap();
+    }
+    ids
+}
+
+benchmarks! {
+    _{ }
+
+    deposit {
+        let n in 3 .. 15 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_local_asset::<T>();
+        let keys = setup_trustees::<T>(n);
+        let amount: BalanceOf<T> = 10_000_000_000u128.saturated_into();
+        let deposit = LocalDeposit {
+            tx_hash: H256::repeat_byte(1),
+            who: caller.clone(),
+            amount,
+        };
+        let message = deposit.encode();
+        let signatures = keys
+            .into_iter()
+            .map(|key| {
+                let signature = sp_io::crypto::ed25519_sign(ACCOUNT, &key, &message).unwrap();
+                (key, signature)
+            })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Signed(caller.clone()), deposit, signatures)
+    verify {
+        assert!(Module::<T>::processed_tx(&H256::repeat_byte(1)));
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), amount);
+    }
+
+    process_withdrawals {
+        let n in 1 .. 100 => ();
+
+        let ids = prepare_withdrawals::<T>(n);
+    }: _(RawOrigin::Root, ids.clone())
+    verify {
+        for id in ids {
+            assert_eq!(XGatewayRecords::<T>::state_of(id), Some(WithdrawalState::Processing));
+        }
+    }
+
+    finish_withdrawals {
+        let n in 1 .. 100 => ();
+
+        let ids = prepare_withdrawals::<T>(n);
+        XGatewayRecords::<T>::process_withdrawals(&ids, Chain::Local).unwrap();
+        let tx_hash = H256::repeat_byte(2);
+    }: _(RawOrigin::Root, tx_hash, ids.clone())
+    verify {
+        assert!(Module::<T>::processed_tx(&tx_hash));
+        for id in ids {
+            assert_eq!(XGatewayRecords::<T>::state_of(id), None);
+        }
+    }
+
+    set_withdrawal_fee {
+        let fee: BalanceOf<T> = 1_000_000_000u128.saturated_into();
+    }: _(RawOrigin::Root, fee)
+    verify {
+        assert_eq!(Module::<T>::withdrawal_fee(), fee);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_deposit::<Test>());
+            assert_ok!(test_benchmark_process_withdrawals::<Test>());
+            assert_ok!(test_benchmark_finish_withdrawals::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_fee::<Test>());
+        });
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is a dummy gateway of the local chain, only for the tests and the development
+//! network, which shows the bridging without a real chain like Bitcoin.
+//!
+//! The local chain has no headers, a deposit is proved by the ed25519 signatures of the hot
+//! trustee keys of current session, and a withdrawal is finished by the trustees directly.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod trustee;
+mod types;
+pub mod weights;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::Encode;
+use sp_core::{ed25519, H256};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    traits::EnsureOrigin,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{traits::TrusteeSession, trustees::local::LocalTrusteeAddrInfo};
+use xpallet_gateway_records::WithdrawalRecordId;
+use xpallet_support::try_addr;
+
+pub use self::types::LocalDeposit;
+pub use self::weights::WeightInfo;
+
+/// The max length of the withdrawal address on the local chain.
+pub const MAX_ADDRESS_LEN: usize = 64;
+
+pub trait Trait:
+    xpallet_assets::Trait + xpallet_gateway_records::Trait + xpallet_gateway_common::Trait
+{
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    type TrusteeSessionProvider: TrusteeSession<Self::AccountId, LocalTrusteeAddrInfo>;
+    type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Local module
+    pub enum Error for Module<T: Trait> {
+        /// invalid local chain address
+        InvalidAddress,
+        /// reject replay processed transaction
+        ReplayedTx,
+        /// the signer is not the hot trustee key or the signature is invalid
+        InvalidSignature,
+        /// the same trustee key signed more than once
+        DuplicatedSigner,
+        /// the signatures are less than the threshold of current trustee session
+        InsufficientSignatures,
+        /// invalid ed25519 public key
+        InvalidPublicKey,
+        /// duplicated pubkey for trustees
+        DuplicatedKeys,
+        /// invalid trustee count
+        InvalidTrusteeCount,
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>
+    {
+        /// An account deposited some X-LOCAL. [tx_hash, who, amount]
+        Deposited(H256, AccountId, Balance),
+        /// A withdrawal was sent by the trustees. [tx_hash, withdrawal_id]
+        Withdrawn(H256, WithdrawalRecordId),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayLocal {
+        /// mark the transaction of the local chain has been processed
+        pub ProcessedTxs get(fn processed_tx): map hasher(identity) H256 => bool;
+
+        /// the withdrawal fee of X-LOCAL
+        pub WithdrawalFee get(fn withdrawal_fee) config(): BalanceOf<T>;
+    }
+    add_extra_genesis {
+        config(genesis_trustees): Vec<T::AccountId>;
+        build(|config| {
+            if !config.genesis_trustees.is_empty() {
+                T::TrusteeSessionProvider::genesis_trustee(Module::<T>::chain(), &config.genesis_trustees);
+            }
+        })
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+        fn deposit_event() = default;
+
+        /// Credit the `deposit` of the local chain, `signatures` are the ed25519 signatures of
+        /// the encoded `deposit` by the hot trustee keys of current session.
+        #[weight = <T as Trait>::WeightInfo::deposit(signatures.len() as u32)]
+        pub fn deposit(
+            origin,
+            deposit: LocalDeposit<T::AccountId, BalanceOf<T>>,
+            signatures: Vec<(ed25519::Public, ed25519::Signature)>
+        ) -> DispatchResult {
+            let _from = ensure_signed(origin)?;
+            debug!("[deposit] from:{:?}, deposit:{:?}", _from, deposit);
+
+            ensure!(!Self::processed_tx(&deposit.tx_hash), Error::<T>::ReplayedTx);
+            trustee::verify_signatures::<T>(&deposit.encode(), &signatures)?;
+
+            Self::apply_deposit(deposit)
+        }
+
+        /// Mark the withdrawals as processing before sending them by the trustees.
+        #[weight = <T as Trait>::WeightInfo::process_withdrawals(ids.len() as u32)]
+        pub fn process_withdrawals(origin, ids: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            xpallet_gateway_records::Module::<T>::process_withdrawals(&ids, Chain::Local)
+        }
+
+        /// Finish the processing withdrawals `ids` which are sent by the local chain transaction
+        /// `tx_hash`.
+        #[weight = <T as Trait>::WeightInfo::finish_withdrawals(ids.len() as u32)]
+        pub fn finish_withdrawals(origin, tx_hash: H256, ids: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            ensure!(!Self::processed_tx(&tx_hash), Error::<T>::ReplayedTx);
+
+            with_transaction_result(|| {
+                for id in ids {
+                    xpallet_gateway_records::Module::<T>::finish_withdrawal(id, Some(Chain::Local))?;
+                    xpallet_gateway_records::Module::<T>::set_withdrawal_settlement(id, tx_hash, None);
+                    info!("[finish_withdrawals] Finish withdrawal:{}, tx:{:?}", id, tx_hash);
+                    Self::deposit_event(Event::<T>::Withdrawn(tx_hash, id));
+                }
+                ProcessedTxs::insert(&tx_hash, true);
+                Ok(())
+            })
+        }
+
+        /// Set the withdrawal fee of X-LOCAL.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee()]
+        pub fn set_withdrawal_fee(origin, #[compact] fee: BalanceOf<T>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            WithdrawalFee::<T>::put(fee);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_LOCAL;
+
+    fn chain() -> Chain {
+        Chain::Local
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        if addr.is_empty() || addr.len() > MAX_ADDRESS_LEN {
+            error!(
+                "[check_addr] Invalid local chain address:{:?}",
+                try_addr(addr)
+            );
+            return Err(Error::<T>::InvalidAddress.into());
+        }
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        if *asset_id != Self::ASSET_ID {
+            return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
+        }
+        let fee = Self::withdrawal_fee();
+        let limit = WithdrawalLimit::<BalanceOf<T>> {
+            minimal_withdrawal: fee * 3u32.saturated_into() / 2u32.saturated_into(),
+            fee,
+        };
+        Ok(limit)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    fn apply_deposit(deposit: LocalDeposit<T::AccountId, BalanceOf<T>>) -> DispatchResult {
+        let LocalDeposit {
+            tx_hash,
+            who,
+            amount,
+        } = deposit;
+        with_transaction_result(|| {
+            xpallet_gateway_records::Module::<T>::deposit_with_tx(
+                &who,
+                Self::ASSET_ID,
+                amount,
+                Some(tx_hash),
+                None,
+            )?;
+            ProcessedTxs::insert(&tx_hash, true);
+            info!(
+                "[apply_deposit] Deposit tx:{:?}, who:{:?}, amount:{:?}",
+                tx_hash, who, amount
+            );
+            Self::deposit_event(Event::<T>::Deposited(tx_hash, who, amount));
+            Ok(())
+        })
+    }
+}
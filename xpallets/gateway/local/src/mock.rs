@@ -0,0 +1,340 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::sync::Arc;
+
+use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_keyring::{ed25519, sr25519};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, DispatchError, DispatchResult, Perbill,
+};
+
+use chainx_primitives::AssetId;
+use xp_assets_registrar::Chain;
+pub use xp_protocol::{X_BTC, X_DOT, X_ETH, X_LOCAL};
+use xpallet_assets::{AssetRestrictions, ChainT};
+use xpallet_assets_registrar::AssetInfo;
+use xpallet_gateway_common::{
+    traits::{AddressOwnership, TrusteeForChain},
+    trustees::{
+        bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
+        ethereum::{EthTrusteeAddrInfo, EthTrusteeType},
+        local::{LocalTrusteeMultisig, LocalTrusteeSessionManager},
+        polkadot::{DotTrusteeAddrInfo, DotTrusteeType},
+    },
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+};
+
+use crate::{Error, GenesisConfig, Module, Trait};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+pub(crate) type Amount = i128;
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+// assets
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = ();
+    type Currency = Balances;
+    type Amount = Amount;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_records::Trait for Test {
+    type Event = ();
+    type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+    type WithdrawalHistoryRetention = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_common::Trait for Test {
+    type Event = ();
+    type Validator = ();
+    type DetermineMultisigAddress = ();
+    type Bitcoin = MockBitcoin;
+    type BitcoinTrustee = MockBitcoin;
+    type Ethereum = MockEthereum;
+    type EthereumTrustee = MockEthereum;
+    type Polkadot = MockPolkadot;
+    type PolkadotTrustee = MockPolkadot;
+    type Local = XGatewayLocal;
+    type LocalTrustee = XGatewayLocal;
+    type WeightInfo = ();
+}
+
+pub struct MockBitcoin;
+impl ChainT<Balance> for MockBitcoin {
+    const ASSET_ID: AssetId = X_BTC;
+
+    fn chain() -> Chain {
+        Chain::Bitcoin
+    }
+}
+impl AddressOwnership<AccountId> for MockBitcoin {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+}
+impl TrusteeForChain<AccountId, BtcTrusteeType, BtcTrusteeAddrInfo> for MockBitcoin {
+    fn check_trustee_entity(_: &[u8]) -> Result<BtcTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<BtcTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, BtcTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+pub struct MockEthereum;
+impl ChainT<Balance> for MockEthereum {
+    const ASSET_ID: AssetId = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+impl TrusteeForChain<AccountId, EthTrusteeType, EthTrusteeAddrInfo> for MockEthereum {
+    fn check_trustee_entity(_: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+pub struct MockPolkadot;
+impl ChainT<Balance> for MockPolkadot {
+    const ASSET_ID: AssetId = X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+impl AddressOwnership<AccountId> for MockPolkadot {
+    fn verify_address_ownership(_: &AccountId, _: &[u8], _: &[u8], _: &[u8]) -> DispatchResult {
+        Err("NotSupportedChain".into())
+    }
+}
+impl TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo> for MockPolkadot {
+    fn check_trustee_entity(_: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+
+    fn generate_trustee_session_info(
+        _: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Err("NotSupportedChain".into())
+    }
+}
+
+impl Trait for Test {
+    type Event = ();
+    type TrusteeSessionProvider = LocalTrusteeSessionManager<Test>;
+    type TrusteeOrigin = EnsureSignedBy<LocalTrusteeMultisig<Test>, AccountId>;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XGatewayRecords = xpallet_gateway_records::Module<Test>;
+pub type XGatewayCommon = xpallet_gateway_common::Module<Test>;
+pub type XGatewayLocal = Module<Test>;
+pub type XGatewayLocalErr = Error<Test>;
+
+pub(crate) fn xlocal() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_LOCAL,
+        AssetInfo::new::<Test>(
+            b"X-LOCAL".to_vec(),
+            b"X-LOCAL".to_vec(),
+            Chain::Local,
+            xp_protocol::LOCAL_DECIMALS,
+            b"ChainX's dummy local chain asset".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+pub const WITHDRAWAL_FEE: Balance = 100_000_000;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let (asset_id, info, restrictions) = xlocal();
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: vec![(asset_id, info, true, true)],
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions: vec![(asset_id, restrictions)],
+            endowed: Default::default(),
+        }
+        .assimilate_storage(&mut storage);
+
+        let info = trustees_info();
+        let genesis_trustees = info[0].2.iter().map(|i| (i.0).clone()).collect();
+
+        let _ = xpallet_gateway_common::GenesisConfig::<Test> { trustees: info }
+            .assimilate_storage(&mut storage);
+
+        let _ = GenesisConfig::<Test> {
+            genesis_trustees,
+            withdrawal_fee: WITHDRAWAL_FEE,
+        }
+        .assimilate_storage(&mut storage);
+
+        let mut ext = sp_io::TestExternalities::new(storage);
+        // the keystore is used to generate the trustee keys in the benchmarks
+        ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+        ext
+    }
+
+    pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
+
+pub fn alice() -> AccountId32 {
+    sr25519::Keyring::Alice.to_account_id()
+}
+pub fn bob() -> AccountId32 {
+    sr25519::Keyring::Bob.to_account_id()
+}
+pub fn charlie() -> AccountId32 {
+    sr25519::Keyring::Charlie.to_account_id()
+}
+pub fn dave() -> AccountId32 {
+    sr25519::Keyring::Dave.to_account_id()
+}
+
+/// The trustees use their ed25519 public keys as the hot and cold keys.
+pub fn trustees() -> Vec<(AccountId32, Vec<u8>, Vec<u8>, Vec<u8>)> {
+    vec![
+        (
+            alice(),
+            b"Alice".to_vec(),
+            ed25519::Keyring::Alice.public().0.to_vec(),
+            ed25519::Keyring::Alice.public().0.to_vec(),
+        ),
+        (
+            bob(),
+            b"Bob".to_vec(),
+            ed25519::Keyring::Bob.public().0.to_vec(),
+            ed25519::Keyring::Bob.public().0.to_vec(),
+        ),
+        (
+            charlie(),
+            b"Charlie".to_vec(),
+            ed25519::Keyring::Charlie.public().0.to_vec(),
+            ed25519::Keyring::Charlie.public().0.to_vec(),
+        ),
+    ]
+}
+
+fn trustees_info() -> Vec<(
+    Chain,
+    TrusteeInfoConfig,
+    Vec<(AccountId, Vec<u8>, Vec<u8>, Vec<u8>)>,
+)> {
+    let local_config = TrusteeInfoConfig {
+        min_trustee_count: 3,
+        max_trustee_count: 15,
+    };
+    vec![(Chain::Local, local_config, trustees())]
+}
@@ -0,0 +1,271 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_core::{ed25519::Signature, H256};
+use sp_keyring::ed25519::Keyring;
+
+use codec::Encode;
+use xpallet_assets::{Chain, ChainT};
+use xpallet_gateway_records::{WithdrawalSettlement, WithdrawalState};
+
+use crate::mock::*;
+use crate::{
+    trustee::{current_trustee_session, trustee_address},
+    LocalDeposit,
+};
+
+type Deposit = LocalDeposit<AccountId, Balance>;
+
+fn sign(deposit: &Deposit, signers: &[Keyring]) -> Vec<(sp_core::ed25519::Public, Signature)> {
+    let message = deposit.encode();
+    signers
+        .iter()
+        .map(|signer| (signer.public(), signer.sign(&message)))
+        .collect()
+}
+
+fn new_deposit(tx: u8, who: AccountId, amount: Balance) -> Deposit {
+    LocalDeposit {
+        tx_hash: H256::repeat_byte(tx),
+        who,
+        amount,
+    }
+}
+
+#[test]
+fn test_genesis_trustees() {
+    ExtBuilder::default().build_and_execute(|| {
+        let session = current_trustee_session::<Test>().unwrap();
+        assert_eq!(session.threshold, 2);
+        assert_eq!(session.trustee_list.len(), 3);
+        let mut signers = vec![
+            Keyring::Alice.public(),
+            Keyring::Bob.public(),
+            Keyring::Charlie.public(),
+        ];
+        signers.sort();
+        assert_eq!(session.hot_address.signers, signers);
+        assert_eq!(
+            session.hot_address.addr,
+            trustee_address(&signers, session.threshold)
+        );
+    })
+}
+
+#[test]
+fn test_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        let amount = 10_000_000_000;
+        let deposit = new_deposit(1, dave(), amount);
+
+        // 1 of 3 signatures is not enough
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(alice()),
+                deposit.clone(),
+                sign(&deposit, &[Keyring::Alice])
+            ),
+            XGatewayLocalErr::InsufficientSignatures
+        );
+        // the same signer twice
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(alice()),
+                deposit.clone(),
+                sign(&deposit, &[Keyring::Alice, Keyring::Alice])
+            ),
+            XGatewayLocalErr::DuplicatedSigner
+        );
+        // not a trustee key
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(alice()),
+                deposit.clone(),
+                sign(&deposit, &[Keyring::Alice, Keyring::Dave])
+            ),
+            XGatewayLocalErr::InvalidSignature
+        );
+        // the signature of another deposit
+        let other = new_deposit(1, dave(), amount + 1);
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(alice()),
+                deposit.clone(),
+                sign(&other, &[Keyring::Alice, Keyring::Bob])
+            ),
+            XGatewayLocalErr::InvalidSignature
+        );
+
+        assert_ok!(XGatewayLocal::deposit(
+            Origin::signed(alice()),
+            deposit.clone(),
+            sign(&deposit, &[Keyring::Alice, Keyring::Bob])
+        ));
+        assert_eq!(XAssets::usable_balance(&dave(), &X_LOCAL), amount);
+        assert!(XGatewayLocal::processed_tx(&deposit.tx_hash));
+        let records = XGatewayRecords::deposits_by_txid(deposit.tx_hash);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1.depositor, dave());
+
+        // replay
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(alice()),
+                deposit.clone(),
+                sign(&deposit, &[Keyring::Bob, Keyring::Charlie])
+            ),
+            XGatewayLocalErr::ReplayedTx
+        );
+    })
+}
+
+#[test]
+fn test_withdraw() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(
+            XGatewayLocal::withdrawal_limit(&X_LOCAL)
+                .unwrap()
+                .minimal_withdrawal,
+            WITHDRAWAL_FEE * 3 / 2
+        );
+
+        let balance = 10_000_000_000;
+        let deposit = new_deposit(1, dave(), balance);
+        assert_ok!(XGatewayLocal::deposit(
+            Origin::signed(alice()),
+            deposit.clone(),
+            sign(&deposit, &[Keyring::Alice, Keyring::Charlie])
+        ));
+
+        // withdraw by the common gateway, which checks the address and the limit of local chain
+        assert_noop!(
+            XGatewayCommon::withdraw(
+                Origin::signed(dave()),
+                X_LOCAL,
+                balance,
+                b"".to_vec(),
+                b"".to_vec().into()
+            ),
+            XGatewayLocalErr::InvalidAddress
+        );
+        assert_ok!(XGatewayCommon::withdraw(
+            Origin::signed(dave()),
+            X_LOCAL,
+            balance,
+            b"dave".to_vec(),
+            b"".to_vec().into()
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Applying)
+        );
+
+        // could not finish the applying withdrawal
+        assert!(XGatewayLocal::finish_withdrawals(
+            RawOrigin::Root.into(),
+            H256::repeat_byte(2),
+            vec![0]
+        )
+        .is_err());
+
+        assert_ok!(XGatewayLocal::process_withdrawals(
+            RawOrigin::Root.into(),
+            vec![0]
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+        assert_ok!(XGatewayLocal::finish_withdrawals(
+            RawOrigin::Root.into(),
+            H256::repeat_byte(2),
+            vec![0]
+        ));
+        assert_eq!(XGatewayRecords::pending_withdrawals(0), None);
+        assert_eq!(XGatewayRecords::state_of(0), None);
+        assert_eq!(
+            XGatewayRecords::withdrawal_history_of(0)
+                .unwrap()
+                .settlement,
+            Some(WithdrawalSettlement {
+                txid: H256::repeat_byte(2),
+                block_hash: None,
+            })
+        );
+        assert_eq!(XAssets::total_issuance(&X_LOCAL), 0);
+
+        // the withdrawal transaction could not be replayed
+        assert_noop!(
+            XGatewayLocal::finish_withdrawals(RawOrigin::Root.into(), H256::repeat_byte(2), vec![]),
+            XGatewayLocalErr::ReplayedTx
+        );
+    })
+}
+
+#[test]
+fn test_trustee_transition() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayCommon::setup_trustee_impl(
+            dave(),
+            Chain::Local,
+            b"Dave".to_vec(),
+            Keyring::Dave.public().0.to_vec(),
+            Keyring::Dave.public().0.to_vec(),
+        ));
+        // invalid ed25519 public key
+        assert!(XGatewayCommon::setup_trustee_impl(
+            dave(),
+            Chain::Local,
+            b"Dave".to_vec(),
+            vec![1; 31],
+            vec![1; 31],
+        )
+        .is_err());
+
+        assert_ok!(XGatewayCommon::transition_trustee_session(
+            RawOrigin::Root.into(),
+            Chain::Local,
+            vec![alice(), bob(), charlie(), dave()]
+        ));
+        let session = current_trustee_session::<Test>().unwrap();
+        assert_eq!(session.trustee_list.len(), 4);
+        assert_eq!(session.threshold, 3);
+        assert!(session
+            .hot_address
+            .signers
+            .contains(&Keyring::Dave.public()));
+
+        // 2 signatures are not enough for the new session
+        let deposit = new_deposit(1, dave(), 10_000_000_000);
+        assert_noop!(
+            XGatewayLocal::deposit(
+                Origin::signed(dave()),
+                deposit.clone(),
+                sign(&deposit, &[Keyring::Alice, Keyring::Bob])
+            ),
+            XGatewayLocalErr::InsufficientSignatures
+        );
+        assert_ok!(XGatewayLocal::deposit(
+            Origin::signed(dave()),
+            deposit.clone(),
+            sign(&deposit, &[Keyring::Alice, Keyring::Bob, Keyring::Dave])
+        ));
+    })
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayLocal::check_addr(b"dave", b""));
+        assert_ok!(XGatewayLocal::check_addr(&[1; 64], b""));
+        assert_noop!(
+            XGatewayLocal::check_addr(b"", b""),
+            XGatewayLocalErr::InvalidAddress
+        );
+        assert_noop!(
+            XGatewayLocal::check_addr(&[1; 65], b""),
+            XGatewayLocalErr::InvalidAddress
+        );
+    })
+}
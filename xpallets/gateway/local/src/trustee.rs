@@ -0,0 +1,147 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::{ed25519, H256};
+use sp_io::hashing::blake2_256;
+use sp_std::{convert::TryFrom, prelude::*};
+
+use xp_logging::{error, info};
+use xpallet_gateway_common::{
+    traits::{TrusteeForChain, TrusteeSession},
+    trustees::local::{LocalTrusteeAddrInfo, LocalTrusteeType},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+
+use crate::{Error, Module, Trait};
+
+pub fn current_trustee_session<T: Trait>(
+) -> Result<TrusteeSessionInfo<T::AccountId, LocalTrusteeAddrInfo>, DispatchError> {
+    T::TrusteeSessionProvider::current_trustee_session()
+}
+
+/// Returns the trustee address of the sorted `signers` and `threshold`.
+pub fn trustee_address(signers: &[ed25519::Public], threshold: u16) -> H256 {
+    H256((b"xlocal", signers, threshold).using_encoded(blake2_256))
+}
+
+/// Verify the `signatures` of `message` by the hot trustee keys of current session, at least
+/// `threshold` distinct keys must be signed.
+pub fn verify_signatures<T: Trait>(
+    message: &[u8],
+    signatures: &[(ed25519::Public, ed25519::Signature)],
+) -> DispatchResult {
+    let session = current_trustee_session::<T>()?;
+    let signers = session.hot_address.signers;
+    let mut signed = Vec::with_capacity(signatures.len());
+    for (public, signature) in signatures {
+        if !signers.contains(public) {
+            error!(
+                "[verify_signatures] The signer {:?} is not the hot trustee key",
+                public
+            );
+            return Err(Error::<T>::InvalidSignature.into());
+        }
+        if signed.contains(public) {
+            error!("[verify_signatures] Duplicated signer {:?}", public);
+            return Err(Error::<T>::DuplicatedSigner.into());
+        }
+        if !sp_io::crypto::ed25519_verify(signature, message, public) {
+            error!(
+                "[verify_signatures] Invalid signature of signer {:?}",
+                public
+            );
+            return Err(Error::<T>::InvalidSignature.into());
+        }
+        signed.push(*public);
+    }
+    if (signed.len() as u16) < session.threshold {
+        error!(
+            "[verify_signatures] Insufficient signatures, signed:{}, threshold:{}",
+            signed.len(),
+            session.threshold
+        );
+        return Err(Error::<T>::InsufficientSignatures.into());
+    }
+    Ok(())
+}
+
+fn check_keys<T: Trait>(keys: &[LocalTrusteeType]) -> DispatchResult {
+    let has_duplicate = (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+    if has_duplicate {
+        error!("[generate_new_trustees] Keys contains duplicate pubkey");
+        return Err(Error::<T>::DuplicatedKeys.into());
+    }
+    Ok(())
+}
+
+fn trustee_addr_info(keys: &[LocalTrusteeType], threshold: u16) -> LocalTrusteeAddrInfo {
+    let mut signers = keys
+        .iter()
+        .map(|key| ed25519::Public::from_slice(&key.0))
+        .collect::<Vec<_>>();
+    signers.sort();
+    LocalTrusteeAddrInfo {
+        addr: trustee_address(&signers, threshold),
+        signers,
+    }
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, LocalTrusteeType, LocalTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<LocalTrusteeType, DispatchError> {
+        let trustee_type = LocalTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| {
+            error!(
+                "[check_trustee_entity] Not a valid ed25519 public key:{:?}",
+                raw_addr
+            );
+            Error::<T>::InvalidPublicKey
+        })?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<LocalTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, LocalTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<LocalTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<LocalTrusteeType>, Vec<LocalTrusteeType>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_keys::<T>(&hot_keys)?;
+        check_keys::<T>(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people, \
+                can't generate trustee addr",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let sig_num = two_thirds_unsafe(trustees.len() as u32) as u16;
+        let hot_address = trustee_addr_info(&hot_keys, sig_num);
+        let cold_address = trustee_addr_info(&cold_keys, sig_num);
+
+        info!(
+            "[generate_trustee_session_info] hot_addr:{:?}, cold_addr:{:?}, trustee_list:{:?}",
+            hot_address, cold_address, trustees
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold: sig_num,
+            hot_address,
+            cold_address,
+        })
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+
+/// The deposit on the local chain, which is credited once signed by the threshold of the hot
+/// trustee keys.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LocalDeposit<AccountId, Balance> {
+    /// the hash of the deposit transaction on the local chain
+    pub tx_hash: H256,
+    pub who: AccountId,
+    pub amount: Balance,
+}
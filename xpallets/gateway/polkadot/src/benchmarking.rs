@@ -0,0 +1,206 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Compact, Encode};
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::storage::{StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use sp_core::{crypto::key_types::ACCOUNT, H256};
+use sp_finality_grandpa::AuthorityId;
+use sp_runtime::{RuntimeAppPublic, SaturatedConversion};
+use sp_std::prelude::*;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
+use xpallet_assets_registrar::{AssetInfo, Module as XAssetsRegistrar};
+use xpallet_gateway_common::traits::AddressBinding;
+use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
+
+use crate::{
+    grandpa::sign_justification,
+    proof::{encode_transfer_record, events_key, single_leaf_proof},
+    trustee::current_trustee_session,
+    AuthoritySet, BestFinalized, Call, CurrentAuthoritySet, DotAccount, DotHeader, DotHeaderIndex,
+    Module, PendingDeposit, PendingDeposits, Trait, Transfer,
+};
+
+const ASSET_ID: AssetId = xp_protocol::X_DOT;
+
+fn ensure_dot_asset<T: Trait>() {
+    if !XAssetsRegistrar::<T>::exists(&ASSET_ID) {
+        let info = AssetInfo::new::<T>(
+            b"XDOT".to_vec(),
+            b"ChainX Polkadot".to_vec(),
+            Chain::Polkadot,
+            xp_protocol::DOT_DECIMALS,
+            b"ChainX's Cross-chain Polkadot".to_vec(),
+        )
+        .unwrap();
+        XAssetsRegistrar::<T>::register(RawOrigin::Root.into(), ASSET_ID, info, true, false)
+            .unwrap();
+    }
+}
+
+fn new_header(number: u32, parent_hash: H256, state_root: H256) -> DotHeader {
+    DotHeader::new(
+        number,
+        Default::default(),
+        state_root,
+        parent_hash,
+        Default::default(),
+    )
+}
+
+/// Insert a finalized header with the given state root, returns its hash.
+fn prepare_finalized_header<T: Trait>(number: u32, state_root: H256) -> H256 {
+    let header = new_header(number, H256::repeat_byte(1), state_root);
+    let hash = header.hash();
+    Module::<T>::insert_header(hash, &header);
+    BestFinalized::put(DotHeaderIndex { hash, number });
+    hash
+}
+
+benchmarks! {
+    _{ }
+
+    push_finalized_header {
+        let p in 1 .. 100 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let voters = (0..p).map(|_| AuthorityId::generate_pair(None)).collect::<Vec<_>>();
+        CurrentAuthoritySet::put(AuthoritySet {
+            authorities: voters.iter().map(|id| (id.clone(), 1)).collect(),
+            set_id: 0,
+        });
+        let parent = prepare_finalized_header::<T>(1_000_000, H256::zero());
+        let header = new_header(1_000_001, parent, H256::zero());
+        let hash = header.hash();
+        let justification = sign_justification(&header, 0, &voters);
+    }: _(RawOrigin::Signed(caller), header, justification)
+    verify {
+        assert_eq!(Module::<T>::best_finalized().hash, hash);
+    }
+
+    push_ancestors {
+        let n in 1 .. 100 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let mut headers = Vec::with_capacity(n as usize);
+        let mut parent = H256::repeat_byte(1);
+        for i in 0..n {
+            let header = new_header(1_000_000 - n + i, parent, H256::zero());
+            parent = header.hash();
+            headers.push(header);
+        }
+        headers.reverse();
+        let child = new_header(1_000_000, parent, H256::zero());
+        let child_hash = child.hash();
+        Module::<T>::insert_header(child_hash, &child);
+    }: _(RawOrigin::Signed(caller), child_hash, headers)
+    verify {
+        assert!(Module::<T>::headers(&parent).is_some());
+    }
+
+    push_transfers {
+        let n in 1 .. 100 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_dot_asset::<T>();
+        let hot = current_trustee_session::<T>().unwrap().hot_address.addr;
+        let amount = 10_000_000_000u128;
+        let mut records = Vec::with_capacity(n as usize);
+        for i in 0..n {
+            let mut from = [0u8; 32];
+            from[..4].copy_from_slice(&i.to_le_bytes());
+            let from = DotAccount::new(from);
+            T::AddressBinding::update_binding(Chain::Polkadot, from.encode(), caller.clone());
+            let transfer = Transfer { from, to: hot.clone(), amount };
+            records.push(encode_transfer_record(Module::<T>::transfer_event_index(), &transfer));
+        }
+        let value = [Compact(n).encode(), records.concat()].concat();
+        let (root, proof) = single_leaf_proof(&events_key(), &value);
+        let block_hash = prepare_finalized_header::<T>(1_000_000, root);
+    }: _(RawOrigin::Root, block_hash, proof, records)
+    verify {
+        assert!(Module::<T>::processed_block(&block_hash));
+        let expected: BalanceOf<T> = (amount * n as u128).saturated_into();
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), expected);
+    }
+
+    bind_dot_address {
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_dot_asset::<T>();
+        let public = sp_io::crypto::sr25519_generate(ACCOUNT, None);
+        let signature = sp_io::crypto::sr25519_sign(ACCOUNT, &public, &caller.encode()).unwrap();
+        let address = DotAccount::new(public.0);
+        let balance: BalanceOf<T> = 10_000_000_000u128.saturated_into();
+        PendingDeposits::<T>::insert(&address, vec![PendingDeposit { block_hash: H256::zero(), balance }]);
+    }: _(RawOrigin::Signed(caller.clone()), address, signature)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), balance);
+    }
+
+    process_withdrawals {
+        let n in 1 .. 100 => ();
+
+        let caller: T::AccountId = whitelisted_caller();
+        ensure_dot_asset::<T>();
+        let balance: BalanceOf<T> = 100_000_000_000u128.saturated_into();
+        let addr = b"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d".to_vec();
+        let mut ids = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            ids.push(xpallet_gateway_records::Module::<T>::id());
+            XGatewayRecords::<T>::deposit(&caller, ASSET_ID, balance).unwrap();
+            XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, balance, addr.clone(), b"".to_vec().into()).unwrap();
+        }
+    }: _(RawOrigin::Root, ids.clone())
+    verify {
+        for id in ids {
+            assert_eq!(XGatewayRecords::<T>::state_of(id), Some(WithdrawalState::Processing));
+        }
+    }
+
+    set_withdrawal_fee {
+        let fee: BalanceOf<T> = 1_000_000_000u128.saturated_into();
+    }: _(RawOrigin::Root, fee)
+    verify {
+        assert_eq!(Module::<T>::withdrawal_fee(), fee);
+    }
+
+    set_authority_set {
+        let authority_set = AuthoritySet {
+            authorities: vec![(AuthorityId::generate_pair(None), 1)],
+            set_id: 1,
+        };
+    }: _(RawOrigin::Root, authority_set.clone())
+    verify {
+        assert_eq!(Module::<T>::authority_set(), authority_set);
+    }
+
+    set_transfer_event_index {
+        let index = (4, 2);
+    }: _(RawOrigin::Root, index)
+    verify {
+        assert_eq!(Module::<T>::transfer_event_index(), index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_push_finalized_header::<Test>());
+            assert_ok!(test_benchmark_push_ancestors::<Test>());
+            assert_ok!(test_benchmark_push_transfers::<Test>());
+            assert_ok!(test_benchmark_bind_dot_address::<Test>());
+            assert_ok!(test_benchmark_process_withdrawals::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_fee::<Test>());
+            assert_ok!(test_benchmark_set_authority_set::<Test>());
+            assert_ok!(test_benchmark_set_transfer_event_index::<Test>());
+        });
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verify the GRANDPA justifications and track the authority set changes of the relay chain.
+
+use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchResult;
+use sp_core::H256;
+use sp_finality_grandpa::{ConsensusLog, RoundNumber, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
+use sp_runtime::RuntimeAppPublic;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+use xp_logging::{error, warn};
+
+use crate::types::{AuthoritySet, DotBlockNumber, DotHeader, GrandpaJustification, Precommit};
+use crate::{Error, Trait};
+
+/// The index of `Precommit` in the GRANDPA `Message` enum.
+const PRECOMMIT_MESSAGE: u8 = 1;
+
+/// Returns the payload signed by the authority, i.e. the encoded `(message, round, set_id)`.
+pub fn localized_payload(precommit: &Precommit, round: RoundNumber, set_id: SetId) -> Vec<u8> {
+    (PRECOMMIT_MESSAGE, precommit, round, set_id).encode()
+}
+
+/// Returns true if the target of `precommit` is `target_hash` or its descendant, the ancestry
+/// between them must be included in `ancestry` (hash => parent hash).
+fn is_descendant(
+    ancestry: &BTreeMap<H256, H256>,
+    precommit: &Precommit,
+    target_hash: H256,
+) -> bool {
+    let mut current = precommit.target_hash;
+    // each header of the ancestry could be visited at most once
+    for _ in 0..=ancestry.len() {
+        if current == target_hash {
+            return true;
+        }
+        match ancestry.get(&current) {
+            Some(parent) => current = *parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+/// Verify that the header `hash` (`number`) is finalized by the `justification` of the
+/// authority set `set`, i.e. the precommits of more than 2/3 weight of the authorities.
+pub fn verify_justification<T: Trait>(
+    hash: H256,
+    number: DotBlockNumber,
+    set: &AuthoritySet,
+    justification: &GrandpaJustification,
+) -> DispatchResult {
+    let commit = &justification.commit;
+    if commit.target_hash != hash || commit.target_number != number {
+        error!(
+            "[verify_justification] The commit target ({:?}, {}) is not the header ({:?}, {})",
+            commit.target_hash, commit.target_number, hash, number
+        );
+        return Err(Error::<T>::InvalidJustification.into());
+    }
+
+    let ancestry = justification
+        .votes_ancestries
+        .iter()
+        .map(|header| (header.hash(), header.parent_hash))
+        .collect::<BTreeMap<_, _>>();
+
+    let total_weight = set
+        .authorities
+        .iter()
+        .fold(0u64, |acc, (_, weight)| acc.saturating_add(*weight));
+    if total_weight == 0 {
+        error!("[verify_justification] The authority set is empty");
+        return Err(Error::<T>::InsufficientVotes.into());
+    }
+    // the same as the threshold of the GRANDPA voter set
+    let threshold = total_weight - (total_weight - 1) / 3;
+
+    let mut voted = vec![false; set.authorities.len()];
+    let mut weight = 0u64;
+    for signed in commit.precommits.iter() {
+        let index = match set.authorities.iter().position(|(id, _)| *id == signed.id) {
+            Some(index) => index,
+            None => {
+                warn!(
+                    "[verify_justification] Ignore the precommit of unknown authority:{:?}",
+                    signed.id
+                );
+                continue;
+            }
+        };
+        if voted[index] {
+            error!(
+                "[verify_justification] Duplicated precommit of authority:{:?}",
+                signed.id
+            );
+            return Err(Error::<T>::InvalidJustification.into());
+        }
+        if !is_descendant(&ancestry, &signed.precommit, commit.target_hash) {
+            error!(
+                "[verify_justification] The precommit target {:?} is not the descendant of commit target {:?}",
+                signed.precommit, commit.target_hash
+            );
+            return Err(Error::<T>::InvalidJustification.into());
+        }
+        let payload = localized_payload(&signed.precommit, justification.round, set.set_id);
+        if !signed.id.verify(&payload, &signed.signature) {
+            error!(
+                "[verify_justification] Invalid signature of authority:{:?}, set id:{}",
+                signed.id, set.set_id
+            );
+            return Err(Error::<T>::InvalidJustification.into());
+        }
+        voted[index] = true;
+        weight = weight.saturating_add(set.authorities[index].1);
+    }
+
+    if weight < threshold {
+        error!(
+            "[verify_justification] Insufficient votes, weight:{}, threshold:{}",
+            weight, threshold
+        );
+        return Err(Error::<T>::InsufficientVotes.into());
+    }
+    Ok(())
+}
+
+/// Returns the standard authority set change scheduled in the header digest.
+///
+/// The forced changes are ignored, so the justifications by the new authorities would fail and
+/// the relay stalls. Root recovers it by `set_authority_set` with the new authorities and set id,
+/// then the relayers push the next finalized header, and the skipped ones by `push_ancestors`.
+pub fn scheduled_change(header: &DotHeader) -> Option<ScheduledChange<DotBlockNumber>> {
+    for log in header.digest.logs() {
+        let log = match log.as_consensus() {
+            Some((engine_id, mut data)) if engine_id == GRANDPA_ENGINE_ID => {
+                ConsensusLog::<DotBlockNumber>::decode(&mut data)
+            }
+            _ => continue,
+        };
+        match log {
+            Ok(ConsensusLog::ScheduledChange(change)) => return Some(change),
+            Ok(ConsensusLog::ForcedChange(..)) => warn!(
+                "[scheduled_change] Ignore the forced change in header:{:?}, \
+                root must set the new authority set by `set_authority_set`",
+                header.hash()
+            ),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Sign the `header` by `voters` in the first round, the voters must be in the keystore.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn sign_justification(
+    header: &DotHeader,
+    set_id: SetId,
+    voters: &[sp_finality_grandpa::AuthorityId],
+) -> GrandpaJustification {
+    use crate::types::{Commit, SignedPrecommit};
+
+    let round = 1;
+    let precommit = Precommit {
+        target_hash: header.hash(),
+        target_number: header.number,
+    };
+    let payload = localized_payload(&precommit, round, set_id);
+    let precommits = voters
+        .iter()
+        .map(|id| SignedPrecommit {
+            precommit: precommit.clone(),
+            signature: id
+                .sign(&payload)
+                .expect("the voter must be in the keystore"),
+            id: id.clone(),
+        })
+        .collect();
+    GrandpaJustification {
+        round,
+        commit: Commit {
+            target_hash: precommit.target_hash,
+            target_number: precommit.target_number,
+            precommits,
+        },
+        votes_ancestries: vec![],
+    }
+}
@@ -0,0 +1,539 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for dot-bridge, X-DOT is backed by the trustee multisig account on the relay
+//! chain.
+//!
+//! The relay chain headers are finalized by the GRANDPA justifications of the tracked authority
+//! set, and the transfers of the trustee accounts are read from the `System::Events` storage
+//! proof against the state root of the finalized header.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod grandpa;
+pub mod proof;
+pub mod transfer;
+pub mod trustee;
+mod types;
+pub mod weights;
+
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_core::{sr25519, H256};
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::EnsureOrigin,
+    weights::Pays,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{
+    traits::{AddressBinding, AddressOwnership, TrusteeSession},
+    trustees::polkadot::DotTrusteeAddrInfo,
+};
+use xpallet_gateway_records::WithdrawalRecordId;
+use xpallet_support::try_addr;
+
+pub use self::types::{
+    AuthoritySet, Commit, DotAccount, DotBlockNumber, DotHeader, DotHeaderIndex, DotHeaderInfo,
+    GrandpaJustification, PendingChange, PendingDeposit, Precommit, SignedPrecommit, Transfer,
+};
+pub use self::weights::WeightInfo;
+
+pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    type TrusteeSessionProvider: TrusteeSession<Self::AccountId, DotTrusteeAddrInfo>;
+    type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    type AddressBinding: AddressBinding<Self::AccountId, Vec<u8>>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Polkadot module
+    pub enum Error for Module<T: Trait> {
+        /// the header is not later than the best finalized header
+        OldHeader,
+        /// the header which enacts the pending authority set change must be finalized first
+        UnenactedAuthorityChange,
+        /// a new authority set change is scheduled before the pending one is enacted
+        PendingAuthorityChange,
+        /// the justification does not finalize the header or has invalid votes
+        InvalidJustification,
+        /// the votes of justification are less than the threshold of the authority set
+        InsufficientVotes,
+        /// header not found
+        HeaderNotFound,
+        /// the header is not the parent of the imported header
+        InvalidAncestor,
+        /// reject replay processed block
+        ReplayedBlock,
+        /// the storage proof of events is invalid
+        BadStorageProof,
+        /// the records are not the events of the block
+        InvalidEventRecords,
+        /// invalid relay chain address
+        InvalidAddress,
+        /// invalid sr25519 public key
+        InvalidPublicKey,
+        /// invalid signature of the address ownership
+        InvalidSignature,
+        /// duplicated pubkey for trustees
+        DuplicatedKeys,
+        /// invalid trustee count
+        InvalidTrusteeCount,
+        /// the amount exceeds the max balance
+        InvalidAmount,
+        /// no processing withdrawal matches the transfer
+        NoWithdrawalRecord,
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>
+    {
+        /// A relay chain header was finalized. [hash, number]
+        HeaderFinalized(H256, DotBlockNumber),
+        /// The ancestors of a finalized header were imported. [child_hash, count]
+        AncestorsImported(H256, u32),
+        /// The GRANDPA authority set was changed. [set_id]
+        AuthoritySetChanged(SetId),
+        /// The transfers of a relay chain block were processed. [block_hash, processed_transfers]
+        TransfersProcessed(H256, u32),
+        /// An account deposited some X-DOT. [block_hash, who, amount]
+        Deposited(H256, AccountId, Balance),
+        /// A deposit from an unbound relay chain account is pending. [block_hash, from, amount]
+        DepositPending(H256, DotAccount, Balance),
+        /// A withdrawal was sent by the trustees. [block_hash, withdrawal_id]
+        Withdrawn(H256, WithdrawalRecordId),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayPolkadot {
+        /// best finalized header info
+        pub BestFinalized get(fn best_finalized): DotHeaderIndex;
+        /// the finalized headers and their imported ancestors
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<DotHeaderInfo>;
+        /// the current GRANDPA authority set
+        pub CurrentAuthoritySet get(fn authority_set): AuthoritySet;
+        /// the scheduled authority set change which is not enacted yet
+        pub NextAuthorityChange get(fn pending_change): Option<PendingChange>;
+
+        /// mark the events of the block has been processed
+        pub ProcessedBlocks get(fn processed_block): map hasher(identity) H256 => bool;
+        /// the deposits from the unbound relay chain accounts
+        pub PendingDeposits get(fn pending_deposits):
+            map hasher(blake2_128_concat) DotAccount => Vec<PendingDeposit<BalanceOf<T>>>;
+        /// the withdrawals processed by this module, waiting for the transfers of the trustees
+        pub ProcessingWithdrawals get(fn processing_withdrawals): Vec<WithdrawalRecordId>;
+
+        /// the withdrawal fee of X-DOT
+        pub WithdrawalFee get(fn withdrawal_fee) config(): BalanceOf<T>;
+        /// the (pallet index, event index) of `Balances::Transfer` on the relay chain
+        pub TransferEventIndex get(fn transfer_event_index) config(): (u8, u8);
+    }
+    add_extra_genesis {
+        /// SCALE encoded finalized header to start from, the relay would not start if it's empty
+        config(genesis_header): Vec<u8>;
+        config(genesis_authorities): AuthorityList;
+        config(genesis_set_id): SetId;
+        config(genesis_trustees): Vec<T::AccountId>;
+        build(|config| {
+            if !config.genesis_header.is_empty() {
+                let header = DotHeader::decode(&mut config.genesis_header.as_slice())
+                    .expect("genesis header must be valid; qed");
+                let hash = header.hash();
+                Module::<T>::insert_header(hash, &header);
+                BestFinalized::put(DotHeaderIndex {
+                    hash,
+                    number: header.number,
+                });
+                CurrentAuthoritySet::put(AuthoritySet {
+                    authorities: config.genesis_authorities.clone(),
+                    set_id: config.genesis_set_id,
+                });
+            }
+
+            if !config.genesis_trustees.is_empty() {
+                T::TrusteeSessionProvider::genesis_trustee(Module::<T>::chain(), &config.genesis_trustees);
+            }
+        })
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+        fn deposit_event() = default;
+
+        /// Relay the relay chain `header` finalized by the GRANDPA `justification` of the current
+        /// authority set.
+        ///
+        /// The headers which schedule or enact the authority set changes must be relayed, or
+        /// the later justifications could not be verified. The forced changes are not tracked,
+        /// root must set the new authorities by `set_authority_set` (see `scheduled_change`).
+        #[weight = <T as Trait>::WeightInfo::push_finalized_header(justification.commit.precommits.len() as u32)]
+        pub fn push_finalized_header(
+            origin,
+            header: DotHeader,
+            justification: GrandpaJustification
+        ) -> DispatchResultWithPostInfo {
+            let _from = ensure_signed(origin)?;
+            debug!("[push_finalized_header] from:{:?}, header:{:?}", _from, header);
+
+            Self::apply_push_finalized_header(header, justification)?;
+
+            // Relayer does not pay a fee.
+            Ok(Pays::No.into())
+        }
+
+        /// Import the ancestors of the imported header `child`, `headers` are ordered from the
+        /// parent of `child` to the older ones.
+        #[weight = <T as Trait>::WeightInfo::push_ancestors(headers.len() as u32)]
+        pub fn push_ancestors(origin, child: H256, headers: Vec<DotHeader>) -> DispatchResultWithPostInfo {
+            let _from = ensure_signed(origin)?;
+            debug!("[push_ancestors] from:{:?}, child:{:?}, count:{}", _from, child, headers.len());
+
+            let count = headers.len() as u32;
+            let imported = Self::apply_push_ancestors(child, headers)?;
+
+            // Relayer does not pay a fee only if all the given headers are new.
+            if imported > 0 && imported == count {
+                Ok(Pays::No.into())
+            } else {
+                Ok(Pays::Yes.into())
+            }
+        }
+
+        /// Relay the transfers of the imported block `block_hash`, `proof` is the storage proof
+        /// of `System::Events`, and `records` are the encoded event records split from it.
+        ///
+        /// NOTE: the split of records is not unique for the opaque events, so the caller must be
+        /// the trustee multisig or root.
+        #[weight = <T as Trait>::WeightInfo::push_transfers(records.len() as u32)]
+        pub fn push_transfers(
+            origin,
+            block_hash: H256,
+            proof: Vec<Vec<u8>>,
+            records: Vec<Vec<u8>>
+        ) -> DispatchResultWithPostInfo {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            debug!("[push_transfers] block:{:?}", block_hash);
+
+            Self::apply_push_transfers(block_hash, proof, records)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Bind the relay chain `address` to the caller, then the deposits from `address` would
+        /// be credited to the caller. The pending deposits of `address` would be claimed as well.
+        ///
+        /// `signature` is the sr25519 signature of the encoded caller's account from `address`.
+        #[weight = <T as Trait>::WeightInfo::bind_dot_address()]
+        pub fn bind_dot_address(origin, address: DotAccount, signature: sr25519::Signature) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::verify_ownership(&who, &address, &signature)?;
+
+            T::AddressBinding::update_binding(Chain::Polkadot, address.encode(), who.clone());
+            transfer::claim_pending_deposits::<T>(&address, &who);
+            Ok(())
+        }
+
+        /// Mark the withdrawals as processing before sending them by the trustee multisig.
+        #[weight = <T as Trait>::WeightInfo::process_withdrawals(ids.len() as u32)]
+        pub fn process_withdrawals(origin, ids: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            xpallet_gateway_records::Module::<T>::process_withdrawals(&ids, Chain::Polkadot)?;
+            ProcessingWithdrawals::mutate(|processing| processing.extend_from_slice(&ids));
+            Ok(())
+        }
+
+        /// Set the withdrawal fee of X-DOT.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee()]
+        pub fn set_withdrawal_fee(origin, #[compact] fee: BalanceOf<T>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            WithdrawalFee::<T>::put(fee);
+            Ok(())
+        }
+
+        /// Dangerous! Set the authority set directly and drop the pending change, e.g. after a
+        /// forced change of the relay chain.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_authority_set()]
+        pub fn set_authority_set(origin, authority_set: AuthoritySet) -> DispatchResult {
+            ensure_root(origin)?;
+            let set_id = authority_set.set_id;
+            CurrentAuthoritySet::put(authority_set);
+            NextAuthorityChange::kill();
+            Self::deposit_event(Event::<T>::AuthoritySetChanged(set_id));
+            Ok(())
+        }
+
+        /// Set the (pallet index, event index) of `Balances::Transfer` on the relay chain.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_transfer_event_index()]
+        pub fn set_transfer_event_index(origin, index: (u8, u8)) -> DispatchResult {
+            ensure_root(origin)?;
+            TransferEventIndex::put(index);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        let address = Self::parse_address(addr).ok_or_else(|| {
+            error!(
+                "[check_addr] Invalid relay chain address:{:?}",
+                try_addr(addr)
+            );
+            Error::<T>::InvalidAddress
+        })?;
+        // do not allow withdraw to the trustee accounts
+        if let Ok(session) = trustee::current_trustee_session::<T>() {
+            ensure!(
+                address != session.hot_address.addr && address != session.cold_address.addr,
+                Error::<T>::InvalidAddress
+            );
+        }
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        if *asset_id != Self::ASSET_ID {
+            return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
+        }
+        let fee = Self::withdrawal_fee();
+        let limit = WithdrawalLimit::<BalanceOf<T>> {
+            minimal_withdrawal: fee * 3u32.saturated_into() / 2u32.saturated_into(),
+            fee,
+        };
+        Ok(limit)
+    }
+}
+
+impl<T: Trait> AddressOwnership<T::AccountId> for Module<T> {
+    fn verify_address_ownership(
+        who: &T::AccountId,
+        address: &[u8],
+        signature: &[u8],
+    ) -> DispatchResult {
+        let address =
+            DotAccount::decode(&mut &address[..]).map_err(|_| Error::<T>::InvalidAddress)?;
+        let signature = sr25519::Signature::decode(&mut &signature[..])
+            .map_err(|_| Error::<T>::InvalidSignature)?;
+        Self::verify_ownership(who, &address, &signature)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Parse the hex public key of the relay chain account with `0x` prefix.
+    pub fn parse_address(addr: &[u8]) -> Option<DotAccount> {
+        if addr.len() != 66 || !addr.starts_with(b"0x") {
+            return None;
+        }
+        let hex = &addr[2..];
+        let mut account = [0u8; 32];
+        for (i, byte) in account.iter_mut().enumerate() {
+            *byte = (hex_value(hex[2 * i])? << 4) | hex_value(hex[2 * i + 1])?;
+        }
+        Some(DotAccount::new(account))
+    }
+
+    /// Verify that the encoded `who` is signed by `address`. The encoded `who` wrapped in
+    /// `<Bytes>...</Bytes>` (signed by the polkadot-js extension) is also accepted.
+    fn verify_ownership(
+        who: &T::AccountId,
+        address: &DotAccount,
+        signature: &sr25519::Signature,
+    ) -> DispatchResult {
+        let account = who.encode();
+        let wrapped = [&b"<Bytes>"[..], &account[..], &b"</Bytes>"[..]].concat();
+        let raw: &[u8; 32] = address.as_ref();
+        let public = sr25519::Public::from_raw(*raw);
+        if !sp_io::crypto::sr25519_verify(signature, &account, &public)
+            && !sp_io::crypto::sr25519_verify(signature, &wrapped, &public)
+        {
+            error!(
+                "[verify_ownership] Invalid signature of address:{:?}, who:{:?}",
+                address, who
+            );
+            return Err(Error::<T>::InvalidSignature.into());
+        }
+        Ok(())
+    }
+
+    fn insert_header(hash: H256, header: &DotHeader) {
+        Headers::insert(
+            &hash,
+            DotHeaderInfo {
+                parent_hash: header.parent_hash,
+                number: header.number,
+                state_root: header.state_root,
+            },
+        );
+    }
+
+    fn apply_push_finalized_header(
+        header: DotHeader,
+        justification: GrandpaJustification,
+    ) -> DispatchResult {
+        let hash = header.hash();
+        let best = Self::best_finalized();
+        if header.number <= best.number {
+            error!(
+                "[apply_push_finalized_header] The header (number:{}, hash:{:?}) is not later than the best finalized:{:?}",
+                header.number, hash, best
+            );
+            return Err(Error::<T>::OldHeader.into());
+        }
+        if let Some(change) = Self::pending_change() {
+            ensure!(
+                header.number <= change.enact_at,
+                Error::<T>::UnenactedAuthorityChange
+            );
+        }
+        let set = Self::authority_set();
+        grandpa::verify_justification::<T>(hash, header.number, &set, &justification)?;
+
+        let scheduled = grandpa::scheduled_change(&header);
+        if scheduled.is_some() && Self::pending_change().is_some() {
+            error!(
+                "[apply_push_finalized_header] The header {:?} schedules a change before the pending one is enacted",
+                hash
+            );
+            return Err(Error::<T>::PendingAuthorityChange.into());
+        }
+
+        with_transaction_result(|| {
+            Self::insert_header(hash, &header);
+            BestFinalized::put(DotHeaderIndex {
+                hash,
+                number: header.number,
+            });
+            info!(
+                "[apply_push_finalized_header] Finalize number:{}, hash:{:?}",
+                header.number, hash
+            );
+            Self::deposit_event(Event::<T>::HeaderFinalized(hash, header.number));
+
+            if let Some(change) = scheduled {
+                NextAuthorityChange::put(PendingChange {
+                    next_authorities: change.next_authorities,
+                    enact_at: header.number.saturating_add(change.delay),
+                });
+            }
+            match Self::pending_change() {
+                Some(change) if change.enact_at == header.number => {
+                    let set_id = set.set_id + 1;
+                    CurrentAuthoritySet::put(AuthoritySet {
+                        authorities: change.next_authorities,
+                        set_id,
+                    });
+                    NextAuthorityChange::kill();
+                    info!(
+                        "[apply_push_finalized_header] Enact the authority set change, set id:{}",
+                        set_id
+                    );
+                    Self::deposit_event(Event::<T>::AuthoritySetChanged(set_id));
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the count of the newly imported headers.
+    fn apply_push_ancestors(child: H256, headers: Vec<DotHeader>) -> Result<u32, DispatchError> {
+        let mut expected = Self::headers(&child)
+            .ok_or(Error::<T>::HeaderNotFound)?
+            .parent_hash;
+        let mut count = 0;
+        for header in headers.iter() {
+            let hash = header.hash();
+            if hash != expected {
+                error!(
+                    "[apply_push_ancestors] The header {:?} is not the parent {:?}",
+                    hash, expected
+                );
+                return Err(Error::<T>::InvalidAncestor.into());
+            }
+            if Self::headers(&hash).is_some() {
+                // the older ancestors have been imported
+                break;
+            }
+            Self::insert_header(hash, header);
+            expected = header.parent_hash;
+            count += 1;
+        }
+        Self::deposit_event(Event::<T>::AncestorsImported(child, count));
+        Ok(count)
+    }
+
+    fn apply_push_transfers(
+        block_hash: H256,
+        proof: Vec<Vec<u8>>,
+        records: Vec<Vec<u8>>,
+    ) -> DispatchResult {
+        let header = Self::headers(&block_hash).ok_or(Error::<T>::HeaderNotFound)?;
+        ensure!(
+            !Self::processed_block(&block_hash),
+            Error::<T>::ReplayedBlock
+        );
+
+        let value = proof::read_proof(&header.state_root, proof, &proof::events_key())
+            .ok_or(Error::<T>::BadStorageProof)?;
+        let transfers = proof::parse_transfers(&value, &records, Self::transfer_event_index())
+            .ok_or_else(|| {
+                error!(
+                    "[apply_push_transfers] The records are not the events of block:{:?}",
+                    block_hash
+                );
+                Error::<T>::InvalidEventRecords
+            })?;
+
+        with_transaction_result(|| {
+            let processed = transfer::process_transfers::<T>(block_hash, transfers);
+            ProcessedBlocks::insert(&block_hash, true);
+            Self::deposit_event(Event::<T>::TransfersProcessed(block_hash, processed));
+            Ok(())
+        })
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
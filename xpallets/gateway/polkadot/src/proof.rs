@@ -0,0 +1,179 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Read the `System::Events` of the relay chain from the storage proof.
+//!
+//! The events are stored as a single `Vec<EventRecord>` value, which could not be decoded
+//! without the metadata of the relay chain. Thus the relayer splits the value into the encoded
+//! records, and only the records of the `Balances::Transfer` event are decoded:
+//! `phase | pallet index | event index | from | to | amount | topics (empty)`.
+
+use codec::{Compact, Decode};
+use sp_core::H256;
+use sp_io::hashing::twox_128;
+use sp_runtime::traits::BlakeTwo256;
+use sp_std::prelude::*;
+use sp_trie::{read_trie_value, Layout, StorageProof};
+
+use crate::types::Transfer;
+
+/// Returns the storage key of `System::Events`.
+pub fn events_key() -> Vec<u8> {
+    [twox_128(b"System"), twox_128(b"Events")].concat()
+}
+
+/// Read the value of `key` in the state `root` from the storage `proof`.
+pub fn read_proof(root: &H256, proof: Vec<Vec<u8>>, key: &[u8]) -> Option<Vec<u8>> {
+    let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+    read_trie_value::<Layout<BlakeTwo256>, _>(&db, root, key)
+        .ok()
+        .flatten()
+}
+
+/// Returns the transfers in the records, `None` if the records are not the split of the encoded
+/// events `value`.
+///
+/// `transfer_index` is the (pallet index, event index) of `Balances::Transfer`.
+pub fn parse_transfers(
+    value: &[u8],
+    records: &[Vec<u8>],
+    transfer_index: (u8, u8),
+) -> Option<Vec<Transfer>> {
+    let mut input = value;
+    let len = Compact::<u32>::decode(&mut input).ok()?.0;
+    if len as usize != records.len() {
+        return None;
+    }
+    let mut transfers = Vec::new();
+    for record in records {
+        if record.is_empty() || !input.starts_with(record) {
+            return None;
+        }
+        input = &input[record.len()..];
+        if let Some(transfer) = parse_transfer(record, transfer_index)? {
+            transfers.push(transfer);
+        }
+    }
+    if !input.is_empty() {
+        return None;
+    }
+    Some(transfers)
+}
+
+/// Returns `None` if the record does not start with a valid phase, `Some(None)` if it's not a
+/// transfer.
+fn parse_transfer(record: &[u8], (pallet, event): (u8, u8)) -> Option<Option<Transfer>> {
+    // Phase::ApplyExtrinsic(u32) | Phase::Finalization | Phase::Initialization
+    let event_data = match record[0] {
+        0 if record.len() >= 5 => &record[5..],
+        1 | 2 => &record[1..],
+        _ => return None,
+    };
+    // pallet index, event index, from, to, amount (u128) and the empty topics
+    if event_data.len() != 2 + 32 + 32 + 16 + 1
+        || event_data[0] != pallet
+        || event_data[1] != event
+        || event_data[event_data.len() - 1] != 0
+    {
+        return Some(None);
+    }
+    Some(Transfer::decode(&mut &event_data[2..]).ok())
+}
+
+/// Returns the storage root and proof of the trie which contains only the `key` => `value`.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn single_leaf_proof(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    use codec::Encode;
+
+    const LEAF_PREFIX_MASK: u8 = 0b01 << 6;
+    // the header of leaf node with the nibble count of key
+    let nibbles = key.len() * 2;
+    let mut node = if nibbles < 63 {
+        vec![LEAF_PREFIX_MASK + nibbles as u8]
+    } else {
+        let mut header = vec![LEAF_PREFIX_MASK + 63];
+        let mut rem = nibbles - 62;
+        while rem > 255 {
+            header.push(255);
+            rem -= 255;
+        }
+        header.push((rem - 1) as u8);
+        header
+    };
+    node.extend_from_slice(key);
+    value.encode_to(&mut node);
+    (H256(sp_io::hashing::blake2_256(&node)), vec![node])
+}
+
+/// Returns the encoded record of `Balances::Transfer` event in the phase `ApplyExtrinsic(0)`.
+#[cfg(any(feature = "runtime-benchmarks", test))]
+pub fn encode_transfer_record(transfer_index: (u8, u8), transfer: &Transfer) -> Vec<u8> {
+    use codec::Encode;
+
+    let mut record = vec![0u8, 0, 0, 0, 0, transfer_index.0, transfer_index.1];
+    transfer.encode_to(&mut record);
+    // empty topics
+    record.push(0);
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Encode;
+
+    fn transfer() -> Transfer {
+        Transfer {
+            from: [1u8; 32].into(),
+            to: [2u8; 32].into(),
+            amount: 10_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_single_leaf_proof() {
+        let key = events_key();
+        let (root, proof) = single_leaf_proof(&key, b"value");
+        assert_eq!(
+            read_proof(&root, proof.clone(), &key),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(read_proof(&root, proof.clone(), &[1u8; 32]), None);
+        assert_eq!(read_proof(&H256::repeat_byte(1), proof, &key), None);
+    }
+
+    #[test]
+    fn test_parse_transfers() {
+        let index = (5, 2);
+        let transfer_record = encode_transfer_record(index, &transfer());
+        // System::ExtrinsicSuccess in Phase::Finalization
+        let other_record = vec![1u8, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
+        let records = vec![other_record.clone(), transfer_record.clone()];
+        let value = [
+            Compact(2u32).encode(),
+            other_record.clone(),
+            transfer_record.clone(),
+        ]
+        .concat();
+
+        assert_eq!(
+            parse_transfers(&value, &records, index),
+            Some(vec![transfer()])
+        );
+        // other event index
+        assert_eq!(parse_transfers(&value, &records, (5, 3)), Some(vec![]));
+        // mismatched count
+        assert_eq!(parse_transfers(&value, &records[..1], index), None);
+        // mismatched split
+        let bad_records = vec![
+            other_record[..5].to_vec(),
+            [&other_record[5..], &transfer_record[..]].concat(),
+        ];
+        assert_eq!(parse_transfers(&value, &bad_records, index), None);
+        // invalid phase
+        let bad_value = [Compact(1u32).encode(), vec![3u8]].concat();
+        assert_eq!(parse_transfers(&bad_value, &[vec![3u8]], index), None);
+        // trailing bytes
+        let long_value = [&value[..], &[0u8][..]].concat();
+        assert_eq!(parse_transfers(&long_value, &records, index), None);
+    }
+}
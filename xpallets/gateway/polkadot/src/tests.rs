@@ -0,0 +1,460 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok, weights::Pays};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityId, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_keyring::sr25519::Keyring;
+use sp_runtime::{
+    generic::{Digest, DigestItem},
+    AccountId32, RuntimeAppPublic,
+};
+
+use codec::{Compact, Encode};
+use xpallet_assets::ChainT;
+use xpallet_gateway_records::WithdrawalState;
+
+use crate::mock::*;
+use crate::{
+    grandpa::sign_justification,
+    proof::{encode_transfer_record, events_key, single_leaf_proof},
+    trustee::{current_trustee_session, multisig_account},
+    DotAccount, DotHeader, Transfer,
+};
+
+fn header(number: u32, parent_hash: H256, state_root: H256) -> DotHeader {
+    DotHeader::new(
+        number,
+        Default::default(),
+        state_root,
+        parent_hash,
+        Default::default(),
+    )
+}
+
+fn voters() -> Vec<AuthorityId> {
+    XGatewayPolkadot::authority_set()
+        .authorities
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Finalize the header by 3 of the 4 authorities.
+fn finalize(header: DotHeader) -> H256 {
+    let hash = header.hash();
+    let set_id = XGatewayPolkadot::authority_set().set_id;
+    let justification = sign_justification(&header, set_id, &voters()[..3]);
+    assert_ok!(XGatewayPolkadot::push_finalized_header(
+        Origin::signed(alice()),
+        header,
+        justification
+    ));
+    hash
+}
+
+/// Finalize a header on the best finalized header with the state root.
+fn finalize_best(state_root: H256) -> H256 {
+    let best = XGatewayPolkadot::best_finalized();
+    finalize(header(best.number + 1, best.hash, state_root))
+}
+
+fn hot_address() -> DotAccount {
+    current_trustee_session::<Test>().unwrap().hot_address.addr
+}
+
+/// Finalize a block whose events are the transfers, returns its hash and the storage proof.
+fn prepare_transfers(transfers: &[Transfer]) -> (H256, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let records = transfers
+        .iter()
+        .map(|transfer| encode_transfer_record(TRANSFER_EVENT_INDEX, transfer))
+        .collect::<Vec<_>>();
+    let value = [Compact(records.len() as u32).encode(), records.concat()].concat();
+    let (root, proof) = single_leaf_proof(&events_key(), &value);
+    let block_hash = finalize_best(root);
+    (block_hash, proof, records)
+}
+
+fn push_transfers(transfers: &[Transfer]) -> H256 {
+    let (block_hash, proof, records) = prepare_transfers(transfers);
+    assert_ok!(XGatewayPolkadot::push_transfers(
+        Origin::root(),
+        block_hash,
+        proof,
+        records
+    ));
+    block_hash
+}
+
+fn bind(who: AccountId32, key: Keyring) {
+    let signature = key.sign(&who.encode());
+    assert_ok!(XGatewayPolkadot::bind_dot_address(
+        Origin::signed(who),
+        key.to_account_id(),
+        signature
+    ));
+}
+
+#[test]
+fn test_genesis() {
+    ExtBuilder::default().build_and_execute(|| {
+        let hash = genesis_header().hash();
+        assert_eq!(XGatewayPolkadot::best_finalized().hash, hash);
+        assert_eq!(XGatewayPolkadot::best_finalized().number, 100);
+        assert!(XGatewayPolkadot::headers(&hash).is_some());
+        assert_eq!(
+            current_trustee_session::<Test>().unwrap().trustee_list,
+            vec![alice(), bob(), charlie()]
+        );
+    })
+}
+
+#[test]
+fn test_trustee_multisig_address() {
+    ExtBuilder::default().build_and_execute(|| {
+        let session = current_trustee_session::<Test>().unwrap();
+        assert_eq!(session.threshold, 2);
+        let mut signers = vec![alice(), bob(), charlie()];
+        signers.sort();
+        assert_eq!(session.hot_address.signers, signers);
+        assert_eq!(
+            session.hot_address.addr,
+            multisig_account(&signers, session.threshold)
+        );
+    })
+}
+
+#[test]
+fn test_push_finalized_header() {
+    ExtBuilder::default().build_and_execute(|| {
+        let best = XGatewayPolkadot::best_finalized();
+        let next = header(best.number + 1, best.hash, H256::zero());
+
+        // 2 of 4 votes are not enough
+        let justification = sign_justification(&next, 0, &voters()[..2]);
+        assert_noop!(
+            XGatewayPolkadot::push_finalized_header(
+                Origin::signed(alice()),
+                next.clone(),
+                justification
+            ),
+            XGatewayPolkadotErr::InsufficientVotes
+        );
+        // signed in another set
+        let justification = sign_justification(&next, 1, &voters()[..3]);
+        assert_noop!(
+            XGatewayPolkadot::push_finalized_header(
+                Origin::signed(alice()),
+                next.clone(),
+                justification
+            ),
+            XGatewayPolkadotErr::InvalidJustification
+        );
+        // justification of another header
+        let other = header(best.number + 1, H256::repeat_byte(2), H256::zero());
+        let justification = sign_justification(&other, 0, &voters()[..3]);
+        assert_noop!(
+            XGatewayPolkadot::push_finalized_header(
+                Origin::signed(alice()),
+                next.clone(),
+                justification
+            ),
+            XGatewayPolkadotErr::InvalidJustification
+        );
+
+        let hash = finalize(next.clone());
+        assert_eq!(XGatewayPolkadot::best_finalized().hash, hash);
+        assert_eq!(XGatewayPolkadot::best_finalized().number, 101);
+
+        let justification = sign_justification(&next, 0, &voters()[..3]);
+        assert_noop!(
+            XGatewayPolkadot::push_finalized_header(Origin::signed(alice()), next, justification),
+            XGatewayPolkadotErr::OldHeader
+        );
+    })
+}
+
+#[test]
+fn test_authority_set_change() {
+    ExtBuilder::default().build_and_execute(|| {
+        let next_voters = (0..3)
+            .map(|_| AuthorityId::generate_pair(None))
+            .collect::<Vec<_>>();
+        let change = ConsensusLog::<u32>::ScheduledChange(ScheduledChange {
+            next_authorities: next_voters.iter().map(|id| (id.clone(), 1)).collect(),
+            delay: 2,
+        });
+        let best = XGatewayPolkadot::best_finalized();
+        let mut scheduling = header(best.number + 1, best.hash, H256::zero());
+        scheduling.digest = Digest {
+            logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, change.encode())],
+        };
+        let scheduling_hash = finalize(scheduling);
+        assert_eq!(XGatewayPolkadot::pending_change().unwrap().enact_at, 103);
+
+        // the enactment header 103 must be finalized before 104
+        let h102 = header(102, scheduling_hash, H256::zero());
+        let h103 = header(103, h102.hash(), H256::zero());
+        let h104 = header(104, h103.hash(), H256::zero());
+        let justification = sign_justification(&h104, 0, &voters()[..3]);
+        assert_noop!(
+            XGatewayPolkadot::push_finalized_header(Origin::signed(alice()), h104, justification),
+            XGatewayPolkadotErr::UnenactedAuthorityChange
+        );
+
+        finalize(h103.clone());
+        assert_eq!(XGatewayPolkadot::pending_change(), None);
+        let set = XGatewayPolkadot::authority_set();
+        assert_eq!(set.set_id, 1);
+        assert_eq!(voters(), next_voters);
+
+        // the next header is finalized by the new authority set
+        let h104 = header(104, h103.hash(), H256::zero());
+        let justification = sign_justification(&h104, 1, &next_voters[..3]);
+        assert_ok!(XGatewayPolkadot::push_finalized_header(
+            Origin::signed(alice()),
+            h104,
+            justification
+        ));
+    })
+}
+
+#[test]
+fn test_push_ancestors() {
+    ExtBuilder::default().build_and_execute(|| {
+        let best = XGatewayPolkadot::best_finalized();
+        let h101 = header(101, best.hash, H256::zero());
+        let h102 = header(102, h101.hash(), H256::zero());
+        let h103 = header(103, h102.hash(), H256::zero());
+        let hash = finalize(h103);
+
+        assert_noop!(
+            XGatewayPolkadot::push_ancestors(
+                Origin::signed(alice()),
+                hash,
+                vec![h101.clone(), h102.clone()]
+            ),
+            XGatewayPolkadotErr::InvalidAncestor
+        );
+        assert_noop!(
+            XGatewayPolkadot::push_ancestors(
+                Origin::signed(alice()),
+                H256::repeat_byte(3),
+                vec![h102.clone()]
+            ),
+            XGatewayPolkadotErr::HeaderNotFound
+        );
+
+        // nothing imported, the relayer pays
+        let info = XGatewayPolkadot::push_ancestors(Origin::signed(alice()), hash, vec![]).unwrap();
+        assert_eq!(info.pays_fee, Pays::Yes);
+
+        let info = XGatewayPolkadot::push_ancestors(
+            Origin::signed(alice()),
+            hash,
+            vec![h102.clone(), h101.clone()],
+        )
+        .unwrap();
+        assert_eq!(info.pays_fee, Pays::No);
+        assert!(XGatewayPolkadot::headers(&h102.hash()).is_some());
+        assert!(XGatewayPolkadot::headers(&h101.hash()).is_some());
+
+        // replayed headers, the relayer pays
+        let info =
+            XGatewayPolkadot::push_ancestors(Origin::signed(alice()), hash, vec![h102.clone()])
+                .unwrap();
+        assert_eq!(info.pays_fee, Pays::Yes);
+    })
+}
+
+#[test]
+fn test_push_transfers_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        bind(bob(), Keyring::Dave);
+
+        let amount = 10_000_000_000u128;
+        let deposit = Transfer {
+            from: Keyring::Dave.to_account_id(),
+            to: hot_address(),
+            amount,
+        };
+        // not a deposit
+        let other = Transfer {
+            from: Keyring::Dave.to_account_id(),
+            to: Keyring::Eve.to_account_id(),
+            amount,
+        };
+        let (block_hash, proof, records) = prepare_transfers(&[other, deposit]);
+
+        // only the trustee multisig could relay the transfers
+        assert_noop!(
+            XGatewayPolkadot::push_transfers(
+                Origin::signed(alice()),
+                block_hash,
+                proof.clone(),
+                records.clone()
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // the records must be the events in the proof
+        assert_noop!(
+            XGatewayPolkadot::push_transfers(
+                Origin::root(),
+                block_hash,
+                proof.clone(),
+                records[1..].to_vec()
+            ),
+            XGatewayPolkadotErr::InvalidEventRecords
+        );
+        assert_noop!(
+            XGatewayPolkadot::push_transfers(
+                Origin::root(),
+                genesis_header().hash(),
+                proof.clone(),
+                records.clone()
+            ),
+            XGatewayPolkadotErr::BadStorageProof
+        );
+
+        assert_ok!(XGatewayPolkadot::push_transfers(
+            Origin::root(),
+            block_hash,
+            proof.clone(),
+            records.clone()
+        ));
+        assert!(XGatewayPolkadot::processed_block(&block_hash));
+        assert_eq!(XAssets::usable_balance(&bob(), &X_DOT), amount);
+        // the deposit is recorded without the extrinsic hash
+        assert!(XGatewayRecords::deposits_by_txid(block_hash).is_empty());
+        let records = XGatewayRecords::deposits_of(bob(), 0, 10);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1.txid, None);
+
+        assert_noop!(
+            XGatewayPolkadot::push_transfers(Origin::root(), block_hash, proof, records),
+            XGatewayPolkadotErr::ReplayedBlock
+        );
+    })
+}
+
+#[test]
+fn test_pending_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        let amount = 10_000_000_000u128;
+        let from = Keyring::Dave.to_account_id();
+        push_transfers(&[
+            Transfer {
+                from: from.clone(),
+                to: hot_address(),
+                amount,
+            },
+            Transfer {
+                from: from.clone(),
+                to: hot_address(),
+                amount,
+            },
+        ]);
+        assert_eq!(XGatewayPolkadot::pending_deposits(&from).len(), 2);
+        assert_eq!(XAssets::total_issuance(&X_DOT), 0);
+
+        // the signature of another account
+        let signature = Keyring::Eve.sign(&bob().encode());
+        assert_noop!(
+            XGatewayPolkadot::bind_dot_address(Origin::signed(bob()), from.clone(), signature),
+            XGatewayPolkadotErr::InvalidSignature
+        );
+
+        bind(bob(), Keyring::Dave);
+        assert!(XGatewayPolkadot::pending_deposits(&from).is_empty());
+        assert_eq!(XAssets::usable_balance(&bob(), &X_DOT), amount * 2);
+    })
+}
+
+#[test]
+fn test_push_transfers_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(
+            XGatewayPolkadot::withdrawal_limit(&X_DOT)
+                .unwrap()
+                .minimal_withdrawal,
+            WITHDRAWAL_FEE * 3 / 2
+        );
+
+        let to = Keyring::Dave.to_account_id();
+        let balance = 10_000_000_000u128;
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_DOT, balance));
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice(),
+            X_DOT,
+            balance,
+            format!("0x{}", hex::encode(&to)).into_bytes(),
+            b"".to_vec().into()
+        ));
+        assert_ok!(XGatewayPolkadot::process_withdrawals(
+            RawOrigin::Root.into(),
+            vec![0]
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+        assert_eq!(XGatewayPolkadot::processing_withdrawals(), vec![0]);
+
+        // mismatched receiver or amount
+        push_transfers(&[
+            Transfer {
+                from: hot_address(),
+                to: Keyring::Eve.to_account_id(),
+                amount: balance - WITHDRAWAL_FEE,
+            },
+            Transfer {
+                from: hot_address(),
+                to: to.clone(),
+                amount: balance - WITHDRAWAL_FEE - 1,
+            },
+        ]);
+        assert_eq!(
+            XGatewayRecords::state_of(0),
+            Some(WithdrawalState::Processing)
+        );
+
+        push_transfers(&[Transfer {
+            from: hot_address(),
+            to,
+            amount: balance - WITHDRAWAL_FEE,
+        }]);
+        assert_eq!(XGatewayRecords::pending_withdrawals(0), None);
+        assert!(XGatewayPolkadot::processing_withdrawals().is_empty());
+        assert_eq!(XAssets::total_issuance(&X_DOT), 0);
+    })
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        let to = Keyring::Dave.to_account_id();
+        let addr = format!("0x{}", hex::encode(&to));
+        assert_ok!(XGatewayPolkadot::check_addr(addr.as_bytes(), b""));
+        assert_eq!(
+            XGatewayPolkadot::parse_address(addr.to_uppercase().replace("0X", "0x").as_bytes()),
+            Some(to)
+        );
+
+        // invalid length or hex
+        assert_noop!(
+            XGatewayPolkadot::check_addr(&addr.as_bytes()[..64], b""),
+            XGatewayPolkadotErr::InvalidAddress
+        );
+        let invalid = addr.replace("0x", "0z");
+        assert_noop!(
+            XGatewayPolkadot::check_addr(invalid.as_bytes(), b""),
+            XGatewayPolkadotErr::InvalidAddress
+        );
+        // the trustee account
+        let hot = format!("0x{}", hex::encode(&hot_address()));
+        assert_noop!(
+            XGatewayPolkadot::check_addr(hot.as_bytes(), b""),
+            XGatewayPolkadotErr::InvalidAddress
+        );
+    })
+}
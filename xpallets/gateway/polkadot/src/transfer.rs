@@ -0,0 +1,164 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Process the transfers from and to the trustee multisig accounts on the relay chain.
+//!
+//! - The transfer to a trustee account is a deposit, which is credited to the ChainX account
+//!   bound to the sender, or kept as pending until the sender is bound.
+//! - The transfer from a trustee account is a withdrawal, which finishes the processing
+//!   withdrawal record of the same receiver and amount (minus the fee).
+//! - The transfer from the last trustee account to the current one is the trustee transition.
+
+use codec::Encode;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::H256;
+use sp_runtime::{traits::Saturating, SaturatedConversion};
+use sp_std::prelude::*;
+
+use xp_logging::{error, info, warn};
+use xpallet_assets::{BalanceOf, Chain, ChainT};
+use xpallet_gateway_common::traits::{AddressBinding, TrusteeSession};
+use xpallet_gateway_records::WithdrawalState;
+
+use crate::trustee::current_trustee_session;
+use crate::types::{DotAccount, PendingDeposit, Transfer};
+use crate::{Error, Event, Module, PendingDeposits, ProcessingWithdrawals, Trait};
+
+/// Process the transfers of the block, returns the count of the processed transfers.
+///
+/// The failed transfers are skipped, and would not be processed again.
+pub fn process_transfers<T: Trait>(block_hash: H256, transfers: Vec<Transfer>) -> u32 {
+    let current = match current_trustee_session::<T>() {
+        Ok(session) => session,
+        Err(_) => {
+            warn!("[process_transfers] No trustee session, skip the transfers");
+            return 0;
+        }
+    };
+    let current_addrs = [current.hot_address.addr, current.cold_address.addr];
+    let last_addrs = T::TrusteeSessionProvider::last_trustee_session()
+        .map(|last| vec![last.hot_address.addr, last.cold_address.addr])
+        .unwrap_or_default();
+    let is_trustee =
+        |account: &DotAccount| current_addrs.contains(account) || last_addrs.contains(account);
+
+    let mut processed = 0;
+    for transfer in transfers {
+        let result = match (is_trustee(&transfer.from), is_trustee(&transfer.to)) {
+            (true, true) => {
+                if last_addrs.contains(&transfer.from) && transfer.to == current_addrs[0] {
+                    info!(
+                        "[process_transfers] Trustee transition was relayed, block:{:?}, transfer:{:?}",
+                        block_hash, transfer
+                    );
+                    T::TrusteeSessionProvider::trustee_transition_relayed(|trustee| {
+                        trustee.addr == transfer.to
+                    });
+                }
+                Ok(())
+            }
+            (false, true) => deposit::<T>(block_hash, transfer.from, transfer.amount),
+            (true, false) => withdraw::<T>(block_hash, transfer.to, transfer.amount),
+            (false, false) => continue,
+        };
+        match result {
+            Ok(()) => processed += 1,
+            Err(err) => error!(
+                "[process_transfers] Process transfer failed, block:{:?}, transfer:{:?}, err:{:?}",
+                block_hash, transfer, err
+            ),
+        }
+    }
+    processed
+}
+
+fn to_balance<T: Trait>(amount: u128) -> Result<BalanceOf<T>, DispatchError> {
+    let balance: BalanceOf<T> = amount.saturated_into();
+    if balance.saturated_into::<u128>() != amount {
+        return Err(Error::<T>::InvalidAmount.into());
+    }
+    Ok(balance)
+}
+
+fn deposit<T: Trait>(block_hash: H256, from: DotAccount, amount: u128) -> DispatchResult {
+    let balance = to_balance::<T>(amount)?;
+    match T::AddressBinding::address(Chain::Polkadot, from.encode()) {
+        Some(who) => deposit_token::<T>(block_hash, &who, balance),
+        None => {
+            warn!(
+                "[deposit] The sender {:?} is not bound, keep the deposit as pending, block:{:?}, balance:{:?}",
+                from, block_hash, balance
+            );
+            PendingDeposits::<T>::append(
+                &from,
+                PendingDeposit {
+                    block_hash,
+                    balance,
+                },
+            );
+            Module::<T>::deposit_event(Event::<T>::DepositPending(block_hash, from, balance));
+            Ok(())
+        }
+    }
+}
+
+/// Credit the X-DOT of the deposit in `block_hash` to `who`.
+pub fn deposit_token<T: Trait>(
+    block_hash: H256,
+    who: &T::AccountId,
+    balance: BalanceOf<T>,
+) -> DispatchResult {
+    let asset_id = <Module<T> as ChainT<_>>::ASSET_ID;
+    // the transfer events do not carry the extrinsic hash, which is left unknown in the
+    // deposit record
+    xpallet_gateway_records::Module::<T>::deposit_with_tx(who, asset_id, balance, None, None)?;
+    info!(
+        "[deposit_token] block:{:?}, who:{:?}, balance:{:?}",
+        block_hash, who, balance
+    );
+    Module::<T>::deposit_event(Event::<T>::Deposited(block_hash, who.clone(), balance));
+    Ok(())
+}
+
+/// Credit the pending deposits of `address` to `who`.
+pub fn claim_pending_deposits<T: Trait>(address: &DotAccount, who: &T::AccountId) {
+    for deposit in PendingDeposits::<T>::take(address) {
+        // ignore error
+        let _ = deposit_token::<T>(deposit.block_hash, who, deposit.balance);
+    }
+}
+
+fn withdraw<T: Trait>(block_hash: H256, to: DotAccount, amount: u128) -> DispatchResult {
+    let amount = to_balance::<T>(amount)?;
+    let fee = Module::<T>::withdrawal_fee();
+    let mut ids = ProcessingWithdrawals::get();
+    // remove the withdrawals which are not processing any more, e.g. recovered by root
+    ids.retain(|id| {
+        xpallet_gateway_records::Module::<T>::state_of(id) == Some(WithdrawalState::Processing)
+    });
+    let position = ids.iter().position(|id| {
+        xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+            .map(|record| {
+                Module::<T>::parse_address(record.addr()) == Some(to.clone())
+                    && amount <= record.balance()
+                    && amount >= record.balance().saturating_sub(fee)
+            })
+            .unwrap_or(false)
+    });
+    let position = match position {
+        Some(position) => position,
+        None => {
+            ProcessingWithdrawals::put(ids);
+            error!(
+                "[withdraw] No processing withdrawal matches the transfer, to:{:?}, amount:{:?}",
+                to, amount
+            );
+            return Err(Error::<T>::NoWithdrawalRecord.into());
+        }
+    };
+    let id = ids.remove(position);
+    ProcessingWithdrawals::put(ids);
+
+    xpallet_gateway_records::Module::<T>::finish_withdrawal(id, Some(Chain::Polkadot))?;
+    Module::<T>::deposit_event(Event::<T>::Withdrawn(block_hash, id));
+    Ok(())
+}
@@ -0,0 +1,112 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Encode;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_io::hashing::blake2_256;
+use sp_std::{convert::TryFrom, prelude::*};
+
+use xp_logging::{error, info};
+use xpallet_gateway_common::{
+    traits::{TrusteeForChain, TrusteeSession},
+    trustees::polkadot::{DotTrusteeAddrInfo, DotTrusteeType},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+
+use crate::types::DotAccount;
+use crate::{Error, Module, Trait};
+
+pub fn current_trustee_session<T: Trait>(
+) -> Result<TrusteeSessionInfo<T::AccountId, DotTrusteeAddrInfo>, DispatchError> {
+    T::TrusteeSessionProvider::current_trustee_session()
+}
+
+/// Returns the multisig account of `pallet_multisig` on the relay chain, `signers` must be
+/// sorted.
+pub fn multisig_account(signers: &[DotAccount], threshold: u16) -> DotAccount {
+    let entropy = (b"modlpy/utilisuba", signers, threshold).using_encoded(blake2_256);
+    DotAccount::new(entropy)
+}
+
+fn check_keys<T: Trait>(keys: &[DotTrusteeType]) -> DispatchResult {
+    let has_duplicate = (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+    if has_duplicate {
+        error!("[generate_new_trustees] Keys contains duplicate pubkey");
+        return Err(Error::<T>::DuplicatedKeys.into());
+    }
+    Ok(())
+}
+
+fn multisig_addr_info(keys: &[DotTrusteeType], threshold: u16) -> DotTrusteeAddrInfo {
+    let mut signers = keys
+        .iter()
+        .map(|key| {
+            let mut account = [0u8; 32];
+            account.copy_from_slice(&key.0);
+            DotAccount::new(account)
+        })
+        .collect::<Vec<_>>();
+    signers.sort();
+    DotTrusteeAddrInfo {
+        addr: multisig_account(&signers, threshold),
+        signers,
+    }
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, DotTrusteeType, DotTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        let trustee_type = DotTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| {
+            error!(
+                "[check_trustee_entity] Not a valid sr25519 public key:{:?}",
+                raw_addr
+            );
+            Error::<T>::InvalidPublicKey
+        })?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<DotTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<DotTrusteeType>, Vec<DotTrusteeType>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_keys::<T>(&hot_keys)?;
+        check_keys::<T>(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people, \
+                can't generate trustee addr",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let sig_num = two_thirds_unsafe(trustees.len() as u32) as u16;
+        let hot_address = multisig_addr_info(&hot_keys, sig_num);
+        let cold_address = multisig_addr_info(&cold_keys, sig_num);
+
+        info!(
+            "[generate_trustee_session_info] hot_addr:{:?}, cold_addr:{:?}, trustee_list:{:?}",
+            hot_address, cold_address, trustees
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold: sig_num,
+            hot_address,
+            cold_address,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityId, AuthorityList, AuthoritySignature, SetId};
+use sp_runtime::{generic, traits::BlakeTwo256, AccountId32, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The block number of the relay chain.
+pub type DotBlockNumber = u32;
+/// The header of the relay chain.
+pub type DotHeader = generic::Header<DotBlockNumber, BlakeTwo256>;
+/// The account of the relay chain, i.e. the sr25519 public key.
+pub type DotAccount = AccountId32;
+
+/// The fields of the finalized relay chain header used by the relay.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotHeaderInfo {
+    pub parent_hash: H256,
+    pub number: DotBlockNumber,
+    pub state_root: H256,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotHeaderIndex {
+    pub hash: H256,
+    pub number: DotBlockNumber,
+}
+
+/// The GRANDPA authority set which finalizes the relay chain headers.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AuthoritySet {
+    pub authorities: AuthorityList,
+    pub set_id: SetId,
+}
+
+/// The scheduled authority set change, enacted when the header `enact_at` is finalized.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingChange {
+    pub next_authorities: AuthorityList,
+    pub enact_at: DotBlockNumber,
+}
+
+/// The precommit vote of GRANDPA.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Precommit {
+    pub target_hash: H256,
+    pub target_number: DotBlockNumber,
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct SignedPrecommit {
+    pub precommit: Precommit,
+    pub signature: AuthoritySignature,
+    pub id: AuthorityId,
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Commit {
+    pub target_hash: H256,
+    pub target_number: DotBlockNumber,
+    pub precommits: Vec<SignedPrecommit>,
+}
+
+/// The GRANDPA justification of a finalized header, same as the encoding of the justification
+/// of the relay chain node.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaJustification {
+    pub round: u64,
+    pub commit: Commit,
+    pub votes_ancestries: Vec<DotHeader>,
+}
+
+/// The deposit from an unbound relay chain account, would be credited after the binding.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingDeposit<Balance> {
+    pub block_hash: H256,
+    pub balance: Balance,
+}
+
+/// The `Balances::Transfer` event of the relay chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Transfer {
+    pub from: DotAccount,
+    pub to: DotAccount,
+    pub amount: u128,
+}
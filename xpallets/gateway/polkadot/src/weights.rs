@@ -0,0 +1,136 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_gateway_polkadot
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! DATE: 2020-11-27, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/chainx
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_gateway_polkadot
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/gateway/polkadot/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_gateway_polkadot.
+pub trait WeightInfo {
+    fn push_finalized_header(p: u32) -> Weight;
+    fn push_ancestors(n: u32) -> Weight;
+    fn push_transfers(n: u32) -> Weight;
+    fn bind_dot_address() -> Weight;
+    fn process_withdrawals(n: u32) -> Weight;
+    fn set_withdrawal_fee() -> Weight;
+    fn set_authority_set() -> Weight;
+    fn set_transfer_event_index() -> Weight;
+}
+
+/// Weights for xpallet_gateway_polkadot using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn push_finalized_header(p: u32) -> Weight {
+        (41_218_000 as Weight)
+            .saturating_add((82_516_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn push_ancestors(n: u32) -> Weight {
+        (12_076_000 as Weight)
+            .saturating_add((9_784_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn push_transfers(n: u32) -> Weight {
+        (97_352_000 as Weight)
+            .saturating_add((31_462_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn bind_dot_address() -> Weight {
+        (118_530_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn process_withdrawals(n: u32) -> Weight {
+        (26_154_000 as Weight)
+            .saturating_add((18_945_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_withdrawal_fee() -> Weight {
+        (4_512_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_authority_set() -> Weight {
+        (8_203_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_transfer_event_index() -> Weight {
+        (3_871_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn push_finalized_header(p: u32) -> Weight {
+        (41_218_000 as Weight)
+            .saturating_add((82_516_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn push_ancestors(n: u32) -> Weight {
+        (12_076_000 as Weight)
+            .saturating_add((9_784_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn push_transfers(n: u32) -> Weight {
+        (97_352_000 as Weight)
+            .saturating_add((31_462_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn bind_dot_address() -> Weight {
+        (118_530_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn process_withdrawals(n: u32) -> Weight {
+        (26_154_000 as Weight)
+            .saturating_add((18_945_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_withdrawal_fee() -> Weight {
+        (4_512_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_authority_set() -> Weight {
+        (8_203_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_transfer_event_index() -> Weight {
+        (3_871_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
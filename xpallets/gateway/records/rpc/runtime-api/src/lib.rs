@@ -3,15 +3,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
 
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use codec::Codec;
+use sp_core::H256;
 
 pub use chainx_primitives::{AssetId, Decimals};
 pub use xpallet_assets::Chain;
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
+pub use xpallet_gateway_records::{
+    DepositRecord, DepositRecordId, Withdrawal, WithdrawalHistory, WithdrawalRecordId,
+    WithdrawalSettlement, WithdrawalState,
+};
 
 sp_api::decl_runtime_apis! {
+    /// Version 2 appends the withdrawal delays, the withdrawal histories and the deposit records.
+    #[api_version(2)]
     pub trait XGatewayRecordsApi<AccountId, Balance, BlockNumber>
     where
         AccountId: Codec,
@@ -21,5 +27,24 @@ sp_api::decl_runtime_apis! {
         fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
 
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        fn withdrawal_delays() -> BTreeMap<WithdrawalRecordId, BlockNumber>;
+
+        fn withdrawals_of(
+            who: AccountId,
+            state_filter: Option<WithdrawalState>,
+            page_index: u32,
+            page_size: u32,
+        ) -> Vec<(WithdrawalRecordId, WithdrawalHistory<AccountId, Balance, BlockNumber>)>;
+
+        fn withdrawal_history(id: WithdrawalRecordId) -> Option<WithdrawalHistory<AccountId, Balance, BlockNumber>>;
+
+        fn deposits_of(
+            who: AccountId,
+            page_index: u32,
+            page_size: u32,
+        ) -> Vec<(DepositRecordId, DepositRecord<AccountId, Balance, BlockNumber>)>;
+
+        fn deposits_by_txid(txid: H256) -> Vec<(DepositRecordId, DepositRecord<AccountId, Balance, BlockNumber>)>;
     }
 }
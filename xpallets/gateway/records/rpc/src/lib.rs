@@ -9,14 +9,16 @@ use codec::Codec;
 use jsonrpc_derive::rpc;
 use serde::{Deserialize, Serialize};
 
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::H256;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 use xp_rpc::{runtime_error_into_rpc_err, Result};
 
 use xpallet_gateway_records_rpc_runtime_api::{
-    AssetId, Chain, Withdrawal, WithdrawalRecordId, WithdrawalState,
+    AssetId, Chain, DepositRecord, DepositRecordId, Withdrawal, WithdrawalHistory,
+    WithdrawalRecordId, WithdrawalSettlement, WithdrawalState,
     XGatewayRecordsApi as GatewayRecordsRuntimeApi,
 };
 
@@ -35,6 +37,41 @@ impl<C, B> XGatewayRecords<C, B> {
     }
 }
 
+impl<C, Block> XGatewayRecords<C, Block>
+where
+    C: ProvideRuntimeApi<Block>,
+    Block: BlockT,
+{
+    /// Return the remaining blocks of the delay of the `Delayed` withdrawals, which are not
+    /// provided by the runtime api before version 2.
+    fn withdrawal_delays<AccountId, Balance, BlockNumber>(
+        &self,
+        at: &BlockId<Block>,
+    ) -> Result<BTreeMap<WithdrawalRecordId, BlockNumber>>
+    where
+        C::Api: GatewayRecordsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        let api = self.client.runtime_api();
+        let has_delays = api
+            .has_api_with::<dyn GatewayRecordsRuntimeApi<
+                Block,
+                AccountId,
+                Balance,
+                BlockNumber,
+                Error = (),
+            >, _>(at, |version| version >= 2)
+            .map_err(runtime_error_into_rpc_err)?;
+        if !has_delays {
+            return Ok(BTreeMap::new());
+        }
+        api.withdrawal_delays(at)
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
 #[rpc]
 pub trait XGatewayRecordsApi<BlockHash, AccountId, Balance, BlockNumber>
 where
@@ -62,6 +99,46 @@ where
         chain: Chain,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<WithdrawalRecordId, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the withdrawals of an account with their state transitions, the latest first.
+    ///
+    /// Only the withdrawals in `state_filter` currently are returned if it's given.
+    #[rpc(name = "xgatewayrecords_withdrawalsOf")]
+    fn withdrawals_of(
+        &self,
+        who: AccountId,
+        state_filter: Option<WithdrawalState>,
+        page_index: u32,
+        page_size: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Page<Vec<RpcWithdrawalHistory<AccountId, Balance, BlockNumber>>>>;
+
+    /// Return the withdrawal `id` with its state transitions and the settling transaction.
+    #[rpc(name = "xgatewayrecords_withdrawalHistory")]
+    fn withdrawal_history(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcWithdrawalHistory<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the deposit records of an account within the retention, the latest first.
+    #[rpc(name = "xgatewayrecords_depositsOf")]
+    fn deposits_of(
+        &self,
+        who: AccountId,
+        page_index: u32,
+        page_size: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Page<Vec<RpcDepositRecord<AccountId, Balance, BlockNumber>>>>;
+
+    /// Return the deposit records credited by the source chain transaction `txid`, in the byte
+    /// order shown by the block explorers.
+    #[rpc(name = "xgatewayrecords_depositsByTxid")]
+    fn deposits_by_txid(
+        &self,
+        txid: H256,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<RpcDepositRecord<AccountId, Balance, BlockNumber>>>;
 }
 
 impl<C, Block, AccountId, Balance, BlockNumber>
@@ -81,10 +158,14 @@ where
     ) -> Result<BTreeMap<u32, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let delays = self.withdrawal_delays(&at)?;
         api.withdrawal_list(&at)
             .map(|map| {
                 map.into_iter()
-                    .map(|(id, withdrawal)| (id, withdrawal.into()))
+                    .map(|(id, withdrawal)| {
+                        let delay_remaining = delays.get(&id).cloned();
+                        (id, RpcWithdrawalRecord::new(withdrawal, delay_remaining))
+                    })
                     .collect()
             })
             .map_err(runtime_error_into_rpc_err)
@@ -97,10 +178,14 @@ where
     ) -> Result<BTreeMap<u32, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let delays = self.withdrawal_delays(&at)?;
         api.withdrawal_list_by_chain(&at, chain)
             .map(|map| {
                 map.into_iter()
-                    .map(|(id, withdrawal)| (id, withdrawal.into()))
+                    .map(|(id, withdrawal)| {
+                        let delay_remaining = delays.get(&id).cloned();
+                        (id, RpcWithdrawalRecord::new(withdrawal, delay_remaining))
+                    })
                     .collect()
             })
             .map_err(runtime_error_into_rpc_err)
@@ -118,7 +203,7 @@ where
                 map.into_iter()
                     .filter_map(|(id, withdrawal)| {
                         if withdrawal.state == WithdrawalState::Applying {
-                            Some((id, withdrawal.into()))
+                            Some((id, RpcWithdrawalRecord::new(withdrawal, None)))
                         } else {
                             None
                         }
@@ -127,6 +212,86 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn withdrawals_of(
+        &self,
+        who: AccountId,
+        state_filter: Option<WithdrawalState>,
+        page_index: u32,
+        page_size: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Page<Vec<RpcWithdrawalHistory<AccountId, Balance, BlockNumber>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let data = api
+            .withdrawals_of(&at, who, state_filter, page_index, page_size)
+            .map(|withdrawals| {
+                withdrawals
+                    .into_iter()
+                    .map(|(id, history)| RpcWithdrawalHistory::new(id, history))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(Page {
+            page_index,
+            page_size,
+            data,
+        })
+    }
+
+    fn withdrawal_history(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcWithdrawalHistory<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.withdrawal_history(&at, id)
+            .map(|history| history.map(|history| RpcWithdrawalHistory::new(id, history)))
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn deposits_of(
+        &self,
+        who: AccountId,
+        page_index: u32,
+        page_size: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Page<Vec<RpcDepositRecord<AccountId, Balance, BlockNumber>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let data = api
+            .deposits_of(&at, who, page_index, page_size)
+            .map(|deposits| {
+                deposits
+                    .into_iter()
+                    .map(|(id, record)| RpcDepositRecord::new(id, record))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(Page {
+            page_index,
+            page_size,
+            data,
+        })
+    }
+
+    fn deposits_by_txid(
+        &self,
+        txid: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<RpcDepositRecord<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.deposits_by_txid(&at, txid)
+            .map(|deposits| {
+                deposits
+                    .into_iter()
+                    .map(|(id, record)| RpcDepositRecord::new(id, record))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -140,13 +305,17 @@ pub struct RpcWithdrawalRecord<AccountId, Balance: Display + FromStr, BlockNumbe
     pub ext: String,
     pub height: BlockNumber,
     pub state: WithdrawalState,
+    /// The remaining blocks before a `Delayed` withdrawal becomes `Applying`.
+    pub delay_remaining: Option<BlockNumber>,
 }
 
 impl<AccountId, Balance: Display + FromStr, BlockNumber>
-    From<Withdrawal<AccountId, Balance, BlockNumber>>
-    for RpcWithdrawalRecord<AccountId, Balance, BlockNumber>
+    RpcWithdrawalRecord<AccountId, Balance, BlockNumber>
 {
-    fn from(record: Withdrawal<AccountId, Balance, BlockNumber>) -> Self {
+    fn new(
+        record: Withdrawal<AccountId, Balance, BlockNumber>,
+        delay_remaining: Option<BlockNumber>,
+    ) -> Self {
         Self {
             asset_id: record.asset_id,
             applicant: record.applicant,
@@ -155,6 +324,91 @@ impl<AccountId, Balance: Display + FromStr, BlockNumber>
             ext: String::from_utf8_lossy(record.ext.as_ref()).into_owned(),
             height: record.height,
             state: record.state,
+            delay_remaining,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub page_index: u32,
+    pub page_size: u32,
+    pub data: T,
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWithdrawalHistory<AccountId, Balance: Display + FromStr, BlockNumber> {
+    pub id: WithdrawalRecordId,
+    pub asset_id: AssetId,
+    pub applicant: AccountId,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
+    pub addr: String,
+    pub ext: String,
+    pub height: BlockNumber,
+    /// The current state, i.e. the state of the last transition.
+    pub state: WithdrawalState,
+    /// The state transitions and the block numbers.
+    pub transitions: Vec<(WithdrawalState, BlockNumber)>,
+    /// The target chain transaction which settled the withdrawal.
+    pub settlement: Option<WithdrawalSettlement>,
+}
+
+impl<AccountId, Balance: Display + FromStr, BlockNumber>
+    RpcWithdrawalHistory<AccountId, Balance, BlockNumber>
+{
+    fn new(
+        id: WithdrawalRecordId,
+        history: WithdrawalHistory<AccountId, Balance, BlockNumber>,
+    ) -> Self {
+        let state = history.state();
+        let withdrawal = Withdrawal::new(history.record, state);
+        Self {
+            id,
+            asset_id: withdrawal.asset_id,
+            applicant: withdrawal.applicant,
+            balance: withdrawal.balance,
+            addr: String::from_utf8_lossy(withdrawal.addr.as_ref()).into_owned(),
+            ext: String::from_utf8_lossy(withdrawal.ext.as_ref()).into_owned(),
+            height: withdrawal.height,
+            state: withdrawal.state,
+            transitions: history.transitions,
+            settlement: history.settlement,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDepositRecord<AccountId, Balance: Display + FromStr, BlockNumber> {
+    pub id: DepositRecordId,
+    pub asset_id: AssetId,
+    pub depositor: AccountId,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
+    /// The txid of the deposit transaction on the source chain, if it's known.
+    pub txid: Option<H256>,
+    pub referral: Option<String>,
+    /// The block number when the deposit was credited.
+    pub height: BlockNumber,
+}
+
+impl<AccountId, Balance: Display + FromStr, BlockNumber>
+    RpcDepositRecord<AccountId, Balance, BlockNumber>
+{
+    fn new(id: DepositRecordId, record: DepositRecord<AccountId, Balance, BlockNumber>) -> Self {
+        Self {
+            id,
+            asset_id: record.asset_id,
+            depositor: record.depositor,
+            balance: record.balance,
+            txid: record.txid,
+            referral: record
+                .referral
+                .map(|referral| String::from_utf8_lossy(&referral).into_owned()),
+            height: record.height,
         }
     }
 }
@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
 use frame_system::RawOrigin;
 
 use super::*;
@@ -89,6 +90,36 @@ benchmarks! {
     verify {
         assert_eq!(XGatewayRecords::<T>::state_of(0), None);
     }
+
+    set_withdrawal_delay {
+        let delay = WithdrawalDelay {
+            threshold: 100.into(),
+            delay: 10.into(),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(delay.clone()))
+    verify {
+        assert_eq!(XGatewayRecords::<T>::withdrawal_delay_of(ASSET_ID), Some(delay));
+    }
+
+    freeze_withdrawal {
+        let receiver: T::AccountId = whitelisted_caller();
+        let amount: BalanceOf<T> = 1000.into();
+        deposit::<T>(receiver.clone(), amount);
+        WithdrawalDelayConfigOf::<T>::insert(ASSET_ID, WithdrawalDelay {
+            threshold: 100.into(),
+            delay: 10.into(),
+        });
+        let addr = b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF".to_vec();
+        let memo = b"memo".to_vec().into();
+        XGatewayRecords::<T>::withdraw(&receiver, ASSET_ID, amount, addr, memo).unwrap();
+        assert_eq!(XGatewayRecords::<T>::state_of(0), Some(WithdrawalState::Delayed));
+        let origin = T::FreezeOrigin::successful_origin();
+        let call = Call::<T>::freeze_withdrawal(0);
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(XGatewayRecords::<T>::state_of(0), Some(WithdrawalState::Frozen));
+        assert_eq!(XGatewayRecords::<T>::delayed_withdrawals(0), None);
+    }
 }
 
 #[cfg(test)]
@@ -103,6 +134,8 @@ mod tests {
             assert_ok!(test_benchmark_root_deposit::<Test>());
             assert_ok!(test_benchmark_root_withdraw::<Test>());
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_delay::<Test>());
+            assert_ok!(test_benchmark_freeze_withdrawal::<Test>());
         });
     }
 }
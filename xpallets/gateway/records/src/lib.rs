@@ -17,20 +17,27 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
-    ensure, IterableStorageMap,
+    ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Weight,
+    IterableStorageMap,
 };
 use frame_system::ensure_root;
-use sp_runtime::traits::StaticLookup;
+use sp_core::H256;
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
 
 use orml_utilities::with_transaction_result;
 
-use chainx_primitives::{AddrStr, AssetId};
+use chainx_primitives::{AddrStr, AssetId, ReferralId};
 use xp_logging::{error, info};
 use xp_runtime::Memo;
 use xpallet_assets::{AssetType, BalanceOf, Chain};
 use xpallet_support::try_addr;
 
-pub use self::types::{Withdrawal, WithdrawalRecord, WithdrawalRecordId, WithdrawalState};
+pub use self::types::{
+    DepositRecord, DepositRecordId, Withdrawal, WithdrawalDelay, WithdrawalHistory,
+    WithdrawalRecord, WithdrawalRecordId, WithdrawalSettlement, WithdrawalState,
+};
 pub use self::weights::WeightInfo;
 
 pub type WithdrawalRecordOf<T> = WithdrawalRecord<
@@ -39,6 +46,21 @@ pub type WithdrawalRecordOf<T> = WithdrawalRecord<
     <T as frame_system::Trait>::BlockNumber,
 >;
 
+pub type WithdrawalHistoryOf<T> = WithdrawalHistory<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type DepositRecordOf<T> = DepositRecord<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type WithdrawalDelayOf<T> =
+    WithdrawalDelay<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
@@ -46,6 +68,13 @@ pub trait Trait: xpallet_assets::Trait {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+    /// The origin which can freeze the delayed withdrawals, e.g. the council or the trustees.
+    type FreezeOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The blocks to keep the history of a finished or canceled withdrawal and the record of a
+    /// deposit, zero to keep them forever.
+    type WithdrawalHistoryRetention: Get<Self::BlockNumber>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -65,6 +94,12 @@ decl_error! {
         InvalidState,
         /// Meet unexpected chain
         UnexpectedChain,
+        /// WithdrawalRecord state not `Delayed`
+        NotDelayedState,
+        /// The delay of withdrawal must be greater than zero
+        InvalidWithdrawalDelay,
+        /// The id of deposit record overflows
+        DepositRecordIdOverflow,
     }
 }
 
@@ -73,6 +108,7 @@ decl_event!(
     pub enum Event<T>
     where
         <T as frame_system::Trait>::AccountId,
+        <T as frame_system::Trait>::BlockNumber,
         Balance = BalanceOf<T>,
         WithdrawalRecord = WithdrawalRecordOf<T>
     {
@@ -88,6 +124,14 @@ decl_event!(
         WithdrawalCanceled(WithdrawalRecordId, WithdrawalState),
         /// A withdrawal proposal was finished successfully. [withdrawal_id, withdrawal_state]
         WithdrawalFinished(WithdrawalRecordId, WithdrawalState),
+        /// A large withdrawal was delayed until the given height. [withdrawal_id, unlock_height]
+        WithdrawalDelayed(WithdrawalRecordId, BlockNumber),
+        /// The delay of a withdrawal was over, it's `Applying` now. [withdrawal_id]
+        WithdrawalDelayEnded(WithdrawalRecordId),
+        /// A delayed withdrawal was frozen. [withdrawal_id]
+        WithdrawalFrozen(WithdrawalRecordId),
+        /// A frozen withdrawal was released to `Applying` by root. [withdrawal_id]
+        WithdrawalUnfrozen(WithdrawalRecordId),
     }
 );
 
@@ -103,6 +147,49 @@ decl_storage! {
 
         /// The id of next withdrawal record.
         pub NextWithdrawalRecordId get(fn id): WithdrawalRecordId = 0;
+
+        /// The withdrawal delay config of an asset, no delay if it's None.
+        pub WithdrawalDelayConfigOf get(fn withdrawal_delay_of):
+            map hasher(twox_64_concat) AssetId => Option<WithdrawalDelayOf<T>>;
+
+        /// The unlock height of a `Delayed` withdrawal.
+        pub DelayedWithdrawals get(fn delayed_withdrawals):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<T::BlockNumber>;
+
+        /// The withdrawals that would be unlocked at a given height.
+        pub DelayedWithdrawalsAt get(fn delayed_withdrawals_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<WithdrawalRecordId>;
+
+        /// The lifecycle of a withdrawal record, kept after it's finished or canceled.
+        pub WithdrawalHistoryOf get(fn withdrawal_history_of):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<WithdrawalHistoryOf<T>>;
+
+        /// The withdrawal records applied by an account, in the order of creation.
+        pub AccountWithdrawals get(fn account_withdrawals):
+            map hasher(blake2_128_concat) T::AccountId => Vec<WithdrawalRecordId>;
+
+        /// The finished or canceled withdrawals whose history would be pruned at a given height.
+        pub WithdrawalHistoryExpiresAt get(fn withdrawal_history_expires_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<WithdrawalRecordId>;
+
+        /// The credited deposits, use serial numbers to mark them.
+        pub DepositRecords get(fn deposit_record):
+            map hasher(twox_64_concat) DepositRecordId => Option<DepositRecordOf<T>>;
+
+        /// The id of next deposit record.
+        pub NextDepositRecordId get(fn deposit_id): DepositRecordId = 0;
+
+        /// The deposit records of an account, in the order of creation.
+        pub AccountDeposits get(fn account_deposits):
+            map hasher(blake2_128_concat) T::AccountId => Vec<DepositRecordId>;
+
+        /// The deposit records credited by a source chain transaction.
+        pub TxDeposits get(fn tx_deposits):
+            map hasher(identity) H256 => Vec<DepositRecordId>;
+
+        /// The deposit records which would be pruned at a given height.
+        pub DepositRecordsExpireAt get(fn deposit_records_expire_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<DepositRecordId>;
     }
 }
 
@@ -112,6 +199,16 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            Self::unlock_delayed_withdrawals(n)
+                + Self::prune_withdrawal_history(n)
+                + Self::prune_deposit_records(n)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_withdrawal_history()
+        }
+
         /// Deposit asset token.
         ///
         /// This is a root-only operation.
@@ -171,6 +268,36 @@ decl_module! {
             }
             Ok(())
         }
+
+        /// Set the delay config for the large withdrawals of an asset, `None` to disable it.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_delay()]
+        pub fn set_withdrawal_delay(
+            origin,
+            #[compact] asset_id: AssetId,
+            delay: Option<WithdrawalDelayOf<T>>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match delay {
+                Some(delay) => {
+                    ensure!(!delay.delay.is_zero(), Error::<T>::InvalidWithdrawalDelay);
+                    WithdrawalDelayConfigOf::<T>::insert(asset_id, delay);
+                }
+                None => WithdrawalDelayConfigOf::<T>::remove(asset_id),
+            }
+            Ok(())
+        }
+
+        /// Freeze a `Delayed` withdrawal, only root could release or cancel it then.
+        ///
+        /// This is an operation for `FreezeOrigin`, e.g. the council or the trustees.
+        #[weight = <T as Trait>::WeightInfo::freeze_withdrawal()]
+        pub fn freeze_withdrawal(origin, #[compact] withdrawal_id: WithdrawalRecordId) -> DispatchResult {
+            T::FreezeOrigin::ensure_origin(origin)?;
+            let (_, curr_state) = Self::ensure_withdrawal_records_exists(withdrawal_id)?;
+            Self::freeze_withdrawal_impl(withdrawal_id, curr_state)
+        }
     }
 }
 
@@ -201,6 +328,125 @@ impl<T: Trait> Module<T> {
         let state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
         Ok((record, state))
     }
+
+    /// Set the state of a pending withdrawal and note the transition in its history.
+    fn set_state(id: WithdrawalRecordId, state: WithdrawalState) {
+        WithdrawalStateOf::insert(id, state);
+        Self::note_transition(id, state);
+    }
+
+    /// Remove the state of a finished or canceled withdrawal, the final state is kept in the
+    /// history until the retention is over.
+    fn remove_state(id: WithdrawalRecordId, final_state: WithdrawalState) {
+        WithdrawalStateOf::remove(id);
+        Self::note_transition(id, final_state);
+
+        let retention = T::WithdrawalHistoryRetention::get();
+        if !retention.is_zero() {
+            let now = frame_system::Module::<T>::block_number();
+            WithdrawalHistoryExpiresAt::<T>::append(now.saturating_add(retention), id);
+        }
+    }
+
+    /// Remove the history of the withdrawals whose retention is over at height `n`.
+    fn prune_withdrawal_history(n: T::BlockNumber) -> Weight {
+        let ids = WithdrawalHistoryExpiresAt::<T>::take(n);
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+        for id in ids {
+            reads += 2;
+            if let Some(history) = WithdrawalHistoryOf::<T>::take(id) {
+                AccountWithdrawals::<T>::mutate(history.record.applicant(), |ids| {
+                    ids.retain(|withdrawal_id| *withdrawal_id != id)
+                });
+                writes += 2;
+            }
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// Remove the deposit records whose retention is over at height `n`.
+    fn prune_deposit_records(n: T::BlockNumber) -> Weight {
+        let ids = DepositRecordsExpireAt::<T>::take(n);
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+        for id in ids {
+            reads += 1;
+            // the record may have been removed since its source chain tx was reverted
+            if let Some(record) = DepositRecords::<T>::take(id) {
+                reads += 1;
+                writes += 2;
+                AccountDeposits::<T>::mutate(&record.depositor, |ids| {
+                    ids.retain(|deposit_id| *deposit_id != id)
+                });
+                if let Some(txid) = record.txid {
+                    reads += 1;
+                    writes += 1;
+                    let mut ids = Self::tx_deposits(txid);
+                    ids.retain(|deposit_id| *deposit_id != id);
+                    if ids.is_empty() {
+                        TxDeposits::remove(txid);
+                    } else {
+                        TxDeposits::insert(txid, ids);
+                    }
+                }
+            }
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// Backfill the history and the account index of the pending withdrawals created before
+    /// the history was introduced.
+    ///
+    /// The earlier transitions are unknown, so the history starts with the current state at the
+    /// upgrade height, and the creation height is kept in the record.
+    fn migrate_withdrawal_history() -> Weight {
+        let now = frame_system::Module::<T>::block_number();
+        let mut reads: Weight = 0;
+        let mut writes: Weight = 0;
+        let mut pending = PendingWithdrawals::<T>::iter()
+            .filter(|(id, _)| {
+                reads += 2;
+                !WithdrawalHistoryOf::<T>::contains_key(id)
+            })
+            .collect::<Vec<_>>();
+        // keep the account index in the order of creation
+        pending.sort_by_key(|(id, _)| *id);
+
+        for (id, record) in pending {
+            reads += 1;
+            let state = match Self::state_of(id) {
+                Some(state) => state,
+                None => continue,
+            };
+            AccountWithdrawals::<T>::append(record.applicant(), id);
+            WithdrawalHistoryOf::<T>::insert(
+                id,
+                WithdrawalHistory {
+                    record,
+                    transitions: vec![(state, now)],
+                    settlement: None,
+                },
+            );
+            writes += 2;
+        }
+        if writes > 0 {
+            info!(
+                "[migrate_withdrawal_history] Backfill the history of {} withdrawals",
+                writes / 2
+            );
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    fn note_transition(id: WithdrawalRecordId, state: WithdrawalState) {
+        let now = frame_system::Module::<T>::block_number();
+        WithdrawalHistoryOf::<T>::mutate(id, |history| {
+            if let Some(history) = history {
+                history.transitions.push((state, now));
+            }
+        });
+    }
 }
 
 impl<T: Trait> Module<T> {
@@ -208,21 +454,72 @@ impl<T: Trait> Module<T> {
     ///
     /// NOTE: this function has included deposit_init and deposit_finish (not wait for block confirm)
     pub fn deposit(who: &T::AccountId, asset_id: AssetId, balance: BalanceOf<T>) -> DispatchResult {
+        Self::deposit_with_tx(who, asset_id, balance, None, None)
+    }
+
+    /// Deposit asset which is sent by the source chain transaction `txid`, and keep a deposit
+    /// record for it.
+    pub fn deposit_with_tx(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        balance: BalanceOf<T>,
+        txid: Option<H256>,
+        referral: Option<ReferralId>,
+    ) -> DispatchResult {
         xpallet_assets::Module::<T>::ensure_not_native_asset(&asset_id)?;
 
         info!(
-            "[deposit] who:{:?}, id:{}, balance:{:?}",
-            who, asset_id, balance
+            "[deposit] who:{:?}, id:{}, balance:{:?}, txid:{:?}",
+            who, asset_id, balance, txid
         );
 
+        // the ids are never reused, or the records of the old deposits would be overwritten
+        let id = Self::deposit_id();
+        let next_id = id
+            .checked_add(1)
+            .ok_or(Error::<T>::DepositRecordIdOverflow)?;
+
         xpallet_assets::Module::<T>::issue(&asset_id, who, balance)?;
+
+        let record = DepositRecord {
+            asset_id,
+            depositor: who.clone(),
+            balance,
+            txid,
+            referral,
+            height: frame_system::Module::<T>::block_number(),
+        };
+        DepositRecords::<T>::insert(id, record);
+        AccountDeposits::<T>::append(who, id);
+        if let Some(txid) = txid {
+            TxDeposits::append(txid, id);
+        }
+        NextDepositRecordId::put(next_id);
+        let retention = T::WithdrawalHistoryRetention::get();
+        if !retention.is_zero() {
+            let now = frame_system::Module::<T>::block_number();
+            DepositRecordsExpireAt::<T>::append(now.saturating_add(retention), id);
+        }
+
         Self::deposit_event(Event::<T>::Deposited(who.clone(), asset_id, balance));
         Ok(())
     }
 
+    /// Remove the deposit records of the source chain transaction `txid`, which is reverted
+    /// since its block has left the main chain of the source chain.
+    pub fn remove_deposit_records(txid: &H256) {
+        for id in TxDeposits::take(txid) {
+            if let Some(record) = DepositRecords::<T>::take(id) {
+                AccountDeposits::<T>::mutate(&record.depositor, |ids| {
+                    ids.retain(|deposit_id| *deposit_id != id)
+                });
+            }
+        }
+    }
+
     /// Withdrawal asset (lock asset token firstly, follow-up operations are required).
     ///
-    /// WithdrawalRecord State: `Applying`
+    /// WithdrawalRecord State: `Applying`, or `Delayed` if the balance reaches the delay threshold.
     ///
     /// NOTE: this function has included withdrawal_init and withdrawal_locking.
     pub fn withdraw(
@@ -254,11 +551,32 @@ impl<T: Trait> Module<T> {
 
         // Set storages
         PendingWithdrawals::<T>::insert(id, record.clone());
-        WithdrawalStateOf::insert(id, WithdrawalState::Applying);
+        WithdrawalHistoryOf::<T>::insert(
+            id,
+            WithdrawalHistory {
+                record: record.clone(),
+                transitions: vec![],
+                settlement: None,
+            },
+        );
+        AccountWithdrawals::<T>::append(who, id);
+        let unlock_height = Self::withdrawal_delay_of(asset_id)
+            .filter(|config| balance >= config.threshold)
+            .map(|config| height.saturating_add(config.delay));
+        if let Some(unlock_height) = unlock_height {
+            Self::set_state(id, WithdrawalState::Delayed);
+            DelayedWithdrawals::<T>::insert(id, unlock_height);
+            DelayedWithdrawalsAt::<T>::append(unlock_height, id);
+        } else {
+            Self::set_state(id, WithdrawalState::Applying);
+        }
         let next_id = id.checked_add(1_u32).unwrap_or(0);
         NextWithdrawalRecordId::put(next_id);
 
         Self::deposit_event(Event::<T>::WithdrawalCreated(id, record));
+        if let Some(unlock_height) = unlock_height {
+            Self::deposit_event(Event::<T>::WithdrawalDelayed(id, unlock_height));
+        }
         Ok(())
     }
 
@@ -282,7 +600,7 @@ impl<T: Trait> Module<T> {
             );
             return Err(Error::<T>::NotApplyingState.into());
         }
-        WithdrawalStateOf::insert(id, WithdrawalState::Processing);
+        Self::set_state(id, WithdrawalState::Processing);
         Self::deposit_event(Event::<T>::WithdrawalProcessed(id));
         Ok(())
     }
@@ -317,14 +635,14 @@ impl<T: Trait> Module<T> {
             );
             return Err(Error::<T>::NotProcessingState.into());
         }
-        WithdrawalStateOf::insert(id, WithdrawalState::Applying);
+        Self::set_state(id, WithdrawalState::Applying);
         Self::deposit_event(Event::<T>::WithdrawalRecovered(id));
         Ok(())
     }
 
     /// Cancel withdrawal
     ///
-    /// WithdrawalRecord State: `Applying` ==> `NormalCancel`
+    /// WithdrawalRecord State: `Applying`|`Delayed` ==> `NormalCancel`
     pub fn cancel_withdrawal(id: WithdrawalRecordId, who: &T::AccountId) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         if record.applicant() != who {
@@ -346,12 +664,17 @@ impl<T: Trait> Module<T> {
         curr_state: WithdrawalState,
         new_state: WithdrawalState,
     ) -> DispatchResult {
-        if curr_state != WithdrawalState::Applying {
-            error!(
-                "[cancel_withdrawal] id:{}, current withdrawal state ({:?}) must be `Applying`",
-                id, curr_state
-            );
-            return Err(Error::<T>::NotApplyingState.into());
+        match (curr_state, new_state) {
+            (WithdrawalState::Applying, _)
+            | (WithdrawalState::Delayed, _)
+            | (WithdrawalState::Frozen, WithdrawalState::RootCancel) => { /*do nothing*/ }
+            _ => {
+                error!(
+                    "[cancel_withdrawal] id:{}, current withdrawal state ({:?}) must be `Applying` or `Delayed`",
+                    id, curr_state
+                );
+                return Err(Error::<T>::NotApplyingState.into());
+            }
         }
 
         // Unlock reserved asset
@@ -359,12 +682,74 @@ impl<T: Trait> Module<T> {
 
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
-        WithdrawalStateOf::remove(id);
+        Self::remove_state(id, new_state);
+        DelayedWithdrawals::<T>::remove(id);
 
         Self::deposit_event(Event::<T>::WithdrawalCanceled(id, new_state));
         Ok(())
     }
 
+    fn freeze_withdrawal_impl(
+        id: WithdrawalRecordId,
+        curr_state: WithdrawalState,
+    ) -> DispatchResult {
+        if curr_state != WithdrawalState::Delayed {
+            error!(
+                "[freeze_withdrawal] id:{}, current withdrawal state ({:?}) must be `Delayed`",
+                id, curr_state
+            );
+            return Err(Error::<T>::NotDelayedState.into());
+        }
+        Self::set_state(id, WithdrawalState::Frozen);
+        DelayedWithdrawals::<T>::remove(id);
+        Self::deposit_event(Event::<T>::WithdrawalFrozen(id));
+        Ok(())
+    }
+
+    /// Release a `Delayed` or `Frozen` withdrawal.
+    ///
+    /// WithdrawalRecord State: `Delayed`|`Frozen` ==> `Applying`
+    fn release_withdrawal_impl(
+        id: WithdrawalRecordId,
+        curr_state: WithdrawalState,
+    ) -> DispatchResult {
+        let event = match curr_state {
+            WithdrawalState::Delayed => Event::<T>::WithdrawalDelayEnded(id),
+            WithdrawalState::Frozen => Event::<T>::WithdrawalUnfrozen(id),
+            _ => {
+                error!(
+                    "[release_withdrawal] id:{}, current withdrawal state ({:?}) must be `Delayed` or `Frozen`",
+                    id, curr_state
+                );
+                return Err(Error::<T>::NotDelayedState.into());
+            }
+        };
+        Self::set_state(id, WithdrawalState::Applying);
+        DelayedWithdrawals::<T>::remove(id);
+        Self::deposit_event(event);
+        Ok(())
+    }
+
+    /// Release the `Delayed` withdrawals whose delay ends at height `n`.
+    fn unlock_delayed_withdrawals(n: T::BlockNumber) -> Weight {
+        let ids = DelayedWithdrawalsAt::<T>::take(n);
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+        for id in ids {
+            reads += 2;
+            // The frozen or canceled withdrawals have been removed from `DelayedWithdrawals`.
+            if Self::delayed_withdrawals(id) != Some(n) {
+                continue;
+            }
+            if let Some(state) = Self::state_of(id) {
+                if Self::release_withdrawal_impl(id, state).is_ok() {
+                    writes += 2;
+                }
+            }
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
     /// Finish withdrawal, destroy the reserved withdrawal asset token.
     ///
     /// WithdrawalRecord State: `Processing` ==> `NormalFinish`
@@ -408,12 +793,22 @@ impl<T: Trait> Module<T> {
 
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
-        WithdrawalStateOf::remove(id);
+        Self::remove_state(id, new_state);
 
         Self::deposit_event(Event::<T>::WithdrawalFinished(id, new_state));
         Ok(())
     }
 
+    /// Note the target chain transaction `txid` (included in `block_hash`) which settled the
+    /// finished withdrawal.
+    pub fn set_withdrawal_settlement(id: WithdrawalRecordId, txid: H256, block_hash: Option<H256>) {
+        WithdrawalHistoryOf::<T>::mutate(id, |history| {
+            if let Some(history) = history {
+                history.settlement = Some(WithdrawalSettlement { txid, block_hash });
+            }
+        });
+    }
+
     /// Finish withdrawal in batches.
     pub fn finish_withdrawals(
         ids: &[WithdrawalRecordId],
@@ -459,6 +854,21 @@ impl<T: Trait> Module<T> {
                 // State: `Processing` ==> `NormalFinish`|`RootFinish`
                 Self::finish_withdrawal_impl(id, record, curr_state, new_state)
             }
+            (WithdrawalState::Delayed, WithdrawalState::Frozen) => {
+                // State: `Delayed` ==> `Frozen`
+                Self::freeze_withdrawal_impl(id, curr_state)
+            }
+            (WithdrawalState::Delayed, WithdrawalState::Applying)
+            | (WithdrawalState::Frozen, WithdrawalState::Applying) => {
+                // State: `Delayed`|`Frozen` ==> `Applying`
+                Self::release_withdrawal_impl(id, curr_state)
+            }
+            (WithdrawalState::Delayed, WithdrawalState::NormalCancel)
+            | (WithdrawalState::Delayed, WithdrawalState::RootCancel)
+            | (WithdrawalState::Frozen, WithdrawalState::RootCancel) => {
+                // State: `Delayed`|`Frozen` ==> `NormalCancel`|`RootCancel`
+                Self::cancel_withdrawal_impl(id, record, curr_state, new_state)
+            }
             _ => {
                 error!(
                     "[set_withdrawal_state_by_root] Shouldn't happen normally, unless called by root, \
@@ -543,6 +953,14 @@ impl<T: Trait> Module<T> {
             .collect()
     }
 
+    /// Get the remaining blocks of the delay of the `Delayed` withdrawals.
+    pub fn withdrawal_delays() -> BTreeMap<WithdrawalRecordId, T::BlockNumber> {
+        let now = frame_system::Module::<T>::block_number();
+        DelayedWithdrawals::<T>::iter()
+            .map(|(id, unlock)| (id, unlock.saturating_sub(now)))
+            .collect()
+    }
+
     pub fn withdrawals_list_by_chain(
         chain: Chain,
     ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
@@ -553,4 +971,49 @@ impl<T: Trait> Module<T> {
             })
             .collect()
     }
+
+    /// Get the withdrawal histories of an account, the latest first.
+    ///
+    /// Only the withdrawals whose current state is `state_filter` are returned if it's given,
+    /// and the returned data will be empty if `page_index` is invalid.
+    pub fn withdrawals_of(
+        who: T::AccountId,
+        state_filter: Option<WithdrawalState>,
+        page_index: u32,
+        page_size: u32,
+    ) -> Vec<(WithdrawalRecordId, WithdrawalHistoryOf<T>)> {
+        Self::account_withdrawals(who)
+            .into_iter()
+            .rev()
+            .filter_map(|id| Self::withdrawal_history_of(id).map(|history| (id, history)))
+            .filter(|(_, history)| state_filter.map_or(true, |state| history.state() == state))
+            .skip(page_index.saturating_mul(page_size) as usize)
+            .take(page_size as usize)
+            .collect()
+    }
+
+    /// Get the deposit records of an account, the latest first.
+    ///
+    /// The returned data will be empty if `page_index` is invalid.
+    pub fn deposits_of(
+        who: T::AccountId,
+        page_index: u32,
+        page_size: u32,
+    ) -> Vec<(DepositRecordId, DepositRecordOf<T>)> {
+        Self::account_deposits(who)
+            .into_iter()
+            .rev()
+            .filter_map(|id| Self::deposit_record(id).map(|record| (id, record)))
+            .skip(page_index.saturating_mul(page_size) as usize)
+            .take(page_size as usize)
+            .collect()
+    }
+
+    /// Get the deposit records credited by the source chain transaction `txid`.
+    pub fn deposits_by_txid(txid: H256) -> Vec<(DepositRecordId, DepositRecordOf<T>)> {
+        Self::tx_deposits(txid)
+            .into_iter()
+            .filter_map(|id| Self::deposit_record(id).map(|record| (id, record)))
+            .collect()
+    }
 }
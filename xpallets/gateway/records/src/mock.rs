@@ -9,7 +9,7 @@ use sp_runtime::{
 };
 
 use chainx_primitives::AssetId;
-pub use xp_protocol::{X_BTC, X_ETH};
+pub use xp_protocol::{X_BTC, X_ETH, X_LOCAL};
 use xpallet_assets::AssetRestrictions;
 use xpallet_assets_registrar::AssetInfo;
 
@@ -98,8 +98,14 @@ impl xpallet_assets::Trait for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const WithdrawalHistoryRetention: u64 = 100;
+}
+
 impl Trait for Test {
     type Event = ();
+    type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+    type WithdrawalHistoryRetention = WithdrawalHistoryRetention;
     type WeightInfo = ();
 }
 
@@ -137,6 +143,20 @@ pub(crate) fn eth() -> (AssetId, AssetInfo, AssetRestrictions) {
         AssetRestrictions::DESTROY_USABLE,
     )
 }
+pub(crate) fn local() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_LOCAL,
+        AssetInfo::new::<Test>(
+            b"X-LOCAL".to_vec(),
+            b"X-LOCAL".to_vec(),
+            Chain::Local,
+            xp_protocol::LOCAL_DECIMALS,
+            b"ChainX's dummy local chain asset".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
@@ -157,9 +177,11 @@ impl ExtBuilder {
 
         let btc_assets = btc();
         let eth_assets = eth();
+        let local_assets = local();
         let assets = vec![
             (btc_assets.0, btc_assets.1, btc_assets.2, true, true),
             (eth_assets.0, eth_assets.1, eth_assets.2, true, true),
+            (local_assets.0, local_assets.1, local_assets.2, true, true),
         ];
         let mut endowed = BTreeMap::new();
         let endowed_info = vec![(ALICE, 100), (BOB, 200), (CHARLIE, 300), (DAVE, 400)];
@@ -3,7 +3,10 @@
 pub use super::mock::*;
 use super::*;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{OnInitialize, OnRuntimeUpgrade},
+};
 use frame_system::RawOrigin;
 
 #[test]
@@ -194,3 +197,429 @@ fn test_withdrawal_chainx() {
         );
     })
 }
+
+#[test]
+fn test_delayed_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XRecords::set_withdrawal_delay(
+                RawOrigin::Root.into(),
+                X_BTC,
+                Some(WithdrawalDelay {
+                    threshold: 50,
+                    delay: 0
+                })
+            ),
+            XRecordsErr::InvalidWithdrawalDelay
+        );
+        assert_ok!(XRecords::set_withdrawal_delay(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalDelay {
+                threshold: 50,
+                delay: 10
+            })
+        ));
+
+        // below the threshold
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            49,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(XRecords::state_of(0), Some(WithdrawalState::Applying));
+
+        // reach the threshold
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(XRecords::state_of(1), Some(WithdrawalState::Delayed));
+        assert_eq!(XRecords::delayed_withdrawals(1), Some(11));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 - 49 - 50);
+        assert_noop!(
+            XRecords::process_withdrawals(&[1], Chain::Bitcoin),
+            XRecordsErr::NotApplyingState
+        );
+
+        System::set_block_number(5);
+        let delays = XRecords::withdrawal_delays();
+        assert_eq!(delays.get(&0), None);
+        assert_eq!(delays.get(&1), Some(&6));
+
+        // the delay ends
+        System::set_block_number(11);
+        XRecords::on_initialize(11);
+        assert_eq!(XRecords::state_of(1), Some(WithdrawalState::Applying));
+        assert_eq!(XRecords::delayed_withdrawals(1), None);
+        assert!(XRecords::withdrawal_delays().is_empty());
+        assert_ok!(XRecords::process_withdrawals(&[0, 1], Chain::Bitcoin));
+    })
+}
+
+#[test]
+fn test_freeze_delayed_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XRecords::set_withdrawal_delay(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalDelay {
+                threshold: 50,
+                delay: 10
+            })
+        ));
+        for _ in 0..3 {
+            assert_ok!(XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                20,
+                b"addr".to_vec(),
+                b"ext".to_vec().into()
+            ));
+        }
+        assert_ok!(XRecords::set_withdrawal_delay(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalDelay {
+                threshold: 10,
+                delay: 10
+            })
+        ));
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Delayed));
+
+        // only the `Delayed` withdrawal could be frozen
+        assert_noop!(
+            XRecords::freeze_withdrawal(RawOrigin::Signed(ALICE).into(), 3),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XRecords::freeze_withdrawal(RawOrigin::Root.into(), 0),
+            XRecordsErr::NotDelayedState
+        );
+        assert_ok!(XRecords::freeze_withdrawal(RawOrigin::Root.into(), 3));
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Frozen));
+
+        // the frozen withdrawal keeps frozen after the delay
+        XRecords::on_initialize(11);
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Frozen));
+        assert_noop!(
+            XRecords::cancel_withdrawal(3, &ALICE),
+            XRecordsErr::NotApplyingState
+        );
+
+        // root could release it
+        assert_ok!(XRecords::set_withdrawal_state(
+            RawOrigin::Root.into(),
+            3,
+            WithdrawalState::Applying
+        ));
+        assert_eq!(XRecords::state_of(3), Some(WithdrawalState::Applying));
+
+        // or cancel it
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_ok!(XRecords::set_withdrawal_state(
+            RawOrigin::Root.into(),
+            4,
+            WithdrawalState::Frozen
+        ));
+        assert_ok!(XRecords::set_withdrawal_state(
+            RawOrigin::Root.into(),
+            4,
+            WithdrawalState::RootCancel
+        ));
+        assert_eq!(XRecords::state_of(4), None);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 - 20 * 3 - 10);
+    })
+}
+
+#[test]
+fn test_withdrawal_history() {
+    ExtBuilder::default().build_and_execute(|| {
+        let withdraw = |who, balance| {
+            XRecords::withdraw(
+                &who,
+                X_BTC,
+                balance,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+            )
+        };
+        assert_ok!(withdraw(ALICE, 10));
+        assert_ok!(withdraw(ALICE, 20));
+        assert_ok!(withdraw(ALICE, 30));
+        assert_ok!(withdraw(BOB, 40));
+
+        System::set_block_number(2);
+        assert_ok!(XRecords::cancel_withdrawal(1, &ALICE));
+
+        System::set_block_number(3);
+        assert_ok!(XRecords::process_withdrawals(&[0, 3], Chain::Bitcoin));
+        System::set_block_number(4);
+        assert_ok!(XRecords::finish_withdrawal(0, None));
+        XRecords::set_withdrawal_settlement(0, H256::repeat_byte(1), Some(H256::repeat_byte(2)));
+
+        // the history is kept after the withdrawal is finished
+        assert_eq!(XRecords::pending_withdrawals(0), None);
+        let history = XRecords::withdrawal_history_of(0).unwrap();
+        assert_eq!(history.record.balance(), 10);
+        assert_eq!(
+            history.transitions,
+            vec![
+                (WithdrawalState::Applying, 1),
+                (WithdrawalState::Processing, 3),
+                (WithdrawalState::NormalFinish, 4),
+            ]
+        );
+        assert_eq!(history.state(), WithdrawalState::NormalFinish);
+        assert_eq!(
+            history.settlement,
+            Some(WithdrawalSettlement {
+                txid: H256::repeat_byte(1),
+                block_hash: Some(H256::repeat_byte(2)),
+            })
+        );
+        assert_eq!(
+            XRecords::withdrawal_history_of(1).unwrap().transitions,
+            vec![
+                (WithdrawalState::Applying, 1),
+                (WithdrawalState::NormalCancel, 2),
+            ]
+        );
+
+        // the latest first
+        let ids = |who, filter, page_index, page_size| {
+            XRecords::withdrawals_of(who, filter, page_index, page_size)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(ALICE, None, 0, 10), vec![2, 1, 0]);
+        assert_eq!(ids(ALICE, None, 0, 2), vec![2, 1]);
+        assert_eq!(ids(ALICE, None, 1, 2), vec![0]);
+        assert!(ids(ALICE, None, 2, 2).is_empty());
+        assert_eq!(ids(ALICE, Some(WithdrawalState::Applying), 0, 10), vec![2]);
+        assert_eq!(
+            ids(ALICE, Some(WithdrawalState::NormalFinish), 0, 10),
+            vec![0]
+        );
+        assert_eq!(ids(BOB, Some(WithdrawalState::Processing), 0, 10), vec![3]);
+
+        // the history is pruned when the retention is over
+        XRecords::on_initialize(101);
+        assert!(XRecords::withdrawal_history_of(1).is_some());
+        XRecords::on_initialize(102);
+        assert!(XRecords::withdrawal_history_of(1).is_none());
+        assert_eq!(ids(ALICE, None, 0, 10), vec![2, 0]);
+        XRecords::on_initialize(104);
+        assert!(XRecords::withdrawal_history_of(0).is_none());
+        assert_eq!(XRecords::account_withdrawals(ALICE), vec![2]);
+        // the pending ones are kept
+        assert!(XRecords::withdrawal_history_of(2).is_some());
+        assert_eq!(XRecords::account_withdrawals(BOB), vec![3]);
+    })
+}
+
+#[test]
+fn test_migrate_withdrawal_history() {
+    ExtBuilder::default().build_and_execute(|| {
+        let withdraw = |who, balance| {
+            XRecords::withdraw(
+                &who,
+                X_BTC,
+                balance,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+            )
+        };
+        assert_ok!(withdraw(ALICE, 10));
+        assert_ok!(withdraw(BOB, 20));
+        assert_ok!(withdraw(ALICE, 30));
+        assert_ok!(XRecords::process_withdrawals(&[1], Chain::Bitcoin));
+        // the storages before the history was introduced
+        for id in 0..3 {
+            WithdrawalHistoryOf::<Test>::remove(id);
+        }
+        AccountWithdrawals::<Test>::remove(ALICE);
+        AccountWithdrawals::<Test>::remove(BOB);
+
+        System::set_block_number(5);
+        XRecords::on_runtime_upgrade();
+        assert_eq!(XRecords::account_withdrawals(ALICE), vec![0, 2]);
+        assert_eq!(XRecords::account_withdrawals(BOB), vec![1]);
+        let history = XRecords::withdrawal_history_of(1).unwrap();
+        assert_eq!(history.record.balance(), 20);
+        assert_eq!(history.transitions, vec![(WithdrawalState::Processing, 5)]);
+
+        // nothing to migrate for the second time
+        XRecords::on_runtime_upgrade();
+        assert_eq!(XRecords::account_withdrawals(ALICE), vec![0, 2]);
+        assert_eq!(WithdrawalHistoryOf::<Test>::iter().count(), 3);
+    })
+}
+
+#[test]
+fn test_deposit_records() {
+    ExtBuilder::default().build_and_execute(|| {
+        let (tx1, tx2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        System::set_block_number(1);
+        assert_ok!(XRecords::deposit_with_tx(
+            &ALICE,
+            X_BTC,
+            10,
+            Some(tx1),
+            Some(b"referral".to_vec())
+        ));
+        System::set_block_number(2);
+        assert_ok!(XRecords::deposit_with_tx(&BOB, X_BTC, 20, Some(tx2), None));
+        assert_ok!(XRecords::deposit(&ALICE, X_ETH, 30));
+        // the native asset could not be deposited, and no record is kept
+        assert_noop!(
+            XRecords::deposit_with_tx(&ALICE, ChainXAssetId::get(), 10, Some(tx2), None),
+            xpallet_assets::Error::<Test>::DenyNativeAsset
+        );
+        assert_eq!(XRecords::deposit_id(), 3);
+
+        assert_eq!(
+            XRecords::deposit_record(0),
+            Some(DepositRecord {
+                asset_id: X_BTC,
+                depositor: ALICE,
+                balance: 10,
+                txid: Some(tx1),
+                referral: Some(b"referral".to_vec()),
+                height: 1,
+            })
+        );
+        assert_eq!(XRecords::deposit_record(2).unwrap().txid, None);
+
+        // the latest first
+        let ids = |who, page_index, page_size| {
+            XRecords::deposits_of(who, page_index, page_size)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(ALICE, 0, 10), vec![2, 0]);
+        assert_eq!(ids(ALICE, 1, 1), vec![0]);
+        assert!(ids(ALICE, 1, 2).is_empty());
+        assert_eq!(ids(BOB, 0, 10), vec![1]);
+
+        let by_txid = XRecords::deposits_by_txid(tx2);
+        assert_eq!(by_txid.len(), 1);
+        assert_eq!(by_txid[0].0, 1);
+        assert_eq!(by_txid[0].1.depositor, BOB);
+
+        // the records of a reverted deposit tx are removed
+        XRecords::remove_deposit_records(&tx1);
+        assert_eq!(XRecords::deposit_record(0), None);
+        assert!(XRecords::deposits_by_txid(tx1).is_empty());
+        assert_eq!(ids(ALICE, 0, 10), vec![2]);
+    })
+}
+
+#[test]
+fn test_local_chain() {
+    ExtBuilder::default().build_and_execute(|| {
+        // deposit
+        let tx = H256::repeat_byte(1);
+        assert_ok!(XRecords::deposit_with_tx(
+            &ALICE,
+            X_LOCAL,
+            100,
+            Some(tx),
+            None
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_LOCAL), 100);
+        assert_eq!(XRecords::deposits_by_txid(tx).len(), 1);
+
+        // withdraw
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_LOCAL,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        let numbers = XRecords::withdrawals_list_by_chain(Chain::Local)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![0]);
+
+        // settled by the trustees of the local chain
+        assert_noop!(
+            XRecords::process_withdrawals(&[0, 1], Chain::Local),
+            XRecordsErr::UnexpectedChain
+        );
+        assert_ok!(XRecords::process_withdrawals(&numbers, Chain::Local));
+        assert_noop!(
+            XRecords::finish_withdrawals(&numbers, Some(Chain::Bitcoin)),
+            XRecordsErr::UnexpectedChain
+        );
+        assert_ok!(XRecords::finish_withdrawals(&numbers, Some(Chain::Local)));
+        // the local chain tx has no block
+        XRecords::set_withdrawal_settlement(0, H256::repeat_byte(2), None);
+        assert!(XRecords::withdrawals_list_by_chain(Chain::Local).is_empty());
+        assert_eq!(
+            XRecords::withdrawal_history_of(0).unwrap().settlement,
+            Some(WithdrawalSettlement {
+                txid: H256::repeat_byte(2),
+                block_hash: None,
+            })
+        );
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_LOCAL), 50);
+        assert_eq!(XAssets::total_issuance(&X_LOCAL), 50);
+    })
+}
+
+#[test]
+fn test_prune_deposit_records() {
+    ExtBuilder::default().build_and_execute(|| {
+        let tx = H256::repeat_byte(1);
+        assert_ok!(XRecords::deposit_with_tx(&ALICE, X_BTC, 10, Some(tx), None));
+        System::set_block_number(2);
+        assert_ok!(XRecords::deposit_with_tx(&BOB, X_BTC, 20, Some(tx), None));
+        assert_ok!(XRecords::deposit(&ALICE, X_ETH, 30));
+        assert_eq!(XRecords::account_deposits(ALICE), vec![0, 2]);
+
+        // the records are pruned when the retention is over
+        XRecords::on_initialize(101);
+        assert_eq!(XRecords::deposit_record(0), None);
+        assert_eq!(XRecords::account_deposits(ALICE), vec![2]);
+        assert_eq!(XRecords::tx_deposits(tx), vec![1]);
+        XRecords::on_initialize(102);
+        assert_eq!(XRecords::deposit_record(1), None);
+        assert_eq!(XRecords::deposit_record(2), None);
+        assert!(XRecords::account_deposits(ALICE).is_empty());
+        assert!(XRecords::account_deposits(BOB).is_empty());
+        assert!(!TxDeposits::contains_key(tx));
+        // the balances are not affected
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 + 10);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 200 + 20);
+    })
+}
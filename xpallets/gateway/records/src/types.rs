@@ -4,16 +4,24 @@ use codec::{Codec, Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use sp_core::H256;
 use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
 
-use chainx_primitives::{AddrStr, AssetId};
+use chainx_primitives::{AddrStr, AssetId, ReferralId};
 use xp_runtime::Memo;
 
 /// The id of withdrawal record (u32 is enough).
 pub type WithdrawalRecordId = u32;
 
+/// The id of deposit record (u64 to never run out).
+pub type DepositRecordId = u64;
+
 /// The state machine of WithdrawState:
 ///
+/// Delayed (lock token) ----> Frozen (can only be changed by root)
+///     |                           |
+///     v                           v
 /// Applying (lock token) <---> Processing (can't cancel, but can be recovered to `Applying`)
 ///     |                           |
 ///     |                           +----> NormalFinish|RootFinish (destroy token)
@@ -22,6 +30,8 @@ pub type WithdrawalRecordId = u32;
 ///     |                           |
 ///     +---------------------------+----> NormalCancel (unlock token)
 ///
+/// The large withdrawal is `Delayed` for some blocks before becoming `Applying`, and could be
+/// `Frozen` during the delay.
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum WithdrawalState {
@@ -31,6 +41,8 @@ pub enum WithdrawalState {
     RootFinish,
     NormalCancel,
     RootCancel,
+    Delayed,
+    Frozen,
 }
 
 impl Default for WithdrawalState {
@@ -93,6 +105,17 @@ where
     }
 }
 
+/// The delay config of the large withdrawals.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalDelay<Balance, BlockNumber> {
+    /// The withdrawal not less than `threshold` would be delayed.
+    pub threshold: Balance,
+    /// The number of blocks to delay.
+    pub delay: BlockNumber,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
 pub struct Withdrawal<AccountId, Balance, BlockNumber> {
     pub asset_id: AssetId,
@@ -120,3 +143,53 @@ impl<AccountId, Balance, BlockNumber> Withdrawal<AccountId, Balance, BlockNumber
         }
     }
 }
+
+/// The target chain transaction which settled a finished withdrawal.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalSettlement {
+    /// The txid of the settling transaction, in the byte order shown by the block explorers of
+    /// the target chain (e.g. reversed for Bitcoin).
+    pub txid: H256,
+    /// The hash of the block including the settling transaction, in the same byte order as
+    /// `txid`, `None` if the target chain has no blocks (e.g. the local chain).
+    pub block_hash: Option<H256>,
+}
+
+/// The lifecycle of a withdrawal record, which is kept after the record is finished or canceled.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalHistory<AccountId, Balance, BlockNumber> {
+    pub record: WithdrawalRecord<AccountId, Balance, BlockNumber>,
+    /// The state transitions and the block numbers, the last one is the current state.
+    pub transitions: Vec<(WithdrawalState, BlockNumber)>,
+    /// The target chain transaction which settled the withdrawal.
+    pub settlement: Option<WithdrawalSettlement>,
+}
+
+impl<AccountId, Balance, BlockNumber> WithdrawalHistory<AccountId, Balance, BlockNumber> {
+    /// Returns the current state of the withdrawal.
+    pub fn state(&self) -> WithdrawalState {
+        self.transitions
+            .last()
+            .map(|(state, _)| *state)
+            .unwrap_or_default()
+    }
+}
+
+/// The record of a deposit credited from the source chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct DepositRecord<AccountId, Balance, BlockNumber> {
+    pub asset_id: AssetId,
+    pub depositor: AccountId,
+    pub balance: Balance,
+    /// The txid of the deposit transaction on the source chain, in the byte order shown by
+    /// its block explorers (e.g. reversed for Bitcoin), `None` if it's unknown, e.g. the deposit
+    /// by root or the deposits relayed without the tx hash (Ethereum receipts and Polkadot
+    /// transfers).
+    pub txid: Option<H256>,
+    /// The referral declared in the deposit transaction.
+    pub referral: Option<ReferralId>,
+    /// The block number when the deposit was credited.
+    pub height: BlockNumber,
+}
@@ -34,6 +34,8 @@ pub trait WeightInfo {
     fn root_withdraw() -> Weight;
     fn set_withdrawal_state() -> Weight;
     fn set_withdrawal_state_list(u: u32) -> Weight;
+    fn set_withdrawal_delay() -> Weight;
+    fn freeze_withdrawal() -> Weight;
 }
 
 /// Weights for xpallet_gateway_records using the Substrate node and recommended hardware.
@@ -59,6 +61,14 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn set_withdrawal_delay() -> Weight {
+        (6_853_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn freeze_withdrawal() -> Weight {
+        (48_212_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -83,4 +93,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn set_withdrawal_delay() -> Weight {
+        (6_853_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn freeze_withdrawal() -> Weight {
+        (48_212_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
@@ -268,6 +268,7 @@ impl xpallet_mining_staking::Trait for Test {
     type SessionInterface = Self;
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
+    type OnNewEra = ();
     type WeightInfo = ();
 }
 
@@ -184,6 +184,46 @@ benchmarks! {
     verify {
         assert_eq!(SessionsPerEra::get(), c);
     }
+
+    set_bond_slashing {
+        let mode = BondSlashing::ValidatorAndNominators;
+    }: _(RawOrigin::Root, mode)
+    verify {
+        assert_eq!(BondSlashingMode::get(), mode);
+    }
+
+    set_slash_defer_duration {
+        let c = 1000u32;
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(SlashDeferDuration::get(), c);
+    }
+
+    cancel_deferred_slash {
+        let s in 1 .. 1000;
+        let session_index = 1000u32;
+        let unapplied = (0..s)
+            .map(|i| UnappliedSlash {
+                validator: account("validator", i, SEED),
+                own: 1000u32.into(),
+                others: vec![(account("nominator", i, SEED), 1000u32.into())],
+                slash_fraction: Perbill::from_percent(10),
+                last_valued: None,
+            })
+            .collect::<Vec<_>>();
+        UnappliedSlashes::<T>::insert(session_index, unapplied);
+        let slash_indices = (0..s).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, session_index, slash_indices)
+    verify {
+        assert!(!UnappliedSlashes::<T>::contains_key(session_index));
+    }
+
+    set_equivocation_severity {
+        let severity = Perbill::from_percent(50);
+    }: _(RawOrigin::Root, EquivocationKind::Grandpa, severity)
+    verify {
+        assert_eq!(EquivocationSeverity::get(EquivocationKind::Grandpa), Some(severity));
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +247,10 @@ mod tests {
             assert_ok!(test_benchmark_set_minimum_validator_count::<Test>());
             assert_ok!(test_benchmark_set_bonding_duration::<Test>());
             assert_ok!(test_benchmark_set_validator_bonding_duration::<Test>());
+            assert_ok!(test_benchmark_set_bond_slashing::<Test>());
+            assert_ok!(test_benchmark_set_slash_defer_duration::<Test>());
+            assert_ok!(test_benchmark_cancel_deferred_slash::<Test>());
+            assert_ok!(test_benchmark_set_equivocation_severity::<Test>());
         });
     }
 }
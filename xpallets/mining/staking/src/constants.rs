@@ -39,3 +39,31 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
+
+/// The maximum number of stakes slashed when applying the deferred slashes in a new session,
+/// the rest is carried over to the next session.
+///
+/// It also bounds the number of nominators valued for the deferred slashes in a session.
+pub const MAX_BOND_SLASHES_PER_SESSION: usize = 256;
+
+/// The maximum number of nominations indexed into `NominatorsOf` in one block when migrating
+/// the existing `Nominations`.
+pub const MAX_NOMINATIONS_INDEXED_PER_BLOCK: u32 = 1024;
+
+/// The default number of sessions a bond slash is deferred by, i.e., 1 day.
+///
+/// Governance can cancel the slash within this window.
+pub const DEFAULT_SLASH_DEFER_DURATION: u32 = 12 * 24;
+
+/// `Offence::ID` of the BABE equivocation offence.
+pub const BABE_EQUIVOCATION_ID: [u8; 16] = *b"babe:equivocatio";
+
+/// `Offence::ID` of the GRANDPA equivocation offence.
+pub const GRANDPA_EQUIVOCATION_ID: [u8; 16] = *b"grandpa:equivoca";
+
+/// The default severity of a BABE equivocation, i.e., producing two blocks in the same slot.
+pub const DEFAULT_BABE_EQUIVOCATION_SEVERITY: u32 = 10;
+
+/// The default severity of a GRANDPA equivocation, i.e., casting two votes in the same round,
+/// which is harsher than BABE's as it threatens the finality.
+pub const DEFAULT_GRANDPA_EQUIVOCATION_SEVERITY: u32 = 20;
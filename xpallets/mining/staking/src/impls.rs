@@ -7,7 +7,7 @@ use frame_support::weights::Weight;
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_core::crypto::UncheckedFrom;
 use sp_runtime::{traits::Hash, Perbill};
-use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+use sp_staking::offence::{Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence};
 
 use xp_mining_common::{
     generic_weight_factors, BaseMiningWeight, Claim, ComputeMiningWeight, WeightFactors, WeightType,
@@ -155,11 +155,18 @@ impl<T: Trait> Module<T> {
         current_block: T::BlockNumber,
         delta: Delta<BalanceOf<T>>,
     ) {
-        Nominations::<T>::mutate(nominator, validator, |claimer| {
+        if let Delta::Add(_) = delta {
+            NominatorsOf::<T>::insert(validator, nominator, true);
+        }
+        let is_empty = Nominations::<T>::mutate(nominator, validator, |claimer| {
             claimer.nomination = delta.calculate(claimer.nomination);
             claimer.last_vote_weight = new_weight;
             claimer.last_vote_weight_update = current_block;
+            claimer.is_empty()
         });
+        if is_empty {
+            NominatorsOf::<T>::remove(validator, nominator);
+        }
     }
 
     ///
@@ -226,9 +233,13 @@ impl<T: Trait> Module<T> {
         // Only the active validators can be rewarded.
         let validator_rewards = Self::distribute_session_reward(session_index);
 
+        // Value the rest nominators of the deferred slashes before the new ones are queued.
+        Self::value_deferred_slashes();
+
         // Reset the session offenders.
         if let Some(offenders) = SessionOffenders::<T>::take() {
-            let force_chilled = Self::slash_offenders_in_session(offenders, validator_rewards);
+            let force_chilled =
+                Self::slash_offenders_in_session(session_index, offenders, validator_rewards);
             if !force_chilled.is_empty() {
                 debug!("Force chilled:{:?}", force_chilled);
                 Self::deposit_event(Event::<T>::ForceChilled(session_index, force_chilled));
@@ -236,6 +247,8 @@ impl<T: Trait> Module<T> {
                 Self::ensure_new_era();
             }
         }
+
+        Self::apply_unapplied_slashes(session_index);
     }
 }
 
@@ -332,7 +345,7 @@ impl<T: Trait> Module<T> {
     /// * reset `active_era.start`,
     /// * update `BondedEras` and apply slashes.
     fn start_era(_start_session: SessionIndex) {
-        let _active_era = ActiveEra::mutate(|active_era| {
+        let active_era = ActiveEra::mutate(|active_era| {
             let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
             *active_era = Some(ActiveEraInfo {
                 index: new_index,
@@ -341,6 +354,7 @@ impl<T: Trait> Module<T> {
             });
             new_index
         });
+        T::OnNewEra::on_new_era(active_era);
     }
 
     /// Compute payout for era.
@@ -383,7 +397,7 @@ pub type IdentificationTuple<T> = (
 type Offender<T> = IdentificationTuple<T>;
 
 /// This is intended to be used with `FilterHistoricalOffences` in Substrate/Staking.
-/// In ChainX, we always apply the slash immediately, no deferred slash.
+/// In ChainX, the reward pot is slashed immediately, the slash of bonded stake is deferred.
 impl<T: Trait> OnOffenceHandler<Reporter<T>, IdentificationTuple<T>, Weight> for Module<T>
 where
     T: pallet_session::Trait<ValidatorId = <T as frame_system::Trait>::AccountId>,
@@ -405,9 +419,9 @@ where
             .map(|(details, slash_fraction)| {
                 // Reporters are ignored for now.
                 let (offender, _reporters) = &details.offender;
-                (offender, slash_fraction)
+                (offender.clone(), *slash_fraction)
             })
-            .collect::<BTreeMap<_, _>>();
+            .collect::<Vec<_>>();
 
         debug!(
             "Reported the offenders:{:?} happened in session {:?}",
@@ -416,7 +430,16 @@ where
 
         // Write a temp environment storage so that we can sum the session reward
         // together later and then perform the slashing operation only once.
-        <SessionOffenders<T>>::put(offenders_tuple);
+        //
+        // The offenders may have been reported by others (e.g. the trustees) or for other
+        // offences (e.g. the equivocations) in this session, the harshest slash fraction is kept.
+        <SessionOffenders<T>>::mutate(|session_offenders| {
+            let session_offenders = session_offenders.get_or_insert_with(BTreeMap::new);
+            for (offender, slash_fraction) in offenders_tuple {
+                let noted = session_offenders.entry(offender).or_insert(slash_fraction);
+                *noted = (*noted).max(slash_fraction);
+            }
+        });
 
         Ok(1)
     }
@@ -426,6 +449,84 @@ where
     }
 }
 
+/// Reporter of the offences that takes special care of the equivocations.
+///
+/// The offences are reported to `R`, i.e., `pallet_offences`, as usual, which in turn
+/// notes the offenders to `SessionOffenders` via `OnOffenceHandler`. Besides, the
+/// equivocators are slashed by the severity of their equivocation kind at least and
+/// disabled at once.
+pub struct EquivocationReporter<T, R>(sp_std::marker::PhantomData<(T, R)>);
+
+impl<T, R, O> ReportOffence<Reporter<T>, Offender<T>, O> for EquivocationReporter<T, R>
+where
+    T: Trait,
+    R: ReportOffence<Reporter<T>, Offender<T>, O>,
+    O: Offence<Offender<T>>,
+{
+    fn report_offence(reporters: Vec<Reporter<T>>, offence: O) -> Result<(), OffenceError> {
+        let equivocation = EquivocationKind::from_offence_id(&O::ID)
+            .map(|kind| (kind, offence.offenders(), offence.session_index()));
+
+        R::report_offence(reporters, offence)?;
+
+        if let Some((kind, offenders, session_index)) = equivocation {
+            for (offender, _) in offenders {
+                Module::<T>::note_equivocation(&offender, kind, session_index);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_known_offence(offenders: &[Offender<T>], time_slot: &O::TimeSlot) -> bool {
+        R::is_known_offence(offenders, time_slot)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Returns the slash fraction of the given kind of equivocation.
+    pub fn equivocation_severity_of(kind: EquivocationKind) -> Perbill {
+        Self::equivocation_severity(kind).unwrap_or_else(|| kind.default_severity())
+    }
+
+    /// Raises the slash fraction of the equivocator to the severity of the equivocation
+    /// and disables it immediately.
+    fn note_equivocation(
+        offender: &T::AccountId,
+        kind: EquivocationKind,
+        session_index: SessionIndex,
+    ) {
+        if !Self::is_validator(offender) {
+            return;
+        }
+
+        let severity = Self::equivocation_severity_of(kind);
+        SessionOffenders::<T>::mutate(|session_offenders| {
+            let slash_fraction = session_offenders
+                .get_or_insert_with(BTreeMap::new)
+                .entry(offender.clone())
+                .or_insert(severity);
+            *slash_fraction = (*slash_fraction).max(severity);
+        });
+
+        match T::SessionInterface::disable_validator(offender) {
+            Ok(true) => Self::ensure_new_era(),
+            Ok(false) => {}
+            Err(_) => debug!("Equivocator {:?} is not in the current session", offender),
+        }
+
+        debug!(
+            "Equivocator {:?} of {:?} in session {}, severity:{:?}",
+            offender, kind, session_index, severity
+        );
+        Self::deposit_event(Event::<T>::Equivocated(
+            offender.clone(),
+            kind,
+            session_index,
+        ));
+    }
+}
+
 /// Simple validator reward pot account determiner.
 ///
 /// Formula: `blake2_256(blake2_256(validator_pubkey) + blake2_256(registered_at))`
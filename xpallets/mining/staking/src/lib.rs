@@ -34,6 +34,7 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     storage::IterableStorageMap,
     traits::{Currency, ExistenceRequirement, Get, LockableCurrency, WithdrawReasons},
+    weights::Weight,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::{
@@ -47,10 +48,12 @@ use xp_logging::debug;
 pub use xp_mining_common::RewardPotAccountFor;
 use xp_mining_common::{Claim, ComputeMiningWeight, Delta, ZeroMiningWeightError};
 use xp_mining_staking::{AssetMining, SessionIndex, UnbondedIndex};
-use xpallet_support::traits::TreasuryAccount;
+use xpallet_support::traits::{OnNewEra, ReportOffenders, TreasuryAccount};
 
 use self::constants::*;
-pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
+pub use self::impls::{
+    EquivocationReporter, IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer,
+};
 pub use self::rpc::*;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
@@ -99,6 +102,9 @@ pub trait Trait: frame_system::Trait {
     /// there is a chance the authority will produce a block and they won't be necessary.
     type SessionDuration: Get<Self::BlockNumber>;
 
+    /// The handler called when a new era starts.
+    type OnNewEra: OnNewEra;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -163,6 +169,19 @@ decl_storage! {
             double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
             => NominatorLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>;
 
+        /// The map from validator to the accounts which have bonded or unbonding balances to
+        /// it, i.e., the index of the non-empty `Nominations` by nominee.
+        pub NominatorsOf get(fn nominators_of):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+            => bool;
+
+        /// True if `NominatorsOf` has been built from the existing `Nominations`.
+        pub NominatorsIndexed get(fn nominators_indexed): bool;
+
+        /// The raw storage key of `Nominations` from which `NominatorsOf` would be continued to
+        /// be built, exists only while the index is being built.
+        pub NominatorsIndexCursor get(fn nominators_index_cursor): Option<Vec<u8>>;
+
         /// The map from nominator to the block number of last `rebond` operation.
         pub LastRebondOf get(fn last_rebond_of):
             map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
@@ -200,6 +219,26 @@ decl_storage! {
         /// Minimum penalty for each slash.
         pub MinimumPenalty get(fn minimum_penalty) config(): BalanceOf<T>;
 
+        /// Whose bonded stake is slashed in proportion to the slash fraction of an offence.
+        pub BondSlashingMode get(fn bond_slashing): BondSlashing;
+
+        /// Number of sessions that a bond slash is deferred by.
+        ///
+        /// The deferred slashes can be cancelled by governance during this window.
+        pub SlashDeferDuration get(fn slash_defer_duration): SessionIndex = DEFAULT_SLASH_DEFER_DURATION;
+
+        /// All the bond slashes that are yet to be applied, keyed by the session to apply them.
+        pub UnappliedSlashes get(fn unapplied_slashes):
+            map hasher(twox_64_concat) SessionIndex => Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>;
+
+        /// The sessions whose unapplied slashes still have nominators to be valued.
+        pub PendingSlashValuations get(fn pending_slash_valuations): Vec<SessionIndex>;
+
+        /// The slash fraction of each kind of equivocation, `EquivocationKind::default_severity()`
+        /// is used if not set.
+        pub EquivocationSeverity get(fn equivocation_severity):
+            map hasher(twox_64_concat) EquivocationKind => Option<Perbill>;
+
         /// Immortal validators will always be elected if any.
         ///
         /// Immortals will be intialized from the genesis validators.
@@ -245,6 +284,8 @@ decl_storage! {
                 Module::<T>::apply_bond(validator, validator, *balance)
                     .expect("Bonding to validator itself can not fail; qed");
             }
+            // `NominatorsOf` is maintained since genesis.
+            NominatorsIndexed::put(true);
         });
     }
 }
@@ -273,6 +314,14 @@ decl_event!(
         ForceChilled(SessionIndex, Vec<AccountId>),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(AccountId),
+        /// A slash of the bonded stake behind the validator was deferred. [validator, apply_session_index]
+        SlashDeferred(AccountId, SessionIndex),
+        /// The bonded stake of a nominator was slashed. [nominator, validator, slashed_amount]
+        BondSlashed(AccountId, AccountId, Balance),
+        /// Some deferred slashes were cancelled. [apply_session_index, slash_indices]
+        DeferredSlashCancelled(SessionIndex, Vec<u32>),
+        /// A validator equivocated and was disabled immediately. [validator, equivocation_kind, session_index]
+        Equivocated(AccountId, EquivocationKind, SessionIndex),
     }
 );
 
@@ -319,6 +368,10 @@ decl_error! {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// No deferred slash is given to cancel.
+        EmptySlashIndices,
+        /// The slash indices are not sorted and unique, or out of range.
+        InvalidSlashIndex,
     }
 }
 
@@ -340,6 +393,14 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            Self::start_nominators_index_migration()
+        }
+
+        fn on_initialize() -> Weight {
+            Self::migrate_nominators_index(MAX_NOMINATIONS_INDEXED_PER_BLOCK)
+        }
+
         /// Nominate the `target` with `value` of the origin account's balance locked.
         #[weight = T::WeightInfo::bond()]
         pub fn bond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
@@ -418,9 +479,13 @@ decl_module! {
             Self::apply_unlock_unbonded_withdrawal(&sender, value);
 
             unbonded_chunks.swap_remove(unbonded_index as usize);
-            Nominations::<T>::mutate(&sender, &target, |nominator| {
+            let is_empty = Nominations::<T>::mutate(&sender, &target, |nominator| {
                 nominator.unbonded_chunks = unbonded_chunks;
+                nominator.is_empty()
             });
+            if is_empty {
+                NominatorsOf::<T>::remove(&target, &sender);
+            }
 
             Self::deposit_event(Event::<T>::Withdrawn(sender, value));
         }
@@ -522,6 +587,52 @@ decl_module! {
             SessionsPerEra::put(new);
         }
 
+        #[weight = T::WeightInfo::set_bond_slashing()]
+        fn set_bond_slashing(origin, new: BondSlashing) {
+            ensure_root(origin)?;
+            BondSlashingMode::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_slash_defer_duration()]
+        fn set_slash_defer_duration(origin, #[compact] new: SessionIndex) {
+            ensure_root(origin)?;
+            SlashDeferDuration::put(new);
+        }
+
+        /// Cancel the deferred slashes to be applied at session `session_index`.
+        ///
+        /// `slash_indices` must be sorted and unique.
+        #[weight = T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32)]
+        fn cancel_deferred_slash(origin, #[compact] session_index: SessionIndex, slash_indices: Vec<u32>) {
+            ensure_root(origin)?;
+            ensure!(!slash_indices.is_empty(), Error::<T>::EmptySlashIndices);
+            ensure!(
+                slash_indices.windows(2).all(|pair| pair[0] < pair[1]),
+                Error::<T>::InvalidSlashIndex
+            );
+
+            let mut unapplied = Self::unapplied_slashes(session_index);
+            let last_index = slash_indices[slash_indices.len() - 1] as usize;
+            ensure!(last_index < unapplied.len(), Error::<T>::InvalidSlashIndex);
+
+            for index in slash_indices.iter().rev() {
+                unapplied.remove(*index as usize);
+            }
+            if unapplied.is_empty() {
+                UnappliedSlashes::<T>::remove(session_index);
+            } else {
+                UnappliedSlashes::<T>::insert(session_index, unapplied);
+            }
+
+            Self::deposit_event(Event::<T>::DeferredSlashCancelled(session_index, slash_indices));
+        }
+
+        #[weight = T::WeightInfo::set_equivocation_severity()]
+        fn set_equivocation_severity(origin, kind: EquivocationKind, severity: Perbill) {
+            ensure_root(origin)?;
+            EquivocationSeverity::insert(kind, severity);
+        }
+
         #[weight = 10_000_000]
         fn set_immortals(origin, new: Vec<T::AccountId>) {
             ensure_root(origin)?;
@@ -611,6 +722,37 @@ impl<T: Trait> xpallet_support::traits::Validator<T::AccountId> for Module<T> {
     fn validator_for(name: &[u8]) -> Option<T::AccountId> {
         Self::validator_for(name)
     }
+
+    fn sorted_validators() -> Vec<T::AccountId> {
+        let mut validators = Self::active_validator_set()
+            .map(|v| (Self::total_votes_of(&v), v))
+            .collect::<Vec<_>>();
+        validators.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(&b1));
+        validators.into_iter().map(|(_, v)| v).collect()
+    }
+}
+
+impl<T: Trait> ReportOffenders<T::AccountId> for Module<T> {
+    /// The reported validators would be slashed with the minimum penalty at the end of
+    /// current session, the ones already reported in this session are not affected.
+    fn report_offenders(offenders: &[T::AccountId]) {
+        let offenders = offenders
+            .iter()
+            .filter(|offender| Self::is_validator(offender))
+            .collect::<Vec<_>>();
+        if offenders.is_empty() {
+            return;
+        }
+        debug!("Reported the offenders:{:?}", offenders);
+        SessionOffenders::<T>::mutate(|session_offenders| {
+            let session_offenders = session_offenders.get_or_insert_with(BTreeMap::new);
+            for offender in offenders {
+                session_offenders
+                    .entry(offender.clone())
+                    .or_insert_with(Perbill::zero);
+            }
+        });
+    }
 }
 
 impl<T: Trait> Module<T> {
@@ -637,6 +779,7 @@ impl<T: Trait> Module<T> {
                 Nominations::<T>::mutate(who, who, |nominator| {
                     nominator.nomination = *self_bonded;
                 });
+                NominatorsOf::<T>::insert(who, who, true);
             }
             Self::apply_register(who, referral_id.to_vec());
             // These validators will be chilled on the network startup.
@@ -661,6 +804,7 @@ impl<T: Trait> Module<T> {
             Nominations::<T>::mutate(sender, target, |nominator| {
                 nominator.nomination = value;
             });
+            NominatorsOf::<T>::insert(target, sender, true);
         }
         Ok(())
     }
@@ -802,9 +946,13 @@ impl<T: Trait> Module<T> {
 
     fn purge_unlockings(who: &T::AccountId) {
         for (target, _) in Nominations::<T>::iter_prefix(who) {
-            Nominations::<T>::mutate(&who, &target, |nominator| {
+            let is_empty = Nominations::<T>::mutate(&who, &target, |nominator| {
                 nominator.unbonded_chunks.clear();
+                nominator.is_empty()
             });
+            if is_empty {
+                NominatorsOf::<T>::remove(&target, &who);
+            }
         }
     }
 
@@ -9,7 +9,10 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
     Perbill,
 };
-use std::{cell::RefCell, collections::HashSet};
+use sp_staking::offence::{
+    Kind, Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence,
+};
+use std::{cell::RefCell, collections::HashSet, marker::PhantomData};
 use xp_mining_staking::SessionIndex;
 
 pub const INIT_TIMESTAMP: u64 = 30_000;
@@ -217,6 +220,7 @@ impl Trait for Test {
     type SessionInterface = Self;
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
+    type OnNewEra = ();
     type WeightInfo = ();
 }
 
@@ -229,6 +233,110 @@ thread_local! {
     static MAX_ITERATIONS: RefCell<u32> = RefCell::new(0);
 }
 
+pub fn is_disabled(validator: AccountId) -> bool {
+    SESSION.with(|d| d.borrow().1.contains(&validator))
+}
+
+pub trait OffenceId {
+    const ID: Kind;
+}
+
+pub struct BabeEquivocationId;
+impl OffenceId for BabeEquivocationId {
+    const ID: Kind = crate::constants::BABE_EQUIVOCATION_ID;
+}
+
+pub struct GrandpaEquivocationId;
+impl OffenceId for GrandpaEquivocationId {
+    const ID: Kind = crate::constants::GRANDPA_EQUIVOCATION_ID;
+}
+
+pub struct UnresponsivenessId;
+impl OffenceId for UnresponsivenessId {
+    const ID: Kind = *b"im-online:offlin";
+}
+
+/// An offence of kind `I` with a fixed slash fraction of 5%.
+pub struct MockOffence<I> {
+    pub session_index: SessionIndex,
+    pub offenders: Vec<IdentificationTuple<Test>>,
+    _marker: PhantomData<I>,
+}
+
+impl<I> MockOffence<I> {
+    pub fn new(session_index: SessionIndex, offenders: Vec<AccountId>) -> Self {
+        Self {
+            session_index,
+            offenders: offenders.into_iter().map(|o| (o, o)).collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: OffenceId> Offence<IdentificationTuple<Test>> for MockOffence<I> {
+    const ID: Kind = I::ID;
+    type TimeSlot = SessionIndex;
+
+    fn offenders(&self) -> Vec<IdentificationTuple<Test>> {
+        self.offenders.clone()
+    }
+
+    fn session_index(&self) -> SessionIndex {
+        self.session_index
+    }
+
+    fn validator_set_count(&self) -> u32 {
+        Session::validators().len() as u32
+    }
+
+    fn time_slot(&self) -> Self::TimeSlot {
+        self.session_index
+    }
+
+    fn slash_fraction(_offenders_count: u32, _validator_set_count: u32) -> Perbill {
+        Perbill::from_percent(5)
+    }
+}
+
+/// Mimics `pallet_offences` which passes the offenders to Staking directly.
+pub struct MockOffences;
+
+impl<O: Offence<IdentificationTuple<Test>>> ReportOffence<AccountId, IdentificationTuple<Test>, O>
+    for MockOffences
+{
+    fn report_offence(reporters: Vec<AccountId>, offence: O) -> Result<(), OffenceError> {
+        let offenders = offence
+            .offenders()
+            .into_iter()
+            .map(|offender| OffenceDetails {
+                offender,
+                reporters: reporters.clone(),
+            })
+            .collect::<Vec<_>>();
+        let slash_fraction =
+            vec![
+                O::slash_fraction(offenders.len() as u32, offence.validator_set_count());
+                offenders.len()
+            ];
+        <XStaking as OnOffenceHandler<AccountId, IdentificationTuple<Test>, Weight>>::on_offence(
+            &offenders,
+            &slash_fraction,
+            offence.session_index(),
+        )
+        .map(|_| ())
+        .map_err(|_| OffenceError::Other(0))
+    }
+
+    fn is_known_offence(
+        _offenders: &[IdentificationTuple<Test>],
+        _time_slot: &O::TimeSlot,
+    ) -> bool {
+        false
+    }
+}
+
+pub type TestEquivocationReporter = EquivocationReporter<Test, MockOffences>;
+
 pub struct ExtBuilder {
     session_length: BlockNumber,
     election_lookahead: BlockNumber,
@@ -2,13 +2,24 @@
 
 use sp_std::ops::Mul;
 
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{StorageDoubleMap, StoragePrefixedMap},
+    StorageHasher, Twox64Concat,
+};
+
+use xp_logging::info;
+
 use super::*;
 
 impl<T: Trait> Module<T> {
     /// Returns the force chilled offenders if any after applying the slashings.
     ///
     /// The slashed balances will be moved to the treasury.
+    ///
+    /// The slashes of bonded stake are deferred, see `defer_bond_slash()`.
     pub(crate) fn slash_offenders_in_session(
+        session_index: SessionIndex,
         offenders: BTreeMap<T::AccountId, Perbill>,
         validator_rewards: Vec<(T::AccountId, BalanceOf<T>)>,
     ) -> Vec<T::AccountId> {
@@ -46,6 +57,7 @@ impl<T: Trait> Module<T> {
         offenders
             .into_iter()
             .flat_map(|(offender, slash_fraction)| {
+                Self::defer_bond_slash(session_index, &offender, slash_fraction);
                 let base_slash = calc_base_slash(&offender, slash_fraction);
                 let penalty = validator_rewards
                     .get(&offender)
@@ -81,4 +93,353 @@ impl<T: Trait> Module<T> {
             })
             .collect()
     }
+
+    /// Queues the slash of the bonded stake behind `offender` according to `BondSlashingMode`.
+    ///
+    /// The slash will be applied `SlashDeferDuration` sessions later unless it's cancelled.
+    /// A zero `slash_fraction` which only incurs the minimum penalty does not slash any stake.
+    ///
+    /// At most `MAX_BOND_SLASHES_PER_SESSION` nominators are valued right now, the rest are
+    /// valued in the following sessions, see `value_deferred_slashes()`.
+    fn defer_bond_slash(
+        session_index: SessionIndex,
+        offender: &T::AccountId,
+        slash_fraction: Perbill,
+    ) {
+        let bond_slashing = Self::bond_slashing();
+        if bond_slashing == BondSlashing::Disabled || slash_fraction.is_zero() {
+            return;
+        }
+
+        let own = slash_fraction.mul(Self::validator_self_bonded(offender));
+        let (others, last_valued) = if bond_slashing == BondSlashing::ValidatorAndNominators {
+            let (others, last_valued, _) = Self::value_nominators(
+                offender,
+                slash_fraction,
+                None,
+                MAX_BOND_SLASHES_PER_SESSION,
+            );
+            (others, last_valued)
+        } else {
+            (Vec::new(), None)
+        };
+
+        if own.is_zero() && others.is_empty() && last_valued.is_none() {
+            return;
+        }
+
+        let apply_at = session_index.saturating_add(Self::slash_defer_duration());
+        debug!(
+            "Defer the bond slash of {:?} to session {}, own:{:?}, others:{:?}, last_valued:{:?}",
+            offender, apply_at, own, others, last_valued
+        );
+        if last_valued.is_some() {
+            Self::note_pending_slash_valuation(apply_at);
+        }
+        UnappliedSlashes::<T>::append(
+            apply_at,
+            UnappliedSlash {
+                validator: offender.clone(),
+                own,
+                others,
+                slash_fraction,
+                last_valued,
+            },
+        );
+        Self::deposit_event(Event::<T>::SlashDeferred(offender.clone(), apply_at));
+    }
+
+    /// Values the stakes of at most `limit` nominators of `validator` after `last_valued`.
+    ///
+    /// Returns the non-zero slashes, the last valued nominator if there might be more nominators
+    /// to value, and the number of the visited `NominatorsOf` entries.
+    fn value_nominators(
+        validator: &T::AccountId,
+        slash_fraction: Perbill,
+        last_valued: Option<&T::AccountId>,
+        limit: usize,
+    ) -> (
+        Vec<(T::AccountId, BalanceOf<T>)>,
+        Option<T::AccountId>,
+        usize,
+    ) {
+        // `NominatorsOf` uses the twox_64_concat hasher, the nominator is the suffix of the key.
+        let mut prefix = NominatorsOf::<T>::final_prefix().to_vec();
+        prefix.extend(Twox64Concat::hash(&validator.encode()));
+        let mut cursor = match last_valued {
+            Some(nominator) => NominatorsOf::<T>::hashed_key_for(validator, nominator),
+            None => prefix.clone(),
+        };
+
+        let mut others = Vec::new();
+        let mut last = None;
+        let mut visited = 0;
+        while visited < limit {
+            let key = match sp_io::storage::next_key(&cursor) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => return (others, None, visited),
+            };
+            visited += 1;
+            if let Ok(nominator) = T::AccountId::decode(&mut &key[prefix.len() + 8..]) {
+                if nominator != *validator {
+                    let value = slash_fraction.mul(Self::bonded_to(&nominator, validator));
+                    if !value.is_zero() {
+                        others.push((nominator.clone(), value));
+                    }
+                }
+                last = Some(nominator);
+            }
+            cursor = key;
+        }
+        (others, last, visited)
+    }
+
+    fn note_pending_slash_valuation(apply_at: SessionIndex) {
+        PendingSlashValuations::mutate(|sessions| {
+            if !sessions.contains(&apply_at) {
+                sessions.push(apply_at);
+            }
+        });
+    }
+
+    /// Continues valuing the nominators of the unapplied slashes, at most
+    /// `MAX_BOND_SLASHES_PER_SESSION` nominators are valued in a session.
+    pub(crate) fn value_deferred_slashes() {
+        let sessions = PendingSlashValuations::take();
+        if sessions.is_empty() {
+            return;
+        }
+
+        let mut remaining = MAX_BOND_SLASHES_PER_SESSION;
+        let mut pending = Vec::new();
+        for session_index in sessions {
+            if remaining == 0 {
+                pending.push(session_index);
+                continue;
+            }
+            let mut unapplied = UnappliedSlashes::<T>::get(session_index);
+            let mut is_pending = false;
+            for slash in unapplied.iter_mut() {
+                let last_valued = match slash.last_valued.take() {
+                    Some(last_valued) => last_valued,
+                    None => continue,
+                };
+                if remaining == 0 {
+                    slash.last_valued = Some(last_valued);
+                    is_pending = true;
+                    continue;
+                }
+                let (others, last_valued, visited) = Self::value_nominators(
+                    &slash.validator,
+                    slash.slash_fraction,
+                    Some(&last_valued),
+                    remaining,
+                );
+                remaining -= visited;
+                slash.others.extend(others);
+                slash.last_valued = last_valued;
+                is_pending |= slash.last_valued.is_some();
+            }
+            // the slashes might have been cancelled or applied.
+            if !unapplied.is_empty() {
+                UnappliedSlashes::<T>::insert(session_index, unapplied);
+            }
+            if is_pending {
+                pending.push(session_index);
+            }
+        }
+
+        if !pending.is_empty() {
+            PendingSlashValuations::put(pending);
+        }
+    }
+
+    /// Applies the deferred bond slashes that are due at `session_index`.
+    ///
+    /// At most `MAX_BOND_SLASHES_PER_SESSION` stakes are slashed, the rest is carried over to
+    /// the next session and applied before the slashes due then, so are the slashes whose
+    /// nominators are not all valued yet.
+    pub(crate) fn apply_unapplied_slashes(session_index: SessionIndex) {
+        let unapplied = UnappliedSlashes::<T>::take(session_index);
+        if unapplied.is_empty() {
+            return;
+        }
+
+        let treasury_account = T::TreasuryAccount::treasury_account();
+        let mut remaining = MAX_BOND_SLASHES_PER_SESSION;
+        let mut carried = Vec::new();
+        for mut slash in unapplied {
+            if remaining == 0 {
+                carried.push(slash);
+                continue;
+            }
+            if !slash.own.is_zero() {
+                Self::slash_bonded(
+                    &slash.validator,
+                    &slash.validator,
+                    slash.own,
+                    &treasury_account,
+                );
+                slash.own = Zero::zero();
+                remaining -= 1;
+            }
+            let count = remaining.min(slash.others.len());
+            for (nominator, value) in slash.others.drain(..count) {
+                Self::slash_bonded(&nominator, &slash.validator, value, &treasury_account);
+            }
+            remaining -= count;
+            if !slash.others.is_empty() || slash.last_valued.is_some() {
+                carried.push(slash);
+            }
+        }
+
+        if !carried.is_empty() {
+            let next = session_index.saturating_add(1);
+            if carried.iter().any(|slash| slash.last_valued.is_some()) {
+                Self::note_pending_slash_valuation(next);
+            }
+            debug!(
+                "Carry {} deferred bond slashes over to session {}",
+                carried.len(),
+                next
+            );
+            UnappliedSlashes::<T>::mutate(next, |unapplied| {
+                carried.append(unapplied);
+                *unapplied = carried;
+            });
+        }
+    }
+
+    /// Starts building `NominatorsOf` from the existing `Nominations` if it's not built yet.
+    ///
+    /// The nominations are indexed in the following blocks, see `migrate_nominators_index()`.
+    pub(crate) fn start_nominators_index_migration() -> Weight {
+        if Self::nominators_indexed() || NominatorsIndexCursor::exists() {
+            return T::DbWeight::get().reads(2);
+        }
+        info!("Start to index the nominators of the existing nominations");
+        NominatorsIndexCursor::put(Nominations::<T>::final_prefix().to_vec());
+        T::DbWeight::get().reads_writes(2, 1)
+    }
+
+    /// Walks `Nominations` from `NominatorsIndexCursor` and indexes at most `limit` non-empty
+    /// nominations into `NominatorsOf`, the rest would be indexed in the following blocks.
+    ///
+    /// The nominators not indexed yet can not be valued for the bond slashes meanwhile.
+    pub(crate) fn migrate_nominators_index(limit: u32) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut cursor = match Self::nominators_index_cursor() {
+            Some(cursor) => cursor,
+            None => return db_weight.reads(1),
+        };
+        let prefix = Nominations::<T>::final_prefix();
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        let mut count = 0;
+        while count < limit {
+            let key = match sp_io::storage::next_key(&cursor) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => {
+                    info!("Indexed the nominators of all the existing nominations");
+                    NominatorsIndexCursor::kill();
+                    NominatorsIndexed::put(true);
+                    return db_weight.reads_writes(reads, writes + 1);
+                }
+            };
+            // Both the keys of `Nominations` use the twox_64_concat hasher.
+            let mut raw = &key[prefix.len() + 8..];
+            if let Ok(nominator) = T::AccountId::decode(&mut raw) {
+                if let Some(mut raw) = raw.get(8..) {
+                    if let Ok(nominee) = T::AccountId::decode(&mut raw) {
+                        if !Nominations::<T>::get(&nominator, &nominee).is_empty() {
+                            NominatorsOf::<T>::insert(&nominee, &nominator, true);
+                            writes += 1;
+                        }
+                        reads += 1;
+                    }
+                }
+            }
+            cursor = key;
+            count += 1;
+        }
+        NominatorsIndexCursor::put(cursor);
+
+        db_weight.reads_writes(reads, writes)
+    }
+
+    /// Slashes at most `value` of the stake `nominator` has bonded to `validator`.
+    ///
+    /// The bonded balance is slashed first, then the unbonded chunks that are still locked,
+    /// since the nominator may have unbonded during the defer window.
+    fn slash_bonded(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        value: BalanceOf<T>,
+        treasury_account: &T::AccountId,
+    ) {
+        let from_bonded = value.min(Self::bonded_to(nominator, validator));
+        if !from_bonded.is_zero() {
+            Self::update_vote_weight(nominator, validator, Delta::Sub(from_bonded));
+        }
+
+        let mut remaining = value - from_bonded;
+        let mut from_unbonded: BalanceOf<T> = Zero::zero();
+        if !remaining.is_zero() {
+            let is_empty = Nominations::<T>::mutate(nominator, validator, |ledger| {
+                for chunk in ledger.unbonded_chunks.iter_mut() {
+                    let slashed = remaining.min(chunk.value);
+                    chunk.value -= slashed;
+                    remaining -= slashed;
+                    from_unbonded += slashed;
+                    if remaining.is_zero() {
+                        break;
+                    }
+                }
+                ledger
+                    .unbonded_chunks
+                    .retain(|chunk| !chunk.value.is_zero());
+                ledger.is_empty()
+            });
+            if is_empty {
+                NominatorsOf::<T>::remove(validator, nominator);
+            }
+        }
+
+        let slashed = from_bonded + from_unbonded;
+        if slashed.is_zero() {
+            return;
+        }
+
+        Locks::<T>::mutate(nominator, |locks| {
+            let bonded = locks.entry(LockedType::Bonded).or_default();
+            *bonded = bonded.saturating_sub(from_bonded);
+            if !from_unbonded.is_zero() {
+                let old_value = *locks.entry(LockedType::BondedWithdrawal).or_default();
+                if old_value <= from_unbonded {
+                    locks.remove(&LockedType::BondedWithdrawal);
+                } else {
+                    locks.insert(LockedType::BondedWithdrawal, old_value - from_unbonded);
+                }
+            }
+
+            let staking_locked = locks
+                .values()
+                .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc + *x);
+            Self::set_lock(nominator, staking_locked);
+        });
+
+        let (imbalance, _) = T::Currency::slash(nominator, slashed);
+        T::Currency::resolve_creating(treasury_account, imbalance);
+
+        debug!(
+            "Slash the bonded stake of {:?} to {:?}, expected:{:?}, actual:{:?}",
+            nominator, validator, value, slashed
+        );
+        Self::deposit_event(Event::<T>::BondSlashed(
+            nominator.clone(),
+            validator.clone(),
+            slashed,
+        ));
+    }
 }
@@ -2,7 +2,11 @@
 
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_err, assert_ok, traits::OnInitialize};
+use frame_support::{
+    assert_err, assert_ok,
+    storage::IterableStorageDoubleMap,
+    traits::{OnInitialize, OnRuntimeUpgrade},
+};
 
 fn t_issue_pcx(to: AccountId, value: Balance) {
     XStaking::mint(&to, value);
@@ -886,3 +890,512 @@ fn migration_session_offset_should_work() {
         }
     });
 }
+
+#[test]
+fn sorted_validators_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let sorted = || <XStaking as xpallet_support::traits::Validator<_>>::sorted_validators();
+        assert_eq!(sorted(), vec![4, 3, 2, 1]);
+
+        // Sorted by the total nomination.
+        assert_ok!(t_bond(1, 2, 15));
+        assert_eq!(sorted(), vec![4, 2, 3, 1]);
+
+        // Chilled validators are excluded.
+        assert_ok!(XStaking::chill(Origin::signed(4)));
+        assert_eq!(sorted(), vec![2, 3, 1]);
+    });
+}
+
+#[test]
+fn report_offenders_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let report = |offenders: &[AccountId]| {
+            <XStaking as xpallet_support::traits::ReportOffenders<_>>::report_offenders(offenders)
+        };
+        // Non-validators are ignored.
+        report(&[7777]);
+        assert_eq!(XStaking::session_offenders(), None);
+
+        SessionOffenders::<Test>::put(
+            vec![(2, Perbill::from_percent(10))]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+        report(&[1, 2, 7777]);
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(
+                vec![(1, Perbill::zero()), (2, Perbill::from_percent(10))]
+                    .into_iter()
+                    .collect()
+            )
+        );
+    });
+}
+
+#[test]
+fn deferred_bond_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 2, 50));
+
+        let offend = |validator: AccountId| {
+            SessionOffenders::<Test>::put(
+                vec![(validator, Perbill::from_percent(10))]
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>(),
+            );
+        };
+
+        // No bonded stake is slashed by default.
+        offend(2);
+        t_start_session(1);
+        assert_eq!(UnappliedSlashes::<Test>::iter().count(), 0);
+
+        assert_ok!(XStaking::set_bond_slashing(
+            Origin::root(),
+            BondSlashing::ValidatorAndNominators
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 2));
+
+        offend(2);
+        t_start_session(2);
+        assert_eq!(
+            XStaking::unapplied_slashes(4),
+            vec![UnappliedSlash {
+                validator: 2,
+                own: 2,
+                others: vec![(t_1, 5)],
+                slash_fraction: Perbill::from_percent(10),
+                last_valued: None,
+            }]
+        );
+
+        // The nominator can not escape from the slash by unbonding within the defer window.
+        assert_ok!(t_unbond(t_1, 2, 48));
+
+        t_start_session(3);
+        assert_eq!(Nominations::<Test>::get(t_1, 2).nomination, 2);
+        assert_eq!(XStaking::free_balance(&t_1), 100);
+
+        t_start_session(4);
+        assert_eq!(UnappliedSlashes::<Test>::contains_key(4), false);
+
+        assert_eq!(Nominations::<Test>::get(2, 2).nomination, 18);
+        assert_bonded_locks(2, 18);
+
+        // 2 is slashed from the bonded and 3 from the unbonded chunk.
+        let t_1_ledger = Nominations::<Test>::get(t_1, 2);
+        assert_eq!(t_1_ledger.nomination, 0);
+        assert_eq!(
+            t_1_ledger
+                .unbonded_chunks
+                .iter()
+                .map(|c| c.value)
+                .sum::<Balance>(),
+            45
+        );
+        assert_bonded_locks(t_1, 0);
+        assert_bonded_withdrawal_locks(t_1, 45);
+        assert_eq!(XStaking::free_balance(&t_1), 95);
+
+        assert_eq!(<ValidatorLedgers<Test>>::get(2).total_nomination, 18);
+    });
+}
+
+#[test]
+fn validator_only_bond_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 3, 50));
+
+        assert_ok!(XStaking::set_bond_slashing(
+            Origin::root(),
+            BondSlashing::Validator
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 0));
+
+        SessionOffenders::<Test>::put(
+            vec![(3, Perbill::from_percent(10)), (4, Perbill::zero())]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+        // Applied in the same session as there is no defer window.
+        t_start_session(1);
+        assert_eq!(UnappliedSlashes::<Test>::iter().count(), 0);
+
+        assert_eq!(Nominations::<Test>::get(3, 3).nomination, 27);
+        assert_bonded_locks(3, 27);
+        assert_eq!(Nominations::<Test>::get(t_1, 3).nomination, 50);
+        assert_eq!(<ValidatorLedgers<Test>>::get(3).total_nomination, 77);
+
+        // A zero slash fraction only incurs the minimum penalty.
+        assert_eq!(Nominations::<Test>::get(4, 4).nomination, 40);
+    });
+}
+
+#[test]
+fn cancel_deferred_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_bond_slashing(
+            Origin::root(),
+            BondSlashing::ValidatorAndNominators
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 2));
+
+        SessionOffenders::<Test>::put(
+            vec![
+                (2, Perbill::from_percent(10)),
+                (3, Perbill::from_percent(10)),
+            ]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>(),
+        );
+        t_start_session(1);
+        assert_eq!(XStaking::unapplied_slashes(3).len(), 2);
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::signed(1), 3, vec![0]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 3, vec![]),
+            <Error<Test>>::EmptySlashIndices
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 3, vec![1, 0]),
+            <Error<Test>>::InvalidSlashIndex
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 3, vec![2]),
+            <Error<Test>>::InvalidSlashIndex
+        );
+
+        assert_ok!(XStaking::cancel_deferred_slash(Origin::root(), 3, vec![0]));
+        assert_eq!(
+            XStaking::unapplied_slashes(3)
+                .into_iter()
+                .map(|slash| slash.validator)
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(Origin::root(), 3, vec![0]));
+        assert_eq!(UnappliedSlashes::<Test>::contains_key(3), false);
+
+        t_start_session(3);
+        assert_eq!(Nominations::<Test>::get(2, 2).nomination, 20);
+        assert_eq!(Nominations::<Test>::get(3, 3).nomination, 30);
+    });
+}
+
+#[test]
+fn nominators_index_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 2, 50));
+        assert_ok!(t_rebond(t_1, 2, 3, 20));
+
+        let nominators = |validator: AccountId| {
+            let mut nominators = NominatorsOf::<Test>::iter_prefix(validator)
+                .map(|(nominator, _)| nominator)
+                .collect::<Vec<_>>();
+            nominators.sort();
+            nominators
+        };
+        assert_eq!(nominators(2), vec![2, t_1]);
+        assert_eq!(nominators(3), vec![3, t_1]);
+        assert!(XStaking::nominators_indexed());
+
+        // The nominator is kept in the index until the unbonded balance is withdrawn.
+        assert_ok!(t_unbond(t_1, 3, 20));
+        assert_eq!(nominators(3), vec![3, t_1]);
+        t_system_block_number_inc(DEFAULT_BONDING_DURATION + 1);
+        assert_ok!(t_withdraw_unbonded(t_1, 3, 0));
+        assert_eq!(nominators(3), vec![3]);
+
+        // The index is built from the existing nominations after runtime upgrade.
+        NominatorsOf::<Test>::remove_prefix(2);
+        NominatorsOf::<Test>::remove_prefix(3);
+        NominatorsIndexed::put(false);
+        XStaking::on_runtime_upgrade();
+        assert!(XStaking::nominators_index_cursor().is_some());
+
+        XStaking::migrate_nominators_index(1);
+        assert!(!XStaking::nominators_indexed());
+        assert!(XStaking::nominators_index_cursor().is_some());
+
+        XStaking::on_initialize(System::block_number());
+        assert_eq!(nominators(2), vec![2, t_1]);
+        assert_eq!(nominators(3), vec![3]);
+        assert!(XStaking::nominators_indexed());
+        assert!(XStaking::nominators_index_cursor().is_none());
+    });
+}
+
+#[test]
+fn bond_slash_nominators_should_be_valued_in_batches() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_bond_slashing(
+            Origin::root(),
+            BondSlashing::ValidatorAndNominators
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 2));
+        UpperBoundFactorOfAcceptableVotes::put(1000);
+
+        let nominators = (0..MAX_BOND_SLASHES_PER_SESSION + 10)
+            .map(|i| 20_000 + i as AccountId)
+            .collect::<Vec<_>>();
+        for nominator in nominators.iter() {
+            t_issue_pcx(*nominator, 10);
+            assert_ok!(t_bond(*nominator, 2, 2));
+        }
+
+        SessionOffenders::<Test>::put(
+            vec![(2, Perbill::from_percent(50))]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+        t_start_session(1);
+        let unapplied = XStaking::unapplied_slashes(3);
+        assert_eq!(unapplied.len(), 1);
+        assert!(unapplied[0].others.len() < nominators.len());
+        assert!(unapplied[0].last_valued.is_some());
+        assert_eq!(XStaking::pending_slash_valuations(), vec![3]);
+
+        // The rest nominators are valued in the next session.
+        t_start_session(2);
+        let unapplied = XStaking::unapplied_slashes(3);
+        let mut valued = unapplied[0]
+            .others
+            .iter()
+            .map(|(nominator, value)| {
+                assert_eq!(*value, 1);
+                *nominator
+            })
+            .collect::<Vec<_>>();
+        valued.sort();
+        assert_eq!(valued, nominators);
+        assert_eq!(unapplied[0].last_valued, None);
+        assert!(XStaking::pending_slash_valuations().is_empty());
+
+        t_start_session(4);
+        assert_eq!(UnappliedSlashes::<Test>::iter().count(), 0);
+        assert_eq!(Nominations::<Test>::get(2, 2).nomination, 10);
+        for nominator in nominators.iter() {
+            assert_eq!(Nominations::<Test>::get(nominator, 2).nomination, 1);
+        }
+    });
+}
+
+#[test]
+fn bond_slashes_should_be_carried_over() {
+    ExtBuilder::default().build_and_execute(|| {
+        let others = (0..MAX_BOND_SLASHES_PER_SESSION + 10)
+            .map(|i| (20_000 + i as AccountId, 1))
+            .collect::<Vec<_>>();
+        UnappliedSlashes::<Test>::insert(
+            1,
+            vec![
+                UnappliedSlash {
+                    validator: 2,
+                    own: 2,
+                    others: others.clone(),
+                    slash_fraction: Perbill::zero(),
+                    last_valued: None,
+                },
+                UnappliedSlash {
+                    validator: 3,
+                    own: 3,
+                    others: vec![],
+                    slash_fraction: Perbill::zero(),
+                    last_valued: None,
+                },
+            ],
+        );
+        UnappliedSlashes::<Test>::insert(
+            2,
+            vec![UnappliedSlash {
+                validator: 4,
+                own: 4,
+                others: vec![],
+                slash_fraction: Perbill::zero(),
+                last_valued: None,
+            }],
+        );
+
+        t_start_session(1);
+        assert_eq!(Nominations::<Test>::get(2, 2).nomination, 18);
+        assert_eq!(Nominations::<Test>::get(3, 3).nomination, 30);
+        // The rest is applied before the slashes due in the next session.
+        assert_eq!(
+            XStaking::unapplied_slashes(2),
+            vec![
+                UnappliedSlash {
+                    validator: 2,
+                    own: 0,
+                    others: others[MAX_BOND_SLASHES_PER_SESSION - 1..].to_vec(),
+                    slash_fraction: Perbill::zero(),
+                    last_valued: None,
+                },
+                UnappliedSlash {
+                    validator: 3,
+                    own: 3,
+                    others: vec![],
+                    slash_fraction: Perbill::zero(),
+                    last_valued: None,
+                },
+                UnappliedSlash {
+                    validator: 4,
+                    own: 4,
+                    others: vec![],
+                    slash_fraction: Perbill::zero(),
+                    last_valued: None,
+                },
+            ]
+        );
+
+        t_start_session(2);
+        assert_eq!(UnappliedSlashes::<Test>::contains_key(2), false);
+        assert_eq!(Nominations::<Test>::get(3, 3).nomination, 27);
+        assert_eq!(Nominations::<Test>::get(4, 4).nomination, 36);
+    });
+}
+
+fn t_equivocated(validator: AccountId, kind: EquivocationKind) -> bool {
+    let expected: MetaEvent = Event::<Test>::Equivocated(validator, kind, 0).into();
+    System::events()
+        .iter()
+        .any(|record| record.event == expected)
+}
+
+#[test]
+fn babe_equivocation_should_work() {
+    use sp_staking::offence::ReportOffence;
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<BabeEquivocationId>::new(0, vec![2])
+        ));
+
+        // Slashed by the default severity of BABE equivocation instead of 5%.
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(vec![(2, Perbill::from_percent(10))].into_iter().collect())
+        );
+        assert!(is_disabled(2));
+        assert!(!is_disabled(1));
+        assert!(t_equivocated(2, EquivocationKind::Babe));
+
+        assert_err!(
+            XStaking::set_equivocation_severity(
+                Origin::signed(1),
+                EquivocationKind::Babe,
+                Perbill::from_percent(30)
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XStaking::set_equivocation_severity(
+            Origin::root(),
+            EquivocationKind::Babe,
+            Perbill::from_percent(30)
+        ));
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<BabeEquivocationId>::new(0, vec![2])
+        ));
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(vec![(2, Perbill::from_percent(30))].into_iter().collect())
+        );
+    });
+}
+
+#[test]
+fn grandpa_equivocation_should_work() {
+    use sp_staking::offence::ReportOffence;
+    ExtBuilder::default().build_and_execute(|| {
+        SessionOffenders::<Test>::put(
+            vec![(3, Perbill::from_percent(50))]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<GrandpaEquivocationId>::new(0, vec![3, 4])
+        ));
+
+        // A harsher slash fraction that has been noted is kept.
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(
+                vec![
+                    (3, Perbill::from_percent(50)),
+                    (4, Perbill::from_percent(20))
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        assert!(is_disabled(3));
+        assert!(is_disabled(4));
+        assert!(t_equivocated(3, EquivocationKind::Grandpa));
+        assert!(t_equivocated(4, EquivocationKind::Grandpa));
+
+        // Too many validators are disabled.
+        assert_eq!(XStaking::force_era(), Forcing::ForceNew);
+    });
+}
+
+#[test]
+fn unresponsiveness_offence_should_not_disable() {
+    use sp_staking::offence::ReportOffence;
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<UnresponsivenessId>::new(0, vec![1])
+        ));
+
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(vec![(1, Perbill::from_percent(5))].into_iter().collect())
+        );
+        assert!(!is_disabled(1));
+        assert!(!t_equivocated(1, EquivocationKind::Babe));
+        assert!(!t_equivocated(1, EquivocationKind::Grandpa));
+    });
+}
+
+#[test]
+fn unresponsiveness_offence_should_not_lower_equivocation_slash() {
+    use sp_staking::offence::ReportOffence;
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<BabeEquivocationId>::new(0, vec![2])
+        ));
+        assert_ok!(TestEquivocationReporter::report_offence(
+            vec![],
+            MockOffence::<UnresponsivenessId>::new(0, vec![2, 3])
+        ));
+
+        // The slash fraction of the equivocation is kept.
+        assert_eq!(
+            XStaking::session_offenders(),
+            Some(
+                vec![
+                    (2, Perbill::from_percent(10)),
+                    (3, Perbill::from_percent(5))
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        assert!(is_disabled(2));
+        assert!(!is_disabled(3));
+    });
+}
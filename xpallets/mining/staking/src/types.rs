@@ -7,8 +7,8 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
-    traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    traits::{SaturatedConversion, Saturating, Zero},
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
@@ -16,6 +16,10 @@ use xp_logging::debug;
 use xp_mining_common::{RewardPotAccountFor, WeightType};
 use xp_mining_staking::MiningPower;
 
+use crate::constants::{
+    BABE_EQUIVOCATION_ID, DEFAULT_BABE_EQUIVOCATION_SEVERITY,
+    DEFAULT_GRANDPA_EQUIVOCATION_SEVERITY, GRANDPA_EQUIVOCATION_ID,
+};
 use crate::{AssetMining, BalanceOf, EraIndex, Event, Module, Trait};
 
 pub type VoteWeight = WeightType;
@@ -90,6 +94,13 @@ pub struct NominatorLedger<Balance, VoteWeight, BlockNumber> {
     pub unbonded_chunks: Vec<Unbonded<Balance, BlockNumber>>,
 }
 
+impl<Balance: Zero, VoteWeight, BlockNumber> NominatorLedger<Balance, VoteWeight, BlockNumber> {
+    /// Returns true if neither bonded nor unbonding balance is left, i.e., nothing to slash.
+    pub fn is_empty(&self) -> bool {
+        self.nomination.is_zero() && self.unbonded_chunks.is_empty()
+    }
+}
+
 /// Profile of staking validator.
 ///
 /// These fields are static or updated less frequently.
@@ -234,6 +245,75 @@ pub enum SlashOutcome<Balance> {
     SlashFailed(DispatchError),
 }
 
+/// Whose bonded stake is slashed in proportion to the slash fraction of an offence.
+///
+/// The reward pot of the offender is always slashed first.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum BondSlashing {
+    /// Only the reward pot of the offender is slashed.
+    Disabled,
+    /// The self-bonded balance of the offender is slashed too.
+    Validator,
+    /// Both the self-bonded balance of the offender and the nominations to it are slashed.
+    ValidatorAndNominators,
+}
+
+impl Default for BondSlashing {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+/// A slash of the bonded stake that has been computed but not applied yet.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct UnappliedSlash<AccountId, Balance> {
+    /// The offending validator.
+    pub validator: AccountId,
+    /// The amount of the self-bonded balance of the validator to be slashed.
+    pub own: Balance,
+    /// The nominators and the amount of their nominations to be slashed.
+    pub others: Vec<(AccountId, Balance)>,
+    /// The slash fraction of the offence.
+    pub slash_fraction: Perbill,
+    /// The last nominator valued into `others` if the rest nominators of `validator` are still
+    /// to be valued in the following sessions.
+    pub last_valued: Option<AccountId>,
+}
+
+/// Kinds of the equivocation offence reported via `pallet_offences`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EquivocationKind {
+    /// Authored two blocks in the same BABE slot.
+    Babe,
+    /// Cast two votes in the same GRANDPA round.
+    Grandpa,
+}
+
+impl EquivocationKind {
+    /// Returns the equivocation kind of the offence given its `Offence::ID`.
+    pub fn from_offence_id(id: &[u8; 16]) -> Option<Self> {
+        if *id == BABE_EQUIVOCATION_ID {
+            Some(Self::Babe)
+        } else if *id == GRANDPA_EQUIVOCATION_ID {
+            Some(Self::Grandpa)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the slash fraction applied when no severity is set for this kind.
+    pub fn default_severity(&self) -> Perbill {
+        match self {
+            Self::Babe => Perbill::from_percent(DEFAULT_BABE_EQUIVOCATION_SEVERITY),
+            Self::Grandpa => Perbill::from_percent(DEFAULT_GRANDPA_EQUIVOCATION_SEVERITY),
+        }
+    }
+}
+
 /// Struct for performing the slash.
 ///
 /// Abstracted for caching the treasury account.
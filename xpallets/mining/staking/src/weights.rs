@@ -44,6 +44,10 @@ pub trait WeightInfo {
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_bond_slashing() -> Weight;
+    fn set_slash_defer_duration() -> Weight;
+    fn cancel_deferred_slash(s: u32) -> Weight;
+    fn set_equivocation_severity() -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -107,6 +111,21 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_bond_slashing() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_equivocation_severity() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -169,4 +188,19 @@ impl WeightInfo for () {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_bond_slashing() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_equivocation_severity() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
@@ -1,5 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use sp_std::prelude::Vec;
+
 pub trait MultisigAddressFor<AccountId> {
     fn calc_multisig(accounts: &[AccountId], threshold: u16) -> AccountId;
 }
@@ -18,6 +20,9 @@ pub trait Validator<AccountId> {
     fn is_validator(who: &AccountId) -> bool;
 
     fn validator_for(name: &[u8]) -> Option<AccountId>;
+
+    /// Returns the active validators, sorted by the total nomination in descending order.
+    fn sorted_validators() -> Vec<AccountId>;
 }
 
 impl<AccountId> Validator<AccountId> for () {
@@ -28,6 +33,28 @@ impl<AccountId> Validator<AccountId> for () {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn sorted_validators() -> Vec<AccountId> {
+        Vec::new()
+    }
+}
+
+/// The handler for the start of a new staking era.
+pub trait OnNewEra {
+    fn on_new_era(era_index: u32);
+}
+
+impl OnNewEra for () {
+    fn on_new_era(_: u32) {}
+}
+
+/// Reports the offenders (e.g. the trustees missing too many withdrawal proposals) to staking.
+pub trait ReportOffenders<AccountId> {
+    fn report_offenders(offenders: &[AccountId]);
+}
+
+impl<AccountId> ReportOffenders<AccountId> for () {
+    fn report_offenders(_: &[AccountId]) {}
 }
 
 /// This trait provides a simple way to get the treasury account.